@@ -0,0 +1,107 @@
+use eyre::{anyhow as err, Result};
+use sha2::Digest;
+use starknet_core::types::Felt;
+
+use crate::{convert_hash_to_u32_array, convert_u256_words_to_felt, window::SECONDS_IN_HOUR};
+
+/// Aggregates consecutive blocks, given as `(number, timestamp, base_fee)`, into hourly
+/// `(timestamp, data_points, avg_fee)` records.
+///
+/// Only the hours strictly between the first and the last block's hour are aggregated: the
+/// first and last blocks bound the range, and since block numbers must be consecutive, every
+/// block of the aggregated hours is then known to be included. Every aggregated hour must
+/// contain at least one block.
+pub fn aggregate_hourly_base_fees(blocks: &[(u64, u64, u64)]) -> Result<Vec<(u64, u64, Felt)>> {
+    if blocks.len() < 3 {
+        return Err(err!(
+            "At least 3 blocks are needed to aggregate a full hour, got {}",
+            blocks.len()
+        ));
+    }
+
+    for pair in blocks.windows(2) {
+        let (number, timestamp, _) = pair[0];
+        let (next_number, next_timestamp, _) = pair[1];
+        if next_number != number + 1 {
+            return Err(err!(
+                "Blocks are not consecutive: block {} follows block {}",
+                next_number,
+                number
+            ));
+        }
+        if next_timestamp <= timestamp {
+            return Err(err!(
+                "Block {} has timestamp {}, not after the previous block's {}",
+                next_number,
+                next_timestamp,
+                timestamp
+            ));
+        }
+    }
+
+    let hour = SECONDS_IN_HOUR as u64;
+    let first_hour = blocks[0].1 - blocks[0].1 % hour + hour;
+    let end_hour = blocks[blocks.len() - 1].1 - blocks[blocks.len() - 1].1 % hour;
+    if first_hour >= end_hour {
+        return Err(err!(
+            "Blocks {} to {} do not span a full hour",
+            blocks[0].0,
+            blocks[blocks.len() - 1].0
+        ));
+    }
+
+    // a single pass over the blocks, skipping those of the first block's hour
+    let mut blocks = blocks
+        .iter()
+        .skip_while(|(_, timestamp, _)| *timestamp < first_hour)
+        .peekable();
+
+    let mut records = vec![];
+    for hour_timestamp in (first_hour..end_hour).step_by(hour as usize) {
+        let (mut sum, mut count) = (0u128, 0u64);
+        while let Some((_, _, base_fee)) =
+            blocks.next_if(|(_, timestamp, _)| *timestamp < hour_timestamp + hour)
+        {
+            sum += *base_fee as u128;
+            count += 1;
+        }
+        if count == 0 {
+            return Err(err!("Hour {} has no blocks", hour_timestamp));
+        }
+
+        records.push((hour_timestamp, count, average_to_fixed_point(sum, count)));
+    }
+
+    Ok(records)
+}
+
+/// `sum / count` as a `UFixedPoint123x128` felt, rounded down to a multiple of 2^-128.
+pub fn average_to_fixed_point(sum: u128, count: u64) -> Felt {
+    assert!(count > 0, "Cannot average over no blocks");
+
+    let count = count as u128;
+    let integer = sum / count;
+
+    // long division of the remainder, 64 fractional bits at a time
+    let remainder = sum % count;
+    let high_fraction = (remainder << 64) / count;
+    let remainder = (remainder << 64) % count;
+    let low_fraction = (remainder << 64) / count;
+
+    convert_u256_words_to_felt(integer, (high_fraction << 64) | low_fraction)
+}
+
+/// SHA-256 digest of `(number, timestamp, base_fee)` blocks, as big-endian u32 words. The
+/// block count is hashed as a little-endian u64 prefix, followed by the little-endian
+/// bytes of every field.
+pub fn hash_blocks(blocks: &[(u64, u64, u64)]) -> [u32; 8] {
+    let mut hasher = sha2::Sha256::new();
+    hasher.update((blocks.len() as u64).to_le_bytes());
+    for (number, timestamp, base_fee) in blocks {
+        hasher.update(number.to_le_bytes());
+        hasher.update(timestamp.to_le_bytes());
+        hasher.update(base_fee.to_le_bytes());
+    }
+
+    convert_hash_to_u32_array(&hasher.finalize())
+}
@@ -0,0 +1,258 @@
+use eyre::{anyhow as err, Result};
+
+use crate::{
+    floating_point::{
+        add_twap_7d, calculate_remove_seasonality, calculate_simulated_log_prices,
+        calculated_reserve_price_for_round, score_settlement_twap, simulate_price_with_model,
+        summarize_forecast_scores, FeeModelKind, ForecastScore, ForecastScoreSummary, PayoffKind,
+        SCORED_QUANTILE_LEVELS,
+    },
+    window::{round_horizon, SettlementSpec, SECONDS_IN_HOUR},
+};
+
+/// How the backtest walks forward over the fee history: every round is priced on the
+/// `history_hours` before it, and the next round starts `step_hours` later.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BacktestConfig {
+    pub history_hours: usize,
+    pub round_hours: usize,
+    pub step_hours: usize,
+    pub settlement: SettlementSpec,
+    pub fee_model: FeeModelKind,
+    pub payoff: PayoffKind,
+    pub num_paths: usize,
+    /// Quantile levels of the pinball loss of the settlement TWAP forecasts.
+    pub quantile_levels: Vec<f64>,
+    /// Bins of the PIT histogram of the summary.
+    pub pit_bins: usize,
+}
+
+impl Default for BacktestConfig {
+    /// Consecutive 30-day rounds priced on the 90 days before them, the 2160 hours the POC
+    /// fits the fee model on.
+    fn default() -> Self {
+        BacktestConfig {
+            history_hours: 2160,
+            round_hours: 720,
+            step_hours: 720,
+            settlement: SettlementSpec::default(),
+            fee_model: FeeModelKind::default(),
+            payoff: PayoffKind::default(),
+            num_paths: 4000,
+            quantile_levels: SCORED_QUANTILE_LEVELS.to_vec(),
+            pit_bins: 10,
+        }
+    }
+}
+
+/// One round of the backtest: what the model priced, and what the option actually paid.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BacktestRound {
+    pub round_start_timestamp: i64,
+    pub round_end_timestamp: i64,
+    /// Last 7-day TWAP before the round.
+    pub strike: f64,
+    /// Premium the vault sells the option for.
+    pub reserve_price: f64,
+    /// Share of the simulated paths on which the option pays out.
+    pub payout_probability: f64,
+    /// Mean simulated payout, before discounting.
+    pub expected_payout: f64,
+    /// TWAP of the realized fees over the settlement window.
+    pub settlement_twap: f64,
+    /// Payoff of the option on the realized fees.
+    pub payout: f64,
+    /// Premium less payout, for the vault selling the option.
+    pub pnl: f64,
+    /// Score of the simulated settlement TWAPs against `settlement_twap`.
+    pub score: ForecastScore,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BacktestSummary {
+    pub rounds: usize,
+    pub total_premium: f64,
+    pub total_payout: f64,
+    pub total_pnl: f64,
+    pub mean_pnl: f64,
+    pub worst_pnl: f64,
+    /// Share of the rounds that paid out.
+    pub hit_rate: f64,
+    /// Hit rate the model expected, the mean payout probability of the rounds.
+    pub mean_payout_probability: f64,
+    /// Mean squared difference between the payout probability and whether a round paid
+    /// out. 0 is perfect, and 0.25 is no better than a coin flip.
+    pub brier_score: f64,
+    /// Realized payouts over the payouts the model expected; 1 when calibrated.
+    pub payout_ratio: f64,
+    pub forecast: ForecastScoreSummary,
+}
+
+/// Prices the round `[round_start_timestamp, round_start_timestamp + round_fees.len()
+/// hours)` on the hourly `history` right before it, and settles it on `round_fees`.
+pub fn backtest_round(
+    history: &[f64],
+    round_fees: &[f64],
+    round_start_timestamp: i64,
+    config: &BacktestConfig,
+) -> Result<BacktestRound> {
+    let history_start_timestamp = round_start_timestamp - history.len() as i64 * SECONDS_IN_HOUR;
+    let round_end_timestamp = round_start_timestamp + round_fees.len() as i64 * SECONDS_IN_HOUR;
+    let horizon = round_horizon(
+        round_start_timestamp,
+        round_end_timestamp,
+        &config.settlement,
+    )?;
+    let history = history.to_vec();
+
+    let twap_7d = add_twap_7d(&history)?;
+    let (slope, intercept, de_seasonalised_detrended_log_base_fee, season_param) =
+        calculate_remove_seasonality(&history)?;
+    let (de_seasonalized_detrended_simulated_prices, _params) = simulate_price_with_model(
+        config.fee_model,
+        &de_seasonalised_detrended_log_base_fee,
+        config.num_paths,
+        horizon.n_periods,
+    )?;
+    let simulated_log_prices = calculate_simulated_log_prices(
+        history_start_timestamp,
+        // timestamp of the last hour of history, as in `calculate_reserve_price`
+        round_start_timestamp - SECONDS_IN_HOUR,
+        &season_param,
+        &de_seasonalized_detrended_simulated_prices,
+        &twap_7d,
+        slope,
+        intercept,
+        history.len(),
+        config.num_paths,
+        horizon.n_periods,
+    )?;
+    let reserve_price = calculated_reserve_price_for_round(
+        &simulated_log_prices,
+        &twap_7d,
+        &horizon,
+        &config.payoff,
+    )?;
+
+    let strike = *twap_7d.last().ok_or_else(|| err!("The series is empty"))?;
+    let payoff = config.payoff.payoff();
+    let simulated_payouts = simulated_log_prices
+        .column_iter()
+        .map(|path| {
+            let settlement_prices = path
+                .iter()
+                .skip(horizon.settlement_start)
+                .map(|log_price| log_price.exp())
+                .collect::<Vec<_>>();
+            payoff.payoff(&settlement_prices, strike)
+        })
+        .collect::<Vec<_>>();
+
+    let settlement_prices = &round_fees[horizon.settlement_start..];
+    let payout = payoff.payoff(settlement_prices, strike);
+    let score = score_settlement_twap(
+        &simulated_log_prices,
+        round_fees,
+        &horizon,
+        &config.quantile_levels,
+    )?;
+
+    Ok(BacktestRound {
+        round_start_timestamp,
+        round_end_timestamp,
+        strike,
+        reserve_price,
+        payout_probability: simulated_payouts
+            .iter()
+            .filter(|payout| **payout > 0.0)
+            .count() as f64
+            / simulated_payouts.len() as f64,
+        expected_payout: simulated_payouts.iter().sum::<f64>() / simulated_payouts.len() as f64,
+        settlement_twap: settlement_prices.iter().sum::<f64>() / settlement_prices.len() as f64,
+        payout,
+        pnl: reserve_price - payout,
+        score,
+    })
+}
+
+/// Walks forward over the hourly `fees` starting at `start_timestamp`, backtesting every
+/// round that has `config.history_hours` of history before it and is fully realized. The
+/// windows are hour offsets rather than the calendar months of
+/// `split_dataframe_into_periods`, so that rounds and their history have the exact hour
+/// counts `round_horizon` prices, without the `original` feature's polars data frames.
+pub fn run_backtest(
+    fees: &[f64],
+    start_timestamp: i64,
+    config: &BacktestConfig,
+) -> Result<Vec<BacktestRound>> {
+    if config.step_hours == 0 {
+        return Err(err!("Rounds must be at least an hour apart"));
+    }
+    let round_length = config.history_hours + config.round_hours;
+    if fees.len() < round_length {
+        return Err(err!(
+            "A round needs {} hours of history and fees, got {}",
+            round_length,
+            fees.len()
+        ));
+    }
+
+    (0..=fees.len() - round_length)
+        .step_by(config.step_hours)
+        .map(|history_start| {
+            let round_start = history_start + config.history_hours;
+            backtest_round(
+                &fees[history_start..round_start],
+                &fees[round_start..round_start + config.round_hours],
+                start_timestamp + round_start as i64 * SECONDS_IN_HOUR,
+                config,
+            )
+        })
+        .collect()
+}
+
+pub fn summarize_backtest(rounds: &[BacktestRound], pit_bins: usize) -> Result<BacktestSummary> {
+    if rounds.is_empty() {
+        return Err(err!("Cannot summarize a backtest of no rounds"));
+    }
+    let n = rounds.len() as f64;
+    let sum = |value: fn(&BacktestRound) -> f64| rounds.iter().map(value).sum::<f64>();
+
+    let total_payout = sum(|round| round.payout);
+    let expected_payout = sum(|round| round.expected_payout);
+
+    Ok(BacktestSummary {
+        rounds: rounds.len(),
+        total_premium: sum(|round| round.reserve_price),
+        total_payout,
+        total_pnl: sum(|round| round.pnl),
+        mean_pnl: sum(|round| round.pnl) / n,
+        worst_pnl: rounds
+            .iter()
+            .map(|round| round.pnl)
+            .fold(f64::INFINITY, f64::min),
+        hit_rate: sum(hit) / n,
+        mean_payout_probability: sum(|round| round.payout_probability) / n,
+        brier_score: sum(|round| (round.payout_probability - hit(round)).powi(2)) / n,
+        payout_ratio: if expected_payout > 0.0 {
+            total_payout / expected_payout
+        } else {
+            f64::NAN
+        },
+        forecast: summarize_forecast_scores(
+            &rounds
+                .iter()
+                .map(|round| round.score.clone())
+                .collect::<Vec<_>>(),
+            pit_bins,
+        )?,
+    })
+}
+
+fn hit(round: &BacktestRound) -> f64 {
+    if round.payout > 0.0 {
+        1.0
+    } else {
+        0.0
+    }
+}
@@ -0,0 +1,356 @@
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::Path,
+    sync::Arc,
+};
+
+use arrow_array::{Array, ArrayRef, Float64Array, RecordBatch, RecordBatchReader};
+use arrow_ipc::{reader::FileReader, writer::FileWriter};
+use arrow_schema::{DataType, Field, Schema};
+use eyre::{anyhow as err, Result};
+use nalgebra::DMatrix;
+use parquet::arrow::{arrow_reader::ParquetRecordBatchReaderBuilder, ArrowWriter};
+
+use crate::floating_point::MrjSimulation;
+
+/// Paths converted to Arrow and written at a time, so that a 720 by 15000 matrix is never
+/// copied whole.
+pub const PATHS_PER_BATCH: usize = 1024;
+
+const NPY_MAGIC: &[u8] = b"\x93NUMPY";
+
+/// File format of an exported `n_periods` by `num_paths` matrix of simulated paths. Every
+/// format stores one row per path and one column per simulated hour, so a notebook loads
+/// it as a `num_paths` by `n_periods` table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnarFormat {
+    /// Parquet, one `h<hour>` float column per hour.
+    Parquet,
+    /// Arrow IPC file (Feather v2), with the same columns as Parquet.
+    ArrowIpc,
+    /// NumPy `.npy` array of little-endian f64 of shape `(num_paths, n_periods)`.
+    Npy,
+}
+
+impl ColumnarFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ColumnarFormat::Parquet => "parquet",
+            ColumnarFormat::ArrowIpc => "arrow",
+            ColumnarFormat::Npy => "npy",
+        }
+    }
+}
+
+/// Writes paths of `n_periods` hours to a file batch by batch, so that a simulation can be
+/// exported as its paths are simulated. The file is only complete once `finish` returns.
+pub struct PathWriter {
+    file_name: String,
+    n_periods: usize,
+    num_paths: usize,
+    sink: PathSink,
+}
+
+enum PathSink {
+    Parquet(ArrowWriter<File>, Arc<Schema>),
+    ArrowIpc(FileWriter<BufWriter<File>>, Arc<Schema>),
+    Npy(BufWriter<File>),
+}
+
+impl PathWriter {
+    pub fn open(file_name: &str, n_periods: usize, format: ColumnarFormat) -> Result<Self> {
+        let file = File::create(file_name)
+            .map_err(|e| err!("Cannot create path file '{}': {}", file_name, e))?;
+
+        let sink = match format {
+            ColumnarFormat::Parquet => {
+                let schema = paths_schema(n_periods);
+                PathSink::Parquet(ArrowWriter::try_new(file, schema.clone(), None)?, schema)
+            }
+            ColumnarFormat::ArrowIpc => {
+                let schema = paths_schema(n_periods);
+                PathSink::ArrowIpc(FileWriter::try_new(BufWriter::new(file), &schema)?, schema)
+            }
+            ColumnarFormat::Npy => {
+                // the number of paths is only known at `finish`, which patches it in
+                let mut writer = BufWriter::new(file);
+                writer.write_all(&npy_preamble(0, n_periods)?)?;
+                PathSink::Npy(writer)
+            }
+        };
+
+        Ok(PathWriter {
+            file_name: file_name.to_string(),
+            n_periods,
+            num_paths: 0,
+            sink,
+        })
+    }
+
+    /// Appends `paths`, an `n_periods` by `num_paths` matrix with one column per path.
+    pub fn write_batch(&mut self, paths: &DMatrix<f64>) -> Result<()> {
+        if paths.nrows() != self.n_periods {
+            return Err(err!(
+                "Expected paths of {} hours for '{}', got {}",
+                self.n_periods,
+                self.file_name,
+                paths.nrows()
+            ));
+        }
+
+        match &mut self.sink {
+            PathSink::Parquet(writer, schema) => {
+                for batch in path_batches(paths, schema) {
+                    writer.write(&batch?)?;
+                }
+            }
+            PathSink::ArrowIpc(writer, schema) => {
+                for batch in path_batches(paths, schema) {
+                    writer.write(&batch?)?;
+                }
+            }
+            // the column-major matrix is the row-major (num_paths, n_periods) array
+            PathSink::Npy(writer) => {
+                for value in paths.iter() {
+                    writer.write_all(&value.to_le_bytes())?;
+                }
+            }
+        }
+        self.num_paths += paths.ncols();
+
+        Ok(())
+    }
+
+    pub fn finish(self) -> Result<()> {
+        match self.sink {
+            PathSink::Parquet(writer, _) => {
+                writer.close()?;
+            }
+            PathSink::ArrowIpc(mut writer, _) => writer.finish()?,
+            PathSink::Npy(mut writer) => {
+                writer.seek(SeekFrom::Start(0))?;
+                writer.write_all(&npy_preamble(self.num_paths, self.n_periods)?)?;
+                writer.flush()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes `paths` (one column per path, as simulated) to `file_name`.
+pub fn write_paths(file_name: &str, paths: &DMatrix<f64>, format: ColumnarFormat) -> Result<()> {
+    let mut writer = PathWriter::open(file_name, paths.nrows(), format)?;
+    writer.write_batch(paths)?;
+    writer.finish()
+}
+
+/// Reads paths written by `write_paths` back into an `n_periods` by `num_paths` matrix.
+pub fn read_paths(file_name: &str, format: ColumnarFormat) -> Result<DMatrix<f64>> {
+    let file =
+        File::open(file_name).map_err(|e| err!("Cannot open path file '{}': {}", file_name, e))?;
+
+    match format {
+        ColumnarFormat::Parquet => {
+            read_path_batches(ParquetRecordBatchReaderBuilder::try_new(file)?.build()?)
+        }
+        ColumnarFormat::ArrowIpc => read_path_batches(FileReader::try_new(file, None)?),
+        ColumnarFormat::Npy => read_npy(BufReader::new(file)),
+    }
+}
+
+/// Writes the simulated prices, jump indicators and normal draws of an MRJ simulation to
+/// `simulated_prices`, `jumps`, `n1` and `n2` files in `dir`, batch by batch.
+pub struct MrjSimulationWriter {
+    simulated_prices: PathWriter,
+    jumps: PathWriter,
+    n1: PathWriter,
+    n2: PathWriter,
+}
+
+impl MrjSimulationWriter {
+    pub fn open(dir: &str, n_periods: usize, format: ColumnarFormat) -> Result<Self> {
+        fs::create_dir_all(dir).map_err(|e| err!("Cannot create directory '{}': {}", dir, e))?;
+        let open =
+            |name| PathWriter::open(&simulation_file_name(dir, name, format), n_periods, format);
+
+        Ok(MrjSimulationWriter {
+            simulated_prices: open("simulated_prices")?,
+            jumps: open("jumps")?,
+            n1: open("n1")?,
+            n2: open("n2")?,
+        })
+    }
+
+    /// Appends the paths of `simulation`.
+    pub fn write_batch(&mut self, simulation: &MrjSimulation) -> Result<()> {
+        self.simulated_prices
+            .write_batch(&simulation.simulated_prices)?;
+        self.jumps.write_batch(&simulation.jumps)?;
+        self.n1.write_batch(&simulation.n1)?;
+        self.n2.write_batch(&simulation.n2)
+    }
+
+    pub fn finish(self) -> Result<()> {
+        self.simulated_prices.finish()?;
+        self.jumps.finish()?;
+        self.n1.finish()?;
+        self.n2.finish()
+    }
+}
+
+/// Writes the simulated prices, jump indicators and normal draws of `simulation` to
+/// `simulated_prices`, `jumps`, `n1` and `n2` files in `dir`.
+pub fn write_mrj_simulation(
+    dir: &str,
+    simulation: &MrjSimulation,
+    format: ColumnarFormat,
+) -> Result<()> {
+    let mut writer = MrjSimulationWriter::open(dir, simulation.simulated_prices.nrows(), format)?;
+    writer.write_batch(simulation)?;
+    writer.finish()
+}
+
+pub fn read_mrj_simulation(dir: &str, format: ColumnarFormat) -> Result<MrjSimulation> {
+    let read = |name| read_paths(&simulation_file_name(dir, name, format), format);
+
+    Ok(MrjSimulation {
+        simulated_prices: read("simulated_prices")?,
+        jumps: read("jumps")?,
+        n1: read("n1")?,
+        n2: read("n2")?,
+    })
+}
+
+fn simulation_file_name(dir: &str, name: &str, format: ColumnarFormat) -> String {
+    Path::new(dir)
+        .join(format!("{}.{}", name, format.extension()))
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn paths_schema(n_periods: usize) -> Arc<Schema> {
+    Arc::new(Schema::new(
+        (0..n_periods)
+            .map(|hour| Field::new(format!("h{}", hour), DataType::Float64, false))
+            .collect::<Vec<_>>(),
+    ))
+}
+
+// PATHS_PER_BATCH paths at a time, one row per path
+fn path_batches<'a>(
+    paths: &'a DMatrix<f64>,
+    schema: &'a Arc<Schema>,
+) -> impl Iterator<Item = Result<RecordBatch>> + 'a {
+    (0..paths.ncols())
+        .step_by(PATHS_PER_BATCH)
+        .map(move |first_path| {
+            let batch_paths = first_path..(first_path + PATHS_PER_BATCH).min(paths.ncols());
+            let columns = (0..paths.nrows())
+                .map(|hour| {
+                    Arc::new(Float64Array::from_iter_values(
+                        batch_paths.clone().map(|path| paths[(hour, path)]),
+                    )) as ArrayRef
+                })
+                .collect::<Vec<_>>();
+
+            Ok(RecordBatch::try_new(schema.clone(), columns)?)
+        })
+}
+
+fn read_path_batches<R: RecordBatchReader>(reader: R) -> Result<DMatrix<f64>> {
+    let n_periods = reader.schema().fields().len();
+
+    // column-major with one column per path, as in the matrix
+    let mut values = vec![];
+    for batch in reader {
+        let batch = batch?;
+        let columns = batch
+            .columns()
+            .iter()
+            .map(|column| {
+                column
+                    .as_any()
+                    .downcast_ref::<Float64Array>()
+                    .ok_or_else(|| err!("Expected f64 columns, got {}", column.data_type()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for path in 0..batch.num_rows() {
+            values.extend(columns.iter().map(|column| column.value(path)));
+        }
+    }
+
+    let num_paths = values.len().checked_div(n_periods).unwrap_or(0);
+    Ok(DMatrix::from_vec(n_periods, num_paths, values))
+}
+
+// NPY version 1.0 magic, version and header, padded so that the data starts on a 64 byte
+// boundary. The header is as long for any number of paths, so that it can be patched in
+// place once every path is written.
+fn npy_preamble(num_paths: usize, n_periods: usize) -> Result<Vec<u8>> {
+    let header = |num_paths: usize| {
+        format!(
+            "{{'descr': '<f8', 'fortran_order': False, 'shape': ({}, {}), }}",
+            num_paths, n_periods
+        )
+    };
+    let unpadded_length = NPY_MAGIC.len() + 2 + 2 + header(usize::MAX).len() + 1;
+    let header_length = unpadded_length + (64 - unpadded_length % 64) % 64 - NPY_MAGIC.len() - 4;
+
+    let mut header = header(num_paths);
+    header.push_str(&" ".repeat(header_length - 1 - header.len()));
+    header.push('\n');
+
+    let mut preamble = NPY_MAGIC.to_vec();
+    preamble.extend_from_slice(&[1, 0]);
+    preamble.extend_from_slice(&u16::try_from(header.len())?.to_le_bytes());
+    preamble.extend_from_slice(header.as_bytes());
+
+    Ok(preamble)
+}
+
+fn read_npy<R: Read>(mut reader: R) -> Result<DMatrix<f64>> {
+    let mut preamble = [0u8; 10];
+    reader.read_exact(&mut preamble)?;
+    if &preamble[..6] != NPY_MAGIC || preamble[6] != 1 {
+        return Err(err!("Not an NPY version 1 file"));
+    }
+
+    let mut header = vec![0u8; u16::from_le_bytes([preamble[8], preamble[9]]) as usize];
+    reader.read_exact(&mut header)?;
+    let header = String::from_utf8(header)?;
+    if !header.contains("'descr': '<f8'") || !header.contains("'fortran_order': False") {
+        return Err(err!(
+            "Expected a C-ordered f64 array, got {}",
+            header.trim()
+        ));
+    }
+
+    let shape = header
+        .split("'shape': (")
+        .nth(1)
+        .and_then(|rest| rest.split(')').next())
+        .ok_or_else(|| err!("No shape in NPY header {}", header.trim()))?
+        .split(',')
+        .map(str::trim)
+        .filter(|dimension| !dimension.is_empty())
+        .map(|dimension| dimension.parse::<usize>())
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let [num_paths, n_periods] = shape[..] else {
+        return Err(err!(
+            "Expected a 2-dimensional array, got shape {:?}",
+            shape
+        ));
+    };
+
+    let mut values = Vec::with_capacity(num_paths * n_periods);
+    let mut value = [0u8; 8];
+    for _ in 0..num_paths * n_periods {
+        reader.read_exact(&mut value)?;
+        values.push(f64::from_le_bytes(value));
+    }
+
+    Ok(DMatrix::from_vec(n_periods, num_paths, values))
+}
@@ -2,6 +2,8 @@ use std::fs::File;
 
 use csv::Writer;
 
+use crate::backtest::BacktestRound;
+
 pub fn open_csv_writer(file_name: &str) -> Writer<File> {
     let wtr = Writer::from_path(file_name).unwrap();
     wtr
@@ -78,3 +80,45 @@ pub fn write_reserve_price_to_csv(
         ],
     );
 }
+
+pub fn open_backtest_csv_writer(file_name: &str, quantile_levels: &[f64]) -> Writer<File> {
+    let mut wtr = Writer::from_path(file_name).unwrap();
+    let mut header = vec![
+        "round_start_timestamp".to_owned(),
+        "round_end_timestamp".to_owned(),
+        "strike".to_owned(),
+        "reserve_price".to_owned(),
+        "payout_probability".to_owned(),
+        "expected_payout".to_owned(),
+        "settlement_twap".to_owned(),
+        "payout".to_owned(),
+        "pnl".to_owned(),
+        "crps".to_owned(),
+        "pit".to_owned(),
+    ];
+    header.extend(
+        quantile_levels
+            .iter()
+            .map(|level| format!("pinball_q{}", level)),
+    );
+    write_to_csv(&mut wtr, &header);
+    wtr
+}
+
+pub fn write_backtest_round_to_csv(wtr: &mut Writer<File>, round: &BacktestRound) {
+    let mut record = vec![
+        round.round_start_timestamp.to_string(),
+        round.round_end_timestamp.to_string(),
+        round.strike.to_string(),
+        round.reserve_price.to_string(),
+        round.payout_probability.to_string(),
+        round.expected_payout.to_string(),
+        round.settlement_twap.to_string(),
+        round.payout.to_string(),
+        round.pnl.to_string(),
+        round.score.crps.to_string(),
+        round.score.pit.to_string(),
+    ];
+    record.extend(round.score.pinball_losses.iter().map(f64::to_string));
+    write_to_csv(wtr, &record);
+}
@@ -1,4 +1,7 @@
 pub mod csv;
 
+#[cfg(feature = "columnar")]
+pub mod columnar;
+
 #[cfg(feature = "original")]
 pub mod dataframe;
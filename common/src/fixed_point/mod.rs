@@ -0,0 +1,142 @@
+use eyre::{anyhow as err, Result};
+use starknet_core::types::{Felt, U256};
+
+// Fees are stored as Cairo `UFixedPoint123x128` felts: read as a u256, the high 128 bits
+// hold the integer part, of which at most 123 bits are used, and the low 128 bits the
+// fractional part in units of 2^-128.
+pub const FIXED_POINT_INTEGER_BITS: u32 = 123;
+pub const FIXED_POINT_FRACTIONAL_BITS: u32 = 128;
+
+/// A fixed point felt decoded to the nearest f64. `error_bound` bounds `|felt - value|`,
+/// and is 0 when the conversion is exact.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CheckedF64 {
+    pub value: f64,
+    pub error_bound: f64,
+}
+
+/// Decodes a fixed point felt to the nearest f64, without checking the integer part range.
+pub fn convert_felt_to_f64(input: Felt) -> f64 {
+    let input_u256 = U256::from(input);
+    fixed_point_to_f64(input_u256.high(), input_u256.low())
+}
+
+/// Decodes a `UFixedPoint123x128` felt to the nearest f64, rejecting felts whose integer part
+/// does not fit in 123 bits.
+pub fn convert_felt_to_f64_checked(input: Felt) -> Result<CheckedF64> {
+    let input_u256 = U256::from(input);
+    if input_u256.high() >> FIXED_POINT_INTEGER_BITS != 0 {
+        return Err(err!(
+            "Felt {:#x} is not a UFixedPoint123x128: integer part exceeds {} bits",
+            input,
+            FIXED_POINT_INTEGER_BITS
+        ));
+    }
+
+    let value = fixed_point_to_f64(input_u256.high(), input_u256.low());
+
+    // rounding to nearest is off by at most half an ulp, which is within value * 2^-53
+    let error_bound = match convert_f64_to_felt(value) {
+        Ok(felt) if felt == input => 0.0,
+        _ => value * f64::EPSILON / 2.0,
+    };
+
+    Ok(CheckedF64 { value, error_bound })
+}
+
+/// Encodes an f64 as a `UFixedPoint123x128` felt. Fails if the value is negative, not finite,
+/// too large, or has bits below 2^-128, so that a successful conversion always round-trips
+/// through `convert_felt_to_f64` exactly.
+pub fn convert_f64_to_felt(value: f64) -> Result<Felt> {
+    if !value.is_finite() || value < 0.0 {
+        return Err(err!("{} is not a finite non-negative fee", value));
+    }
+    if value >= 2f64.powi(FIXED_POINT_INTEGER_BITS as i32) {
+        return Err(err!(
+            "{} does not fit the {} integer bits of UFixedPoint123x128",
+            value,
+            FIXED_POINT_INTEGER_BITS
+        ));
+    }
+    if value == 0.0 {
+        return Ok(Felt::ZERO);
+    }
+
+    // value = mantissa * 2^exponent, and the fixed point integer is mantissa * 2^shift
+    let bits = value.to_bits();
+    let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+    let mut mantissa = bits & ((1u64 << 52) - 1);
+    let exponent = if biased_exponent == 0 {
+        -1074
+    } else {
+        mantissa |= 1u64 << 52;
+        biased_exponent - 1075
+    };
+    let shift = exponent + FIXED_POINT_FRACTIONAL_BITS as i32;
+
+    let (high, low) = if shift < 0 {
+        let shift = (-shift) as u32;
+        if shift >= 64 || mantissa & ((1u64 << shift) - 1) != 0 {
+            return Err(err!(
+                "{} has bits below 2^-{} and cannot be represented exactly",
+                value,
+                FIXED_POINT_FRACTIONAL_BITS
+            ));
+        }
+        (0, (mantissa >> shift) as u128)
+    } else {
+        let shift = shift as u32;
+        if shift >= 128 {
+            ((mantissa as u128) << (shift - 128), 0)
+        } else if shift == 0 {
+            (0, mantissa as u128)
+        } else {
+            (
+                (mantissa as u128) >> (128 - shift),
+                (mantissa as u128) << shift,
+            )
+        }
+    };
+
+    Ok(convert_u256_words_to_felt(high, low))
+}
+
+/// Felt with `high` and `low` as the high and low 128 bits, i.e. the fixed point value
+/// `high + low / 2^128`. `high` must be below 2^123 for the felt to hold it unreduced.
+pub fn convert_u256_words_to_felt(high: u128, low: u128) -> Felt {
+    let mut bytes = [0u8; 32];
+    bytes[..16].copy_from_slice(&high.to_be_bytes());
+    bytes[16..].copy_from_slice(&low.to_be_bytes());
+    Felt::from_bytes_be(&bytes)
+}
+
+// Rounds the 256-bit fixed point value to the nearest f64 (ties to even). The top 64
+// significant bits are converted with every lower bit folded into a sticky bit, which
+// makes the u64 -> f64 rounding match that of the exact value.
+fn fixed_point_to_f64(high: u128, low: u128) -> f64 {
+    let bit_length = if high != 0 {
+        256 - high.leading_zeros()
+    } else {
+        128 - low.leading_zeros()
+    };
+    if bit_length <= 64 {
+        return low as u64 as f64 * 2f64.powi(-(FIXED_POINT_FRACTIONAL_BITS as i32));
+    }
+
+    let shift = bit_length - 64;
+    let (top, sticky) = if shift >= 128 {
+        let rest_shift = shift - 128;
+        (
+            high >> rest_shift,
+            low != 0 || (rest_shift > 0 && high & ((1u128 << rest_shift) - 1) != 0),
+        )
+    } else {
+        (
+            (high << (128 - shift)) | (low >> shift),
+            low & ((1u128 << shift) - 1) != 0,
+        )
+    };
+
+    let mantissa = top as u64 | sticky as u64;
+    mantissa as f64 * 2f64.powi(shift as i32 - FIXED_POINT_FRACTIONAL_BITS as i32)
+}
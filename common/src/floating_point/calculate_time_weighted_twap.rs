@@ -0,0 +1,95 @@
+use eyre::{anyhow as err, Result};
+
+/// Time-weighted average of `(timestamp, fee)` samples over `[samples[0].0, end_timestamp)`.
+/// Each fee holds from its own timestamp until the next sample's, and the last one until
+/// `end_timestamp`, so irregularly spaced samples are weighted by how long they were in effect.
+/// On evenly spaced samples it matches the arithmetic mean of `calculate_twap`.
+pub fn calculate_time_weighted_twap(samples: &[(i64, f64)], end_timestamp: i64) -> Result<f64> {
+    if samples.is_empty() {
+        return Err(err!("Cannot calculate a TWAP over no samples"));
+    }
+
+    let mut weighted_sum = 0.0;
+    for (i, (timestamp, fee)) in samples.iter().enumerate() {
+        let next_timestamp = samples.get(i + 1).map_or(end_timestamp, |(next, _)| *next);
+        if next_timestamp <= *timestamp {
+            return Err(err!(
+                "Sample at {} is not followed by a later timestamp, got {}",
+                timestamp,
+                next_timestamp
+            ));
+        }
+
+        weighted_sum += fee * (next_timestamp - timestamp) as f64;
+    }
+
+    Ok(weighted_sum / (end_timestamp - samples[0].0) as f64)
+}
+
+/// `(timestamp, fee)` samples of the base fees of consecutive `(number, timestamp, base_fee)`
+/// blocks over `[start_timestamp, end_timestamp)`, for `calculate_time_weighted_twap`.
+///
+/// The base fee in effect at `start_timestamp` is that of the last block at or before it,
+/// followed by every block up to `end_timestamp`. The first block must be at or before the
+/// start and the last one at or after the end: since block numbers must be consecutive, no
+/// block of the window is then left out.
+pub fn block_base_fee_samples(
+    blocks: &[(u64, u64, u64)],
+    start_timestamp: i64,
+    end_timestamp: i64,
+) -> Result<Vec<(i64, f64)>> {
+    if end_timestamp <= start_timestamp {
+        return Err(err!(
+            "Window end {} is not after its start {}",
+            end_timestamp,
+            start_timestamp
+        ));
+    }
+
+    for pair in blocks.windows(2) {
+        let (number, timestamp, _) = pair[0];
+        let (next_number, next_timestamp, _) = pair[1];
+        if next_number != number + 1 {
+            return Err(err!(
+                "Blocks are not consecutive: block {} follows block {}",
+                next_number,
+                number
+            ));
+        }
+        if next_timestamp <= timestamp {
+            return Err(err!(
+                "Block {} has timestamp {}, not after the previous block's {}",
+                next_number,
+                next_timestamp,
+                timestamp
+            ));
+        }
+    }
+
+    match (blocks.first(), blocks.last()) {
+        (Some((_, first_timestamp, _)), Some((_, last_timestamp, _)))
+            if *first_timestamp as i64 <= start_timestamp
+                && *last_timestamp as i64 >= end_timestamp => {}
+        _ => {
+            return Err(err!(
+                "Blocks do not cover the window [{}, {})",
+                start_timestamp,
+                end_timestamp
+            ))
+        }
+    }
+
+    let in_effect = blocks
+        .iter()
+        .rposition(|(_, timestamp, _)| *timestamp as i64 <= start_timestamp)
+        .unwrap();
+    let mut samples = vec![(start_timestamp, blocks[in_effect].2 as f64)];
+    samples.extend(
+        blocks[in_effect + 1..]
+            .iter()
+            .take_while(|(_, timestamp, _)| (*timestamp as i64) < end_timestamp)
+            .map(|(_, timestamp, base_fee)| (*timestamp as i64, *base_fee as f64)),
+    );
+
+    Ok(samples)
+}
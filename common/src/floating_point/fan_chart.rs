@@ -0,0 +1,153 @@
+use std::io::Write;
+
+use eyre::{anyhow as err, Result};
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+use super::{calculate_quantile, calculate_simulated_log_prices};
+use crate::window::{round_horizon, SettlementSpec, SECONDS_IN_HOUR};
+
+/// Quantiles of the usual fan chart: the median, the 50% band and the 90% band.
+pub const FAN_CHART_QUANTILES: [f64; 5] = [0.05, 0.25, 0.5, 0.75, 0.95];
+
+/// What the model expects of the fees over an option round, from the simulated paths.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct FanChart {
+    pub quantiles: Vec<f64>,
+    /// Start of each simulated hour of the round.
+    pub timestamps: Vec<i64>,
+    /// `fee_quantiles[t][q]` is the `quantiles[q]` quantile of the fee at `timestamps[t]`.
+    pub fee_quantiles: Vec<Vec<f64>>,
+    /// Quantiles of the TWAP over the settlement window of each path.
+    pub settlement_twap_quantiles: Vec<f64>,
+    pub settlement_twap_mean: f64,
+}
+
+/// Fan chart of the simulated log prices of the round `[round_start_timestamp,
+/// round_end_timestamp)`, one row per hour of the round.
+pub fn calculated_fan_chart_from_simulated_log_prices(
+    simulated_log_prices: &DMatrix<f64>,
+    round_start_timestamp: i64,
+    round_end_timestamp: i64,
+    settlement: &SettlementSpec,
+    quantiles: &[f64],
+) -> Result<FanChart> {
+    let horizon = round_horizon(round_start_timestamp, round_end_timestamp, settlement)?;
+    if simulated_log_prices.nrows() != horizon.n_periods {
+        return Err(err!(
+            "Expected {} simulated hours for the round, got {}",
+            horizon.n_periods,
+            simulated_log_prices.nrows()
+        ));
+    }
+    if simulated_log_prices.ncols() == 0 {
+        return Err(err!("Cannot draw a fan chart of no paths"));
+    }
+
+    let quantiles_of = |values: &[f64]| {
+        quantiles
+            .iter()
+            .map(|quantile| calculate_quantile(values, *quantile))
+            .collect::<Result<Vec<_>>>()
+    };
+
+    let simulated_prices = simulated_log_prices.map(f64::exp);
+    let fee_quantiles = simulated_prices
+        .row_iter()
+        .map(|hour| quantiles_of(&hour.iter().copied().collect::<Vec<_>>()))
+        .collect::<Result<Vec<_>>>()?;
+
+    let settlement_twaps = simulated_prices
+        .rows(horizon.settlement_start, settlement.twap_window_hours)
+        .row_mean()
+        .iter()
+        .copied()
+        .collect::<Vec<_>>();
+
+    Ok(FanChart {
+        quantiles: quantiles.to_vec(),
+        timestamps: (0..horizon.n_periods as i64)
+            .map(|hour| round_start_timestamp + hour * SECONDS_IN_HOUR)
+            .collect(),
+        fee_quantiles,
+        settlement_twap_quantiles: quantiles_of(&settlement_twaps)?,
+        settlement_twap_mean: settlement_twaps.iter().sum::<f64>() / settlement_twaps.len() as f64,
+    })
+}
+
+/// Simulates the round like `calculate_reserve_price` and draws its fan chart.
+pub fn calculate_fan_chart(
+    period_start_timestamp: i64,
+    period_end_timestamp: i64,
+    season_param: &DVector<f64>,
+    de_seasonalized_detrended_simulated_prices: &DMatrix<f64>,
+    twap_7d: &[f64],
+    slope: f64,
+    intercept: f64,
+    log_base_fee_len: usize,
+    num_paths: usize,
+    round_start_timestamp: i64,
+    round_end_timestamp: i64,
+    settlement: &SettlementSpec,
+    quantiles: &[f64],
+) -> Result<FanChart> {
+    let horizon = round_horizon(round_start_timestamp, round_end_timestamp, settlement)?;
+    let simulated_log_prices = calculate_simulated_log_prices(
+        period_start_timestamp,
+        period_end_timestamp,
+        season_param,
+        de_seasonalized_detrended_simulated_prices,
+        twap_7d,
+        slope,
+        intercept,
+        log_base_fee_len,
+        num_paths,
+        horizon.n_periods,
+    )?;
+
+    calculated_fan_chart_from_simulated_log_prices(
+        &simulated_log_prices,
+        round_start_timestamp,
+        round_end_timestamp,
+        settlement,
+        quantiles,
+    )
+}
+
+impl FanChart {
+    /// One row per hour, `timestamp` followed by a `q<quantile>` column per quantile.
+    pub fn write_csv<W: Write>(&self, writer: W) -> Result<()> {
+        let mut wtr = csv::Writer::from_writer(writer);
+        wtr.write_record(
+            std::iter::once("timestamp".to_owned()).chain(
+                self.quantiles
+                    .iter()
+                    .map(|quantile| format!("q{}", quantile)),
+            ),
+        )?;
+        for (timestamp, fees) in self.timestamps.iter().zip(&self.fee_quantiles) {
+            wtr.write_record(
+                std::iter::once(timestamp.to_string()).chain(fees.iter().map(f64::to_string)),
+            )?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    /// One row per quantile of the settlement TWAP, then its mean.
+    pub fn write_settlement_twap_csv<W: Write>(&self, writer: W) -> Result<()> {
+        let mut wtr = csv::Writer::from_writer(writer);
+        wtr.write_record(["quantile", "settlement_twap"])?;
+        for (quantile, twap) in self.quantiles.iter().zip(&self.settlement_twap_quantiles) {
+            wtr.write_record([quantile.to_string(), twap.to_string()])?;
+        }
+        wtr.write_record(["mean".to_owned(), self.settlement_twap_mean.to_string()])?;
+        wtr.flush()?;
+        Ok(())
+    }
+
+    pub fn write_json<W: Write>(&self, writer: W) -> Result<()> {
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+}
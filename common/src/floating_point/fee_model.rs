@@ -0,0 +1,650 @@
+use std::f64::consts::PI;
+
+use eyre::{anyhow as err, Result};
+use nalgebra::{DMatrix, DVector};
+use rand::{thread_rng, Rng, RngCore};
+use rand_distr::Distribution;
+use serde::{Deserialize, Serialize};
+use statrs::distribution::{Binomial, Normal};
+
+use crate::{is_saddle_point, Solution};
+
+use super::{gradient, minimize, neg_log_likelihood, MrjParams};
+
+/// Stochastic model of the de-seasonalised, detrended log base fee. Parameters are in
+/// hourly units and are fitted by minimizing the negative log likelihood of every `pt`
+/// given the previous hour's `pt_1`.
+pub trait FeeModel {
+    /// Starting point of the minimization, given the mean square of `pt`.
+    fn initial_params(&self, var_pt: f64) -> Vec<f64>;
+
+    /// Inclusive `(lower, upper)` bound of every parameter.
+    fn param_bounds(&self) -> Vec<(f64, f64)>;
+
+    fn neg_log_likelihood(&self, params: &[f64], pt: &DVector<f64>, pt_1: &DVector<f64>) -> f64;
+
+    /// `n_periods` hours of `num_paths` paths, each starting at `initial_value`, with the
+    /// random draws from `rng`.
+    fn simulate_with_rng(
+        &self,
+        params: &[f64],
+        initial_value: f64,
+        n_periods: usize,
+        num_paths: usize,
+        rng: &mut dyn RngCore,
+    ) -> Result<DMatrix<f64>>;
+
+    /// `simulate_with_rng` from the thread RNG.
+    fn simulate(
+        &self,
+        params: &[f64],
+        initial_value: f64,
+        n_periods: usize,
+        num_paths: usize,
+    ) -> Result<DMatrix<f64>> {
+        self.simulate_with_rng(
+            params,
+            initial_value,
+            n_periods,
+            num_paths,
+            &mut thread_rng(),
+        )
+    }
+
+    /// Minimizes the negative log likelihood from `initial_position`.
+    fn fit(
+        &self,
+        initial_position: Vec<f64>,
+        pt: &DVector<f64>,
+        pt_1: &DVector<f64>,
+        max_iterations: u64,
+    ) -> Solution<f64> {
+        fit_fee_model(self, initial_position, pt, pt_1, max_iterations)
+    }
+
+    /// Whether `params` are within bounds and a stationary point of the likelihood over
+    /// them, i.e. the result of a converged fit.
+    fn verify_params(
+        &self,
+        params: &[f64],
+        pt: &DVector<f64>,
+        pt_1: &DVector<f64>,
+        gradient_tolerance: f64,
+    ) -> bool {
+        let bounds = self.param_bounds();
+        params.len() == bounds.len()
+            && params
+                .iter()
+                .zip(&bounds)
+                .all(|(param, (lower, upper))| lower <= param && param <= upper)
+            && is_saddle_point(
+                &projected_fee_model_gradient(self, params, pt, pt_1),
+                gradient_tolerance,
+            )
+    }
+}
+
+/// Fee model proven by the simulation, as selected in the composition input.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FeeModelKind {
+    /// Mean-reverting diffusion with normally distributed jumps (`MrjModel`).
+    #[default]
+    MeanRevertingJump,
+    /// AR(1) whose intercept and variance switch between two Markov regimes
+    /// (`RegimeSwitchingModel`).
+    RegimeSwitching,
+    /// AR(1) with GARCH(1, 1) errors (`GarchModel`).
+    Garch,
+}
+
+impl FeeModelKind {
+    pub fn model(self) -> &'static dyn FeeModel {
+        match self {
+            FeeModelKind::MeanRevertingJump => &MrjModel,
+            FeeModelKind::RegimeSwitching => &RegimeSwitchingModel,
+            FeeModelKind::Garch => &GarchModel,
+        }
+    }
+}
+
+/// Central difference gradient of the negative log likelihood, one-sided at a bound. The
+/// step is relative to each parameter, so that variances of a few 1e-4 are differentiated
+/// as accurately as the fit is verified.
+pub fn fee_model_gradient<M: FeeModel + ?Sized>(
+    model: &M,
+    params: &[f64],
+    pt: &DVector<f64>,
+    pt_1: &DVector<f64>,
+) -> Vec<f64> {
+    let bounds = model.param_bounds();
+    let mut x = params.to_vec();
+    let current = model.neg_log_likelihood(params, pt, pt_1);
+
+    params
+        .iter()
+        .cloned()
+        .enumerate()
+        .map(|(i, x_i)| {
+            let h = if x_i == 0.0 {
+                f64::EPSILON * 1.0e10
+            } else {
+                f64::EPSILON.cbrt() * x_i.abs()
+            };
+            assert!(h.is_finite());
+
+            let (lower, upper) = bounds
+                .get(i)
+                .copied()
+                .unwrap_or((f64::NEG_INFINITY, f64::INFINITY));
+            let mut value_at = |x_i: f64| {
+                x[i] = x_i;
+                let value = model.neg_log_likelihood(&x, pt, pt_1);
+                x[i] = params[i];
+                value
+            };
+
+            let d_i = if x_i - h < lower {
+                (value_at(x_i + h) - current) / h
+            } else if x_i + h > upper {
+                (current - value_at(x_i - h)) / h
+            } else {
+                (value_at(x_i + h) - value_at(x_i - h)) / (2.0 * h)
+            };
+            assert!(d_i.is_finite());
+
+            d_i
+        })
+        .collect()
+}
+
+/// `fee_model_gradient` with the components that point past an active bound zeroed: at
+/// a bound, the likelihood may still decrease outwards, but not within the bounds.
+pub fn projected_fee_model_gradient<M: FeeModel + ?Sized>(
+    model: &M,
+    params: &[f64],
+    pt: &DVector<f64>,
+    pt_1: &DVector<f64>,
+) -> Vec<f64> {
+    fee_model_gradient(model, params, pt, pt_1)
+        .into_iter()
+        .zip(params)
+        .zip(model.param_bounds())
+        .map(|((g, x), (lower, upper))| {
+            if (*x <= lower && g > 0.0) || (*x >= upper && g < 0.0) {
+                0.0
+            } else {
+                g
+            }
+        })
+        .collect()
+}
+
+// Armijo line search as in `simulate_price`, keeping every step within the bounds. Returns
+// `None` once the step is too small to decrease the likelihood.
+fn search<M: FeeModel + ?Sized>(
+    model: &M,
+    initial_position: &[f64],
+    initial_value: f64,
+    gradient: &[f64],
+    direction: &[f64],
+    pt: &DVector<f64>,
+    pt_1: &DVector<f64>,
+) -> Option<Vec<f64>> {
+    let bounds = model.param_bounds();
+    let control_parameter = 0.5;
+    let decay_factor = 0.5;
+
+    let m = gradient
+        .iter()
+        .zip(direction)
+        .map(|(g, d)| g * d)
+        .sum::<f64>();
+    let t = -control_parameter * m;
+    if t <= 0.0 {
+        return None;
+    }
+
+    let mut step_width = 1.0;
+    while step_width > f64::EPSILON {
+        let position = initial_position
+            .iter()
+            .zip(direction)
+            .zip(&bounds)
+            .map(|((x, d), (lower, upper))| (x + step_width * d).clamp(*lower, *upper))
+            .collect::<Vec<_>>();
+
+        if model.neg_log_likelihood(&position, pt, pt_1) <= initial_value - step_width * t {
+            return Some(position);
+        }
+
+        step_width *= decay_factor;
+    }
+
+    None
+}
+
+/// Fits `model` by projected Newton steps from `initial_position`, stopping at a stationary
+/// point, when no step within the bounds improves the likelihood, or after `max_iterations`.
+///
+/// Plain gradient descent stalls on these likelihoods, whose curvature differs by orders of
+/// magnitude between the parameters, long before the gradient is within the tolerance the
+/// fit is verified at.
+pub fn fit_fee_model<M: FeeModel + ?Sized>(
+    model: &M,
+    initial_position: Vec<f64>,
+    pt: &DVector<f64>,
+    pt_1: &DVector<f64>,
+    max_iterations: u64,
+) -> Solution<f64> {
+    let gradient_tolerance = 1.0e-4;
+
+    let mut position = initial_position;
+    let mut value = model.neg_log_likelihood(&position, pt, pt_1);
+    for _ in 0..max_iterations {
+        // descend, except along parameters held at a bound the gradient points past
+        let gradient = projected_fee_model_gradient(model, &position, pt, pt_1);
+        if is_saddle_point(&gradient, gradient_tolerance) {
+            break;
+        }
+        let direction = newton_direction(model, &position, &gradient, pt, pt_1)
+            .unwrap_or_else(|| gradient.iter().map(|g| -g).collect());
+
+        match search(model, &position, value, &gradient, &direction, pt, pt_1) {
+            Some(next) => {
+                position = next;
+                value = model.neg_log_likelihood(&position, pt, pt_1);
+            }
+            None => break,
+        }
+    }
+
+    Solution::new(position, value)
+}
+
+// Newton step over the parameters the projected `gradient` leaves free, with the Hessian
+// from differences of `fee_model_gradient`. `None` where the Hessian is not positive
+// definite, i.e. away from a minimum.
+fn newton_direction<M: FeeModel + ?Sized>(
+    model: &M,
+    params: &[f64],
+    gradient: &[f64],
+    pt: &DVector<f64>,
+    pt_1: &DVector<f64>,
+) -> Option<Vec<f64>> {
+    let bounds = model.param_bounds();
+    let free = (0..params.len())
+        .filter(|i| gradient[*i] != 0.0)
+        .collect::<Vec<_>>();
+
+    let current = fee_model_gradient(model, params, pt, pt_1);
+    let mut hessian = DMatrix::zeros(free.len(), free.len());
+    let mut x = params.to_vec();
+    for (column, j) in free.iter().enumerate() {
+        let h = 1.0e-5 * params[*j].abs().max(1.0e-4);
+        let h = if params[*j] + h > bounds[*j].1 { -h } else { h };
+
+        x[*j] = params[*j] + h;
+        let step = fee_model_gradient(model, &x, pt, pt_1);
+        x[*j] = params[*j];
+
+        for (row, i) in free.iter().enumerate() {
+            hessian[(row, column)] = (step[*i] - current[*i]) / h;
+        }
+    }
+    let hessian = (&hessian + hessian.transpose()) / 2.0;
+
+    let free_gradient = DVector::from_iterator(free.len(), free.iter().map(|i| gradient[*i]));
+    let step = hessian.cholesky()?.solve(&-free_gradient);
+
+    let mut direction = vec![0.0; params.len()];
+    for (row, i) in free.iter().enumerate() {
+        direction[*i] = step[row];
+    }
+    direction.iter().all(|d| d.is_finite()).then_some(direction)
+}
+
+/// Mean-reverting jump diffusion, with parameters `[a, phi, mu_j, sigma^2, sigma_j^2, lambda]`
+/// of `pt = a + phi * pt_1 + e`, where `e` is `N(0, sigma^2)` plus, with probability
+/// `lambda`, a jump `N(mu_j, sigma_j^2)`. See `MrjParams` for the annualized parameters.
+///
+/// It is fitted and verified exactly as in the original POC, by `simulate_price::minimize`
+/// and the unbounded `simulate_price::gradient`, so that existing MRJ fits and positions
+/// stay valid. Its bounds are only those of the parameters' meaning.
+pub struct MrjModel;
+
+impl FeeModel for MrjModel {
+    fn initial_params(&self, var_pt: f64) -> Vec<f64> {
+        vec![-3.928e-02, 2.873e-04, 4.617e-02, var_pt, var_pt, 0.2]
+    }
+
+    fn param_bounds(&self) -> Vec<(f64, f64)> {
+        vec![
+            (f64::NEG_INFINITY, f64::INFINITY),
+            (f64::NEG_INFINITY, f64::INFINITY),
+            (f64::NEG_INFINITY, f64::INFINITY),
+            (0.0, f64::INFINITY),
+            (0.0, f64::INFINITY),
+            (0.0, 1.0),
+        ]
+    }
+
+    fn neg_log_likelihood(&self, params: &[f64], pt: &DVector<f64>, pt_1: &DVector<f64>) -> f64 {
+        neg_log_likelihood(params, pt, pt_1)
+    }
+
+    fn fit(
+        &self,
+        initial_position: Vec<f64>,
+        pt: &DVector<f64>,
+        pt_1: &DVector<f64>,
+        max_iterations: u64,
+    ) -> Solution<f64> {
+        minimize(initial_position, pt, pt_1, max_iterations)
+    }
+
+    fn verify_params(
+        &self,
+        params: &[f64],
+        pt: &DVector<f64>,
+        pt_1: &DVector<f64>,
+        gradient_tolerance: f64,
+    ) -> bool {
+        is_saddle_point(&gradient(params, pt, pt_1), gradient_tolerance)
+    }
+
+    fn simulate_with_rng(
+        &self,
+        params: &[f64],
+        initial_value: f64,
+        n_periods: usize,
+        num_paths: usize,
+        rng: &mut dyn RngCore,
+    ) -> Result<DMatrix<f64>> {
+        self.simulate_with_draws(params, initial_value, n_periods, num_paths, rng)
+            .map(|simulation| simulation.simulated_prices)
+    }
+}
+
+/// Paths of an `MrjModel` simulation with the random draws behind them, all `n_periods`
+/// by `num_paths`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MrjSimulation {
+    pub simulated_prices: DMatrix<f64>,
+    pub jumps: DMatrix<f64>,
+    pub n1: DMatrix<f64>,
+    pub n2: DMatrix<f64>,
+}
+
+impl MrjModel {
+    /// `FeeModel::simulate_with_rng`, keeping the jump indicators and the normal draws of
+    /// the diffusion (`n1`) and of the jump sizes (`n2`).
+    pub fn simulate_with_draws(
+        &self,
+        params: &[f64],
+        initial_value: f64,
+        n_periods: usize,
+        num_paths: usize,
+        rng: &mut dyn RngCore,
+    ) -> Result<MrjSimulation> {
+        let dt = 1.0 / (365.0 * 24.0);
+
+        let MrjParams {
+            alpha,
+            kappa,
+            mu_j,
+            sigma,
+            sigma_j,
+            lambda: lambda_,
+        } = MrjParams::from_positions(params)?;
+
+        // Simulate the Poisson process (jumps)
+        let binom = Binomial::new(lambda_ * dt, 1)?;
+        let mut jumps = DMatrix::zeros(n_periods, num_paths);
+        for i in 0..n_periods {
+            for j in 0..num_paths {
+                jumps[(i, j)] = binom.sample(rng) as f64;
+            }
+        }
+
+        // Initialize simulated prices
+        let mut simulated_prices = DMatrix::zeros(n_periods, num_paths);
+        for j in 0..num_paths {
+            simulated_prices[(0, j)] = initial_value;
+        }
+
+        // Generate standard normal variables
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let mut n1 = DMatrix::zeros(n_periods, num_paths);
+        let mut n2 = DMatrix::zeros(n_periods, num_paths);
+        for i in 0..n_periods {
+            for j in 0..num_paths {
+                n1[(i, j)] = normal.sample(rng);
+                n2[(i, j)] = normal.sample(rng);
+            }
+        }
+        // Simulate prices over time
+        for i in 1..n_periods {
+            for j in 0..num_paths {
+                let prev_price = simulated_prices[(i - 1, j)];
+                let current_n1 = n1[(i, j)];
+                let current_n2 = n2[(i, j)];
+                let current_j = jumps[(i, j)];
+
+                simulated_prices[(i, j)] = alpha * dt
+                    + (1.0 - kappa * dt) * prev_price
+                    + sigma * dt.sqrt() * current_n1
+                    + current_j * (mu_j + sigma_j * current_n2);
+            }
+        }
+
+        Ok(MrjSimulation {
+            simulated_prices,
+            jumps,
+            n1,
+            n2,
+        })
+    }
+}
+
+/// Two-regime Markov-switching AR(1), with parameters
+/// `[a_0, a_1, phi, sigma_0^2, sigma_1^2, p_00, p_11]` of `pt = a_k + phi * pt_1 + N(0, sigma_k^2)`
+/// in regime `k`, which stays in regime `k` the next hour with probability `p_kk`.
+pub struct RegimeSwitchingModel;
+
+impl RegimeSwitchingModel {
+    // Long-run probability of regime 0
+    fn stationary_probability(p_00: f64, p_11: f64) -> f64 {
+        let switching = 2.0 - p_00 - p_11;
+        if switching > 0.0 {
+            (1.0 - p_11) / switching
+        } else {
+            0.5
+        }
+    }
+}
+
+impl FeeModel for RegimeSwitchingModel {
+    fn initial_params(&self, var_pt: f64) -> Vec<f64> {
+        // a calm and a volatile regime
+        vec![0.0, 0.0, 0.5, 0.5 * var_pt, 2.0 * var_pt, 0.95, 0.9]
+    }
+
+    fn param_bounds(&self) -> Vec<(f64, f64)> {
+        vec![
+            (f64::NEG_INFINITY, f64::INFINITY),
+            (f64::NEG_INFINITY, f64::INFINITY),
+            (f64::NEG_INFINITY, f64::INFINITY),
+            (0.0, f64::INFINITY),
+            (0.0, f64::INFINITY),
+            (0.0, 1.0),
+            (0.0, 1.0),
+        ]
+    }
+
+    // Hamilton filter
+    fn neg_log_likelihood(&self, params: &[f64], pt: &DVector<f64>, pt_1: &DVector<f64>) -> f64 {
+        let (a, phi, sigma_sq, p_00, p_11) = (
+            [params[0], params[1]],
+            params[2],
+            [params[3], params[4]],
+            params[5],
+            params[6],
+        );
+
+        let mut probability_0 = Self::stationary_probability(p_00, p_11);
+        let mut neg_log_likelihood = 0.0;
+        for (p, p_1) in pt.iter().zip(pt_1.iter()) {
+            let predicted = [
+                p_00 * probability_0 + (1.0 - p_11) * (1.0 - probability_0),
+                (1.0 - p_00) * probability_0 + p_11 * (1.0 - probability_0),
+            ];
+            let joint = [0, 1].map(|k| {
+                let residual = p - (a[k] + phi * p_1);
+                let variance = sigma_sq[k].max(1e-10);
+                predicted[k] * (-residual.powi(2) / (2.0 * variance)).exp()
+                    / (2.0 * PI * variance).sqrt()
+            });
+            let likelihood = joint[0] + joint[1];
+
+            neg_log_likelihood -= (likelihood + 1e-10).ln();
+            probability_0 = if likelihood > 0.0 {
+                joint[0] / likelihood
+            } else {
+                predicted[0]
+            };
+        }
+
+        neg_log_likelihood
+    }
+
+    fn simulate_with_rng(
+        &self,
+        params: &[f64],
+        initial_value: f64,
+        n_periods: usize,
+        num_paths: usize,
+        rng: &mut dyn RngCore,
+    ) -> Result<DMatrix<f64>> {
+        if params.len() != 7 {
+            return Err(err!(
+                "Expected 7 regime switching parameters, got {}",
+                params.len()
+            ));
+        }
+        let (a, phi, sigma_sq, p_stay) = (
+            [params[0], params[1]],
+            params[2],
+            [params[3], params[4]],
+            [params[5], params[6]],
+        );
+        let sigma = [
+            Normal::new(0.0, sigma_sq[0].sqrt())?,
+            Normal::new(0.0, sigma_sq[1].sqrt())?,
+        ];
+
+        let probability_0 = Self::stationary_probability(p_stay[0], p_stay[1]);
+
+        let mut simulated_prices = DMatrix::zeros(n_periods, num_paths);
+        for j in 0..num_paths {
+            simulated_prices[(0, j)] = initial_value;
+
+            let mut regime = if rng.gen::<f64>() < probability_0 {
+                0
+            } else {
+                1
+            };
+            for i in 1..n_periods {
+                if rng.gen::<f64>() >= p_stay[regime] {
+                    regime = 1 - regime;
+                }
+                simulated_prices[(i, j)] =
+                    a[regime] + phi * simulated_prices[(i - 1, j)] + sigma[regime].sample(rng);
+            }
+        }
+
+        Ok(simulated_prices)
+    }
+}
+
+/// AR(1) with GARCH(1, 1) errors, with parameters `[a, phi, omega, alpha, beta]` of
+/// `pt = a + phi * pt_1 + e`, where `e` is `N(0, h)` and the next hour's variance is
+/// `omega + alpha * e^2 + beta * h`.
+pub struct GarchModel;
+
+impl GarchModel {
+    // Variance of the first hour: the long-run variance when it exists
+    fn initial_variance(omega: f64, alpha: f64, beta: f64, fallback: f64) -> f64 {
+        if alpha + beta < 1.0 {
+            omega / (1.0 - alpha - beta)
+        } else {
+            fallback
+        }
+    }
+}
+
+impl FeeModel for GarchModel {
+    fn initial_params(&self, var_pt: f64) -> Vec<f64> {
+        vec![0.0, 0.5, 0.1 * var_pt, 0.1, 0.8]
+    }
+
+    fn param_bounds(&self) -> Vec<(f64, f64)> {
+        vec![
+            (f64::NEG_INFINITY, f64::INFINITY),
+            (f64::NEG_INFINITY, f64::INFINITY),
+            (0.0, f64::INFINITY),
+            (0.0, 1.0),
+            (0.0, 1.0),
+        ]
+    }
+
+    fn neg_log_likelihood(&self, params: &[f64], pt: &DVector<f64>, pt_1: &DVector<f64>) -> f64 {
+        let (a, phi, omega, alpha, beta) = (params[0], params[1], params[2], params[3], params[4]);
+
+        let residuals = pt
+            .iter()
+            .zip(pt_1.iter())
+            .map(|(p, p_1)| p - (a + phi * p_1))
+            .collect::<Vec<_>>();
+        let mean_square = residuals.iter().map(|e| e * e).sum::<f64>() / residuals.len() as f64;
+
+        let mut variance = Self::initial_variance(omega, alpha, beta, mean_square);
+        let mut neg_log_likelihood = 0.0;
+        for residual in residuals {
+            let h = variance.max(1e-10);
+            neg_log_likelihood += 0.5 * ((2.0 * PI * h).ln() + residual * residual / h);
+            variance = omega + alpha * residual * residual + beta * h;
+        }
+
+        neg_log_likelihood
+    }
+
+    fn simulate_with_rng(
+        &self,
+        params: &[f64],
+        initial_value: f64,
+        n_periods: usize,
+        num_paths: usize,
+        rng: &mut dyn RngCore,
+    ) -> Result<DMatrix<f64>> {
+        if params.len() != 5 {
+            return Err(err!("Expected 5 GARCH parameters, got {}", params.len()));
+        }
+        let (a, phi, omega, alpha, beta) = (params[0], params[1], params[2], params[3], params[4]);
+
+        let normal = Normal::new(0.0, 1.0)?;
+
+        let mut simulated_prices = DMatrix::zeros(n_periods, num_paths);
+        for j in 0..num_paths {
+            simulated_prices[(0, j)] = initial_value;
+
+            let mut variance = Self::initial_variance(omega, alpha, beta, omega);
+            for i in 1..n_periods {
+                let residual = variance.sqrt() * normal.sample(rng);
+                simulated_prices[(i, j)] = a + phi * simulated_prices[(i - 1, j)] + residual;
+                variance = omega + alpha * residual * residual + beta * variance;
+            }
+        }
+
+        Ok(simulated_prices)
+    }
+}
@@ -0,0 +1,184 @@
+use eyre::{anyhow as err, Result};
+use nalgebra::{DMatrix, DVector};
+use rand::{rngs::StdRng, SeedableRng};
+
+use super::{
+    calculate_simulated_log_prices_from_shocks, calculate_trend_volatility,
+    calculated_reserve_price_for_round, generate_trend_shocks, FeeModelKind, MrjParams, PayoffKind,
+};
+use crate::window::RoundHorizon;
+
+// Relative bump of the strike and of the volatilities for the central differences
+const BUMP: f64 = 0.01;
+
+/// Sensitivities of the reserve price, by bump-and-reprice on common random numbers: every
+/// repricing simulates the fee model from the same seeded draws and uses the same seeded
+/// trend shocks.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Greeks {
+    pub reserve_price: f64,
+    /// Derivative with respect to the strike.
+    pub dual_delta: f64,
+    /// Derivative with respect to the volatility of the stochastic trend.
+    pub vega: f64,
+    /// Change in the reserve price when maturity is one day closer.
+    pub theta: f64,
+    /// Derivative with respect to the annualized diffusion volatility `sigma` of the MRJ
+    /// model, `None` for the other fee models.
+    pub sigma_vega: Option<f64>,
+    /// Derivative with respect to the jump volatility `sigma_j` of the MRJ model, `None`
+    /// for the other fee models.
+    pub sigma_j_vega: Option<f64>,
+}
+
+// Central bump of a volatility, floored at zero
+fn bump_volatility(volatility: f64) -> (f64, f64) {
+    let bump = if volatility > 0.0 {
+        BUMP * volatility
+    } else {
+        BUMP
+    };
+    ((volatility - bump).max(0.0), volatility + bump)
+}
+
+// Reserve price of the round at `strike`
+fn price(
+    simulated_log_prices: &DMatrix<f64>,
+    horizon: &RoundHorizon,
+    payoff: &PayoffKind,
+    strike: f64,
+) -> Result<f64> {
+    calculated_reserve_price_for_round(simulated_log_prices, &[strike], horizon, payoff)
+}
+
+/// Greeks of the reserve price of `payoff` for the round of `horizon`, settled as by
+/// `calculated_reserve_price_for_round`, when `fee_model` with the fitted `positions`
+/// simulates `num_paths` paths of the round from the last de-seasonalised, detrended log
+/// base fee. The fee model draws from `seed + 1` and the stochastic trend from `seed`, so
+/// that the Greeks are a deterministic function of the inputs. The other arguments are
+/// those of `calculate_reserve_price`.
+pub fn calculate_greeks(
+    period_start_timestamp: i64,
+    period_end_timestamp: i64,
+    season_param: &DVector<f64>,
+    fee_model: FeeModelKind,
+    positions: &[f64],
+    de_seasonalised_detrended_log_base_fee: &DVector<f64>,
+    horizon: &RoundHorizon,
+    num_paths: usize,
+    twap_7d: &[f64],
+    slope: f64,
+    intercept: f64,
+    log_base_fee_len: usize,
+    payoff: &PayoffKind,
+    seed: u64,
+) -> Result<Greeks> {
+    let n_periods = horizon.n_periods;
+    // the same round a day shorter, settled on a window of the same length
+    let one_day_closer_horizon = match horizon.settlement_start.checked_sub(24) {
+        Some(settlement_start) => RoundHorizon {
+            n_periods: n_periods - 24,
+            settlement_start,
+        },
+        None => {
+            return Err(err!(
+                "Theta needs a day of the round before its settlement window, got {} hours",
+                horizon.settlement_start
+            ))
+        }
+    };
+
+    let strike = *twap_7d.last().ok_or_else(|| err!("The series is empty"))?;
+    let initial_value = *de_seasonalised_detrended_log_base_fee
+        .as_slice()
+        .last()
+        .ok_or_else(|| err!("The de-seasonalised, detrended log base fee is empty"))?;
+    let simulate_fees = |positions: &[f64]| {
+        fee_model.model().simulate_with_rng(
+            positions,
+            initial_value,
+            n_periods,
+            num_paths,
+            &mut StdRng::seed_from_u64(seed.wrapping_add(1)),
+        )
+    };
+
+    let trend_volatility = calculate_trend_volatility(twap_7d);
+    let shocks = generate_trend_shocks(n_periods, num_paths, seed)?;
+    let simulate = |simulated_fees: &DMatrix<f64>, trend_volatility: f64| {
+        calculate_simulated_log_prices_from_shocks(
+            period_start_timestamp,
+            period_end_timestamp,
+            season_param,
+            simulated_fees,
+            slope,
+            intercept,
+            log_base_fee_len,
+            trend_volatility,
+            &shocks,
+        )
+    };
+
+    let simulated_fees = simulate_fees(positions)?;
+    let simulated_log_prices = simulate(&simulated_fees, trend_volatility)?;
+    let reserve_price = price(&simulated_log_prices, horizon, payoff, strike)?;
+
+    let strike_bump = BUMP * strike;
+    let dual_delta = (price(&simulated_log_prices, horizon, payoff, strike + strike_bump)?
+        - price(&simulated_log_prices, horizon, payoff, strike - strike_bump)?)
+        / (2.0 * strike_bump);
+
+    let (lower, upper) = bump_volatility(trend_volatility);
+    let vega = (price(&simulate(&simulated_fees, upper)?, horizon, payoff, strike)?
+        - price(&simulate(&simulated_fees, lower)?, horizon, payoff, strike)?)
+        / (upper - lower);
+
+    // the same paths, settled a day earlier
+    let one_day_closer = simulated_log_prices.rows(0, n_periods - 24).into_owned();
+    let theta = price(&one_day_closer, &one_day_closer_horizon, payoff, strike)? - reserve_price;
+
+    let (sigma_vega, sigma_j_vega) = match fee_model {
+        FeeModelKind::MeanRevertingJump => {
+            let params = MrjParams::from_positions(positions)?;
+            let reprice = |params: MrjParams| -> Result<f64> {
+                let simulated_fees = simulate_fees(&params.to_positions())?;
+                price(
+                    &simulate(&simulated_fees, trend_volatility)?,
+                    horizon,
+                    payoff,
+                    strike,
+                )
+            };
+
+            let (lower, upper) = bump_volatility(params.sigma);
+            let sigma_vega = (reprice(MrjParams {
+                sigma: upper,
+                ..params
+            })? - reprice(MrjParams {
+                sigma: lower,
+                ..params
+            })?) / (upper - lower);
+
+            let (lower, upper) = bump_volatility(params.sigma_j);
+            let sigma_j_vega = (reprice(MrjParams {
+                sigma_j: upper,
+                ..params
+            })? - reprice(MrjParams {
+                sigma_j: lower,
+                ..params
+            })?) / (upper - lower);
+
+            (Some(sigma_vega), Some(sigma_j_vega))
+        }
+        FeeModelKind::RegimeSwitching | FeeModelKind::Garch => (None, None),
+    };
+
+    Ok(Greeks {
+        reserve_price,
+        dual_delta,
+        vega,
+        theta,
+        sigma_vega,
+        sigma_j_vega,
+    })
+}
@@ -2,10 +2,14 @@ use std::f64::consts::PI;
 
 use nalgebra::{DMatrix, DVector};
 
+use crate::window::RoundHorizon;
+
 pub mod remove_seasonality;
 pub use remove_seasonality::*;
 pub mod reserve_price;
 pub use reserve_price::*;
+pub mod reserve_price_grid;
+pub use reserve_price_grid::*;
 pub mod simulate_price;
 pub use simulate_price::*;
 pub mod add_twap;
@@ -14,8 +18,26 @@ pub mod simulate_price_verify_position;
 pub use simulate_price_verify_position::*;
 pub mod calculate_twap;
 pub use calculate_twap::*;
+pub mod calculate_time_weighted_twap;
+pub use calculate_time_weighted_twap::*;
+pub mod reference_prices;
+pub use reference_prices::*;
 pub mod max_return;
 pub use max_return::*;
+pub mod tail_risk;
+pub use tail_risk::*;
+pub mod mrj_params;
+pub use mrj_params::*;
+pub mod fee_model;
+pub use fee_model::*;
+pub mod payoff;
+pub use payoff::*;
+pub mod greeks;
+pub use greeks::*;
+pub mod fan_chart;
+pub use fan_chart::*;
+pub mod scoring;
+pub use scoring::*;
 
 pub fn mrjpdf(params: &[f64], pt: &DVector<f64>, pt_1: &DVector<f64>) -> DVector<f64> {
     let (a, phi, mu_j, sigma_sq, sigma_sq_j, lambda) = (
@@ -77,9 +99,9 @@ pub fn calculate_reserve_price_full(
     start_timestamp: i64,
     end_timestamp: i64,
     input: &Vec<f64>,
+    horizon: &RoundHorizon,
 ) -> AllInputsToReservePrice {
     let num_paths = 15000;
-    let n_periods = 720;
     let twap = add_twap_7d(&input).unwrap();
     let (slope, intercept, de_seasonalised_detrended_log_base_fee, season_param) =
         calculate_remove_seasonality(&input).unwrap();
@@ -87,7 +109,7 @@ pub fn calculate_reserve_price_full(
     let (simulated_prices, _params) = simulate_price(
         &de_seasonalised_detrended_log_base_fee,
         num_paths,
-        n_periods,
+        horizon.n_periods,
     );
 
     let reserve_price = calculate_reserve_price(
@@ -100,7 +122,7 @@ pub fn calculate_reserve_price_full(
         intercept,
         input.len(),
         num_paths,
-        n_periods,
+        horizon,
     )
     .unwrap();
 
@@ -125,8 +147,6 @@ pub fn error_bound_matrix(
         return false;
     }
 
-    // let mut wtr = open_error_bound_diff_csv_writer("error_bound_diff.csv");
-    // let mut total_diff = 0;
     for i in 0..target.nrows() {
         for j in 0..target.ncols() {
             let target_val = target[(i, j)];
@@ -141,25 +161,12 @@ pub fn error_bound_matrix(
                 0.0 // Both are 0, no difference
             };
 
-            // write_error_bound_diff_to_csv(&mut wtr, i, j, target_val, calc_val, percentage_diff);
-
             if percentage_diff > tolerance {
-                // total_diff += 1;
-                // println!("i: {:?}", i);
-                // println!("j: {:?}", j);
-                // println!("target_val: {:?}", target_val);
-                // println!("calc_val: {:?}", calc_val);
-                // println!("percentage_diff: {:?}\n", percentage_diff);
-                // continue;
                 return false;
             }
         }
     }
 
-    // close_csv_file(&mut wtr);
-
-    // println!("total_diff: {:?}", total_diff);
-
     true
 }
 
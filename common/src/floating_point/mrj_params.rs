@@ -0,0 +1,78 @@
+use eyre::{anyhow as err, Result};
+use serde::{Deserialize, Serialize};
+
+// The MRJ model is fitted on hourly data
+const HOURS_PER_YEAR: f64 = 365.0 * 24.0;
+
+/// Annualized parameters of the mean-reverting jump model simulated by
+/// `simulate_price_verify_position`, for log fees following
+/// `dp = (alpha - kappa * p) dt + sigma dW + J dN` with jumps `J ~ N(mu_j, sigma_j^2)`
+/// arriving at rate `lambda`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MrjParams {
+    pub alpha: f64,
+    pub kappa: f64,
+    pub mu_j: f64,
+    pub sigma: f64,
+    pub sigma_j: f64,
+    pub lambda: f64,
+}
+
+impl MrjParams {
+    /// Converts the hourly `positions` found by the minimization, i.e. the intercept, AR(1)
+    /// coefficient, jump mean, diffusion variance, jump variance and jump probability.
+    pub fn from_positions(positions: &[f64]) -> Result<Self> {
+        if positions.len() != 6 {
+            return Err(err!("Expected 6 MRJ positions, got {}", positions.len()));
+        }
+
+        let dt = 1.0 / HOURS_PER_YEAR;
+        Ok(MrjParams {
+            alpha: positions[0] / dt,
+            kappa: (1.0 - positions[1]) / dt,
+            mu_j: positions[2],
+            sigma: (positions[3] / dt).sqrt(),
+            sigma_j: positions[4].sqrt(),
+            lambda: positions[5] / dt,
+        })
+    }
+
+    /// The hourly positions these parameters are annualized from.
+    pub fn to_positions(&self) -> Vec<f64> {
+        let dt = 1.0 / HOURS_PER_YEAR;
+        vec![
+            self.alpha * dt,
+            1.0 - self.kappa * dt,
+            self.mu_j,
+            self.sigma.powi(2) * dt,
+            self.sigma_j.powi(2),
+            self.lambda * dt,
+        ]
+    }
+}
+
+/// Annualized standard deviation of the hourly changes in log fee.
+pub fn calculate_realized_volatility(base_fees: &[f64]) -> Result<f64> {
+    if base_fees.len() < 3 {
+        return Err(err!(
+            "Realized volatility needs at least 3 fees, got {}",
+            base_fees.len()
+        ));
+    }
+    if let Some(fee) = base_fees.iter().find(|fee| fee.is_nan() || **fee <= 0.0) {
+        return Err(err!("Realized volatility needs positive fees, got {}", fee));
+    }
+
+    let log_returns = base_fees
+        .windows(2)
+        .map(|pair| (pair[1] / pair[0]).ln())
+        .collect::<Vec<_>>();
+    let mean = log_returns.iter().sum::<f64>() / log_returns.len() as f64;
+    let variance = log_returns
+        .iter()
+        .map(|value| (value - mean).powi(2))
+        .sum::<f64>()
+        / (log_returns.len() - 1) as f64;
+
+    Ok((variance * HOURS_PER_YEAR).sqrt())
+}
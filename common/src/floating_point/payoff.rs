@@ -0,0 +1,128 @@
+use nalgebra::DMatrix;
+use serde::{Deserialize, Serialize};
+
+// Hourly log price changes are annualized like the realized volatility
+const HOURS_PER_YEAR: f64 = 365.0 * 24.0;
+
+/// Payoff of an option settled on the simulated fees. `settlement_prices` are the hourly
+/// fees of one path over the settlement window, and `strike` is the last 7-day TWAP.
+pub trait Payoff {
+    fn payoff(&self, settlement_prices: &[f64], strike: f64) -> f64;
+}
+
+/// Call on the settlement TWAP, paying at most `cap_level` times the strike.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CappedCall {
+    pub cap_level: f64,
+}
+
+impl Payoff for CappedCall {
+    fn payoff(&self, settlement_prices: &[f64], strike: f64) -> f64 {
+        let capped_price = (1.0 + self.cap_level) * strike;
+        (mean(settlement_prices).min(capped_price) - strike).max(0.0)
+    }
+}
+
+/// Put on the settlement TWAP, paying at most `cap_level` times the strike. This is the
+/// floor a vault buys against fees falling.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CappedPut {
+    pub cap_level: f64,
+}
+
+impl Payoff for CappedPut {
+    fn payoff(&self, settlement_prices: &[f64], strike: f64) -> f64 {
+        let floored_price = (1.0 - self.cap_level) * strike;
+        (strike - mean(settlement_prices).max(floored_price)).max(0.0)
+    }
+}
+
+/// Pays `payout` when the settlement TWAP is above the strike, and nothing otherwise.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Digital {
+    pub payout: f64,
+}
+
+impl Payoff for Digital {
+    fn payoff(&self, settlement_prices: &[f64], strike: f64) -> f64 {
+        if mean(settlement_prices) > strike {
+            self.payout
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Floating leg of a variance swap: the annualized realized variance of the hourly log
+/// fees over the settlement window. Its price is the fair variance strike, so the strike
+/// fee is not used.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct VarianceSwap;
+
+impl Payoff for VarianceSwap {
+    fn payoff(&self, settlement_prices: &[f64], _strike: f64) -> f64 {
+        if settlement_prices.len() < 2 {
+            return 0.0;
+        }
+
+        let squared_returns = settlement_prices
+            .windows(2)
+            .map(|pair| (pair[1] / pair[0]).ln().powi(2))
+            .sum::<f64>();
+        squared_returns / (settlement_prices.len() - 1) as f64 * HOURS_PER_YEAR
+    }
+}
+
+/// Payoff priced by the reserve price, as selected in the composition input.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub enum PayoffKind {
+    CappedCall(CappedCall),
+    CappedPut(CappedPut),
+    Digital(Digital),
+    VarianceSwap(VarianceSwap),
+}
+
+impl Default for PayoffKind {
+    /// The capped call the reserve price has always priced, capped at 30%.
+    fn default() -> Self {
+        PayoffKind::CappedCall(CappedCall { cap_level: 0.3 })
+    }
+}
+
+impl PayoffKind {
+    pub fn payoff(&self) -> &dyn Payoff {
+        match self {
+            PayoffKind::CappedCall(payoff) => payoff,
+            PayoffKind::CappedPut(payoff) => payoff,
+            PayoffKind::Digital(payoff) => payoff,
+            PayoffKind::VarianceSwap(payoff) => payoff,
+        }
+    }
+}
+
+/// Mean payoff over the paths (columns) of `simulated_prices`, settled on the rows from
+/// `settlement_start` on.
+pub fn calculate_expected_payoff<P: Payoff + ?Sized>(
+    payoff: &P,
+    simulated_prices: &DMatrix<f64>,
+    settlement_start: usize,
+    strike: f64,
+) -> f64 {
+    let payoffs = simulated_prices
+        .column_iter()
+        .map(|path| {
+            let settlement_prices = path
+                .iter()
+                .skip(settlement_start)
+                .copied()
+                .collect::<Vec<_>>();
+            payoff.payoff(&settlement_prices, strike)
+        })
+        .collect::<Vec<_>>();
+
+    payoffs.iter().sum::<f64>() / payoffs.len() as f64
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
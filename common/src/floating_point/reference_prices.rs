@@ -0,0 +1,74 @@
+use eyre::{anyhow as err, Result};
+
+// Alternative reference prices to the arithmetic TWAP. A single spike hour moves the
+// arithmetic mean by spike / n, while the geometric mean only moves by its n-th root
+// and the median not at all.
+
+/// Geometric mean of the hourly fees, `exp(mean(ln fee))`. Every fee must be positive.
+pub fn calculate_geometric_twap(base_fees: &[f64]) -> Result<f64> {
+    if base_fees.is_empty() {
+        return Err(err!("Cannot calculate a geometric TWAP over no fees"));
+    }
+    if let Some(fee) = base_fees.iter().find(|fee| fee.is_nan() || **fee <= 0.0) {
+        return Err(err!("Geometric TWAP needs positive fees, got {}", fee));
+    }
+
+    let log_sum = base_fees.iter().map(|fee| fee.ln()).sum::<f64>();
+    Ok((log_sum / base_fees.len() as f64).exp())
+}
+
+/// Median of the values, averaging the two middle values of an even count.
+pub fn calculate_median(values: &[f64]) -> Result<f64> {
+    if values.is_empty() {
+        return Err(err!("Cannot calculate the median of no values"));
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let middle = sorted.len() / 2;
+    if sorted.len() % 2 == 0 {
+        Ok((sorted[middle - 1] + sorted[middle]) / 2.0)
+    } else {
+        Ok(sorted[middle])
+    }
+}
+
+/// Median over the trailing `window` hours at every hour, over fewer hours at the start
+/// as in `add_twap_7d`. The last value is the reference price at the end of the data.
+pub fn calculate_rolling_median(base_fees: &[f64], window: usize) -> Result<Vec<f64>> {
+    if window == 0 || window > base_fees.len() {
+        return Err(err!(
+            "Rolling median window of {} hours does not fit {} hours of data",
+            window,
+            base_fees.len()
+        ));
+    }
+
+    (0..base_fees.len())
+        .map(|i| calculate_median(&base_fees[(i + 1).saturating_sub(window)..=i]))
+        .collect()
+}
+
+/// Exponential moving average with smoothing factor `alpha` in (0, 1], starting from the
+/// first fee. The last value is the reference price at the end of the data.
+pub fn calculate_ema(base_fees: &[f64], alpha: f64) -> Result<Vec<f64>> {
+    if base_fees.is_empty() {
+        return Err(err!("Cannot calculate an EMA over no fees"));
+    }
+    if alpha.is_nan() || alpha <= 0.0 || alpha > 1.0 {
+        return Err(err!(
+            "EMA smoothing factor must be in (0, 1], got {}",
+            alpha
+        ));
+    }
+
+    let mut ema = Vec::with_capacity(base_fees.len());
+    ema.push(base_fees[0]);
+    for fee in &base_fees[1..] {
+        let previous = ema[ema.len() - 1];
+        ema.push(alpha * fee + (1.0 - alpha) * previous);
+    }
+
+    Ok(ema)
+}
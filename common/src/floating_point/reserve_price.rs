@@ -1,10 +1,11 @@
 use eyre::{anyhow as err, Result};
 use nalgebra::{DMatrix, DVector};
-use rand::thread_rng;
+use rand::{rngs::StdRng, thread_rng, SeedableRng};
 use rand_distr::Distribution;
 use statrs::distribution::Normal;
 
-use super::season_matrix;
+use super::{calculate_expected_payoff, season_matrix, PayoffKind};
+use crate::window::RoundHorizon;
 
 fn standard_deviation(values: &[f64]) -> f64 {
     let n = values.len() as f64;
@@ -23,89 +24,137 @@ pub fn calculate_reserve_price(
     intercept: f64,
     log_base_fee_len: usize,
     num_paths: usize,
-    n_periods: usize,
+    horizon: &RoundHorizon,
 ) -> Result<f64> {
-    // timestamps are assumed to be in milliseconds for this calculation
-    let total_hours = (period_end_timestamp * 1000 - period_start_timestamp * 1000) / 3600 / 1000;
-
-    let sim_hourly_times = DVector::from_iterator(
-        n_periods,
-        (0..n_periods).map(|i| total_hours as f64 + i as f64),
-    );
+    calculate_reserve_price_with_payoff(
+        period_start_timestamp,
+        period_end_timestamp,
+        season_param,
+        de_seasonalized_detrended_simulated_prices,
+        twap_7d,
+        slope,
+        intercept,
+        log_base_fee_len,
+        num_paths,
+        horizon,
+        &PayoffKind::default(),
+    )
+}
 
-    let c = season_matrix(sim_hourly_times);
-    let season = &c * season_param;
-    let season_matrix = season.reshape_generic(nalgebra::Dyn(n_periods), nalgebra::Const::<1>);
-    let season_matrix_shaped =
-        DMatrix::from_fn(n_periods, num_paths, |row, _| season_matrix[(row, 0)]);
+/// Reserve price of `payoff`: its discounted mean over the fees simulated for each hour of
+/// the round, settled on the settlement window of its `horizon`.
+pub fn calculate_reserve_price_with_payoff(
+    period_start_timestamp: i64,
+    period_end_timestamp: i64,
+    season_param: &DVector<f64>,
+    de_seasonalized_detrended_simulated_prices: &DMatrix<f64>,
+    twap_7d: &[f64],
+    slope: f64,
+    intercept: f64,
+    log_base_fee_len: usize,
+    num_paths: usize,
+    horizon: &RoundHorizon,
+    payoff: &PayoffKind,
+) -> Result<f64> {
+    let simulated_log_prices = calculate_simulated_log_prices(
+        period_start_timestamp,
+        period_end_timestamp,
+        season_param,
+        de_seasonalized_detrended_simulated_prices,
+        twap_7d,
+        slope,
+        intercept,
+        log_base_fee_len,
+        num_paths,
+        horizon.n_periods,
+    )?;
+
+    calculated_reserve_price_for_round(&simulated_log_prices, twap_7d, horizon, payoff)
+}
 
-    let detrended_simulated_prices =
-        de_seasonalized_detrended_simulated_prices + &season_matrix_shaped;
+pub fn calculate_simulated_log_prices(
+    period_start_timestamp: i64,
+    period_end_timestamp: i64,
+    season_param: &DVector<f64>,
+    de_seasonalized_detrended_simulated_prices: &DMatrix<f64>,
+    twap_7d: &[f64],
+    slope: f64,
+    intercept: f64,
+    log_base_fee_len: usize,
+    num_paths: usize,
+    n_periods: usize,
+) -> Result<DMatrix<f64>> {
+    let normal = Normal::new(0.0, 1.0)?;
+    let mut rng = thread_rng();
+    let shocks = DMatrix::from_fn(n_periods, num_paths, |_, _| normal.sample(&mut rng));
+
+    calculate_simulated_log_prices_from_shocks(
+        period_start_timestamp,
+        period_end_timestamp,
+        season_param,
+        de_seasonalized_detrended_simulated_prices,
+        slope,
+        intercept,
+        log_base_fee_len,
+        calculate_trend_volatility(twap_7d),
+        &shocks,
+    )
+}
 
+/// Volatility of the stochastic trend: the standard deviation of the hourly log changes
+/// of the 7-day TWAP, scaled to a week.
+pub fn calculate_trend_volatility(twap_7d: &[f64]) -> f64 {
     let log_twap_7d: Vec<f64> = twap_7d.iter().map(|x| x.ln()).collect();
     let returns: Vec<f64> = log_twap_7d
         .windows(2)
         .map(|window| window[1] - window[0])
         .collect();
 
-    let mu = 0.05 / 52.0;
-    let sigma = standard_deviation(&returns) * f64::sqrt(24.0 * 7.0);
-    let dt = 1.0 / 24.0;
-
-    let mut stochastic_trend = DMatrix::zeros(n_periods, num_paths);
-    let normal = Normal::new(0.0, sigma * f64::sqrt(dt))?;
-    let mut rng = thread_rng();
-
-    for i in 0..num_paths {
-        let random_shocks: Vec<f64> = (0..n_periods).map(|_| normal.sample(&mut rng)).collect();
-        let mut cumsum = 0.0;
-        for j in 0..n_periods {
-            cumsum += (mu - 0.5 * sigma.powi(2)) * dt + random_shocks[j];
-            stochastic_trend[(j, i)] = cumsum;
-        }
-    }
-
-    let final_trend_value = slope * (log_base_fee_len - 1) as f64 + intercept;
-    let mut simulated_log_prices = DMatrix::zeros(n_periods, num_paths);
-
-    for i in 0..n_periods {
-        let trend = final_trend_value;
-        for j in 0..num_paths {
-            simulated_log_prices[(i, j)] =
-                detrended_simulated_prices[(i, j)] + trend + stochastic_trend[(i, j)];
-        }
-    }
-
-    let simulated_prices = simulated_log_prices.map(f64::exp);
-
-    let twap_start = n_periods.saturating_sub(24 * 7);
-
-    let final_prices_twap = simulated_prices
-        .rows(twap_start, n_periods - twap_start)
-        .row_mean();
-
-    let strike = twap_7d.last().ok_or_else(|| err!("The series is empty"))?;
-    let capped_price = (1.0 + 0.3) * strike;
-    let payoffs = final_prices_twap.map(|price| (price.min(capped_price) - strike).max(0.0));
-    let average_payoff = payoffs.mean();
+    standard_deviation(&returns) * f64::sqrt(24.0 * 7.0)
+}
 
-    let reserve_price = f64::exp(-0.05) * average_payoff;
+/// Standard normal shocks of the stochastic trend from a seeded RNG, so that prices
+/// simulated with bumped parameters share their random numbers.
+pub fn generate_trend_shocks(
+    n_periods: usize,
+    num_paths: usize,
+    seed: u64,
+) -> Result<DMatrix<f64>> {
+    let normal = Normal::new(0.0, 1.0)?;
+    let mut rng = StdRng::seed_from_u64(seed);
 
-    Ok(reserve_price)
+    Ok(DMatrix::from_fn(n_periods, num_paths, |_, _| {
+        normal.sample(&mut rng)
+    }))
 }
 
-pub fn calculate_simulated_log_prices(
+/// Same as `calculate_simulated_log_prices`, with the stochastic trend driven by standard
+/// normal `shocks` of the shape of the simulated prices and the given `trend_volatility`.
+pub fn calculate_simulated_log_prices_from_shocks(
     period_start_timestamp: i64,
     period_end_timestamp: i64,
     season_param: &DVector<f64>,
     de_seasonalized_detrended_simulated_prices: &DMatrix<f64>,
-    twap_7d: &[f64],
     slope: f64,
     intercept: f64,
     log_base_fee_len: usize,
-    num_paths: usize,
-    n_periods: usize,
+    trend_volatility: f64,
+    shocks: &DMatrix<f64>,
 ) -> Result<DMatrix<f64>> {
+    let (n_periods, num_paths) = de_seasonalized_detrended_simulated_prices.shape();
+    if shocks.shape() != (n_periods, num_paths) {
+        return Err(err!(
+            "Expected {}x{} trend shocks, got {}x{}",
+            n_periods,
+            num_paths,
+            shocks.nrows(),
+            shocks.ncols()
+        ));
+    }
+    if trend_volatility.is_nan() || trend_volatility < 0.0 {
+        return Err(err!("Invalid trend volatility {}", trend_volatility));
+    }
+
     let total_hours = (period_end_timestamp * 1000 - period_start_timestamp * 1000) / 3600 / 1000;
 
     let sim_hourly_times = DVector::from_iterator(
@@ -122,25 +171,15 @@ pub fn calculate_simulated_log_prices(
     let detrended_simulated_prices =
         de_seasonalized_detrended_simulated_prices + &season_matrix_shaped;
 
-    let log_twap_7d: Vec<f64> = twap_7d.iter().map(|x| x.ln()).collect();
-    let returns: Vec<f64> = log_twap_7d
-        .windows(2)
-        .map(|window| window[1] - window[0])
-        .collect();
-
     let mu = 0.05 / 52.0;
-    let sigma = standard_deviation(&returns) * f64::sqrt(24.0 * 7.0);
+    let sigma = trend_volatility;
     let dt = 1.0 / 24.0;
 
     let mut stochastic_trend = DMatrix::zeros(n_periods, num_paths);
-    let normal = Normal::new(0.0, sigma * f64::sqrt(dt))?;
-    let mut rng = thread_rng();
-
     for i in 0..num_paths {
-        let random_shocks: Vec<f64> = (0..n_periods).map(|_| normal.sample(&mut rng)).collect();
         let mut cumsum = 0.0;
         for j in 0..n_periods {
-            cumsum += (mu - 0.5 * sigma.powi(2)) * dt + random_shocks[j];
+            cumsum += (mu - 0.5 * sigma.powi(2)) * dt + sigma * f64::sqrt(dt) * shocks[(j, i)];
             stochastic_trend[(j, i)] = cumsum;
         }
     }
@@ -159,22 +198,41 @@ pub fn calculate_simulated_log_prices(
     Ok(simulated_log_prices)
 }
 
-pub fn calculated_reserve_price_from_simulated_log_prices(
+/// Reserve price of `payoff` over a round simulated hour by hour, settled on the
+/// settlement window of its `horizon`.
+pub fn calculated_reserve_price_for_round(
     simulated_log_prices: &DMatrix<f64>,
     twap_7d: &[f64],
-    n_periods: usize,
+    horizon: &RoundHorizon,
+    payoff: &PayoffKind,
 ) -> Result<f64> {
-    let simulated_prices = simulated_log_prices.map(f64::exp);
-    let twap_start = n_periods.saturating_sub(24 * 7);
+    if simulated_log_prices.nrows() != horizon.n_periods {
+        return Err(err!(
+            "Expected {} simulated hours for the round, got {}",
+            horizon.n_periods,
+            simulated_log_prices.nrows()
+        ));
+    }
 
-    let final_prices_twap = simulated_prices
-        .rows(twap_start, n_periods - twap_start)
-        .row_mean();
+    discounted_expected_payoff(
+        simulated_log_prices,
+        twap_7d,
+        horizon.settlement_start,
+        payoff,
+    )
+}
+
+fn discounted_expected_payoff(
+    simulated_log_prices: &DMatrix<f64>,
+    twap_7d: &[f64],
+    twap_start: usize,
+    payoff: &PayoffKind,
+) -> Result<f64> {
+    let simulated_prices = simulated_log_prices.map(f64::exp);
 
     let strike = twap_7d.last().ok_or_else(|| err!("The series is empty"))?;
-    let capped_price = (1.0 + 0.3) * strike;
-    let payoffs = final_prices_twap.map(|price| (price.min(capped_price) - strike).max(0.0));
-    let average_payoff = payoffs.mean();
+    let average_payoff =
+        calculate_expected_payoff(payoff.payoff(), &simulated_prices, twap_start, *strike);
 
     let reserve_price = f64::exp(-0.05) * average_payoff;
 
@@ -0,0 +1,141 @@
+use eyre::{anyhow as err, Result};
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    calculate_expected_payoff, calculate_simulated_log_prices, error_bound_f64, PayoffKind,
+};
+use crate::window::SettlementSpec;
+
+/// Reserve prices of one simulation across strikes and maturities.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ReservePriceGrid {
+    /// Strikes as fractions of the last 7-day TWAP, e.g. 1.1 for 110%.
+    pub strike_levels: Vec<f64>,
+    /// Maturities in hours, each at most the `n_periods` simulated.
+    pub horizons: Vec<usize>,
+    /// `reserve_prices[h][k]` is the reserve price at `horizons[h]` and `strike_levels[k]`.
+    pub reserve_prices: Vec<Vec<f64>>,
+}
+
+/// Prices every strike and horizon on the same simulated log prices. A horizon of `n`
+/// hours settles on the last `settlement.twap_window_hours` of the first `n` simulated
+/// hours, like `calculated_reserve_price_for_round` does for the whole round.
+pub fn calculated_reserve_price_grid_from_simulated_log_prices(
+    simulated_log_prices: &DMatrix<f64>,
+    twap_7d: &[f64],
+    strike_levels: &[f64],
+    horizons: &[usize],
+    payoff: &PayoffKind,
+    settlement: &SettlementSpec,
+) -> Result<ReservePriceGrid> {
+    let twap = twap_7d.last().ok_or_else(|| err!("The series is empty"))?;
+    if let Some(level) = strike_levels
+        .iter()
+        .find(|level| level.is_nan() || **level <= 0.0)
+    {
+        return Err(err!("Strike levels must be positive, got {}", level));
+    }
+    if let Some(horizon) = horizons
+        .iter()
+        .find(|horizon| **horizon == 0 || **horizon > simulated_log_prices.nrows())
+    {
+        return Err(err!(
+            "Horizon of {} hours is outside the {} simulated hours",
+            horizon,
+            simulated_log_prices.nrows()
+        ));
+    }
+
+    let simulated_prices = simulated_log_prices.map(f64::exp);
+    let reserve_prices = horizons
+        .iter()
+        .map(|horizon| {
+            let horizon_prices = simulated_prices.rows(0, *horizon).into_owned();
+            let twap_start = horizon.saturating_sub(settlement.twap_window_hours);
+
+            strike_levels
+                .iter()
+                .map(|level| {
+                    f64::exp(-0.05)
+                        * calculate_expected_payoff(
+                            payoff.payoff(),
+                            &horizon_prices,
+                            twap_start,
+                            level * twap,
+                        )
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(ReservePriceGrid {
+        strike_levels: strike_levels.to_vec(),
+        horizons: horizons.to_vec(),
+        reserve_prices,
+    })
+}
+
+/// `calculate_reserve_price` across `strike_levels` and `horizons`, from one simulation.
+pub fn calculate_reserve_price_grid(
+    period_start_timestamp: i64,
+    period_end_timestamp: i64,
+    season_param: &DVector<f64>,
+    de_seasonalized_detrended_simulated_prices: &DMatrix<f64>,
+    twap_7d: &[f64],
+    slope: f64,
+    intercept: f64,
+    log_base_fee_len: usize,
+    num_paths: usize,
+    n_periods: usize,
+    strike_levels: &[f64],
+    horizons: &[usize],
+    payoff: &PayoffKind,
+    settlement: &SettlementSpec,
+) -> Result<ReservePriceGrid> {
+    let simulated_log_prices = calculate_simulated_log_prices(
+        period_start_timestamp,
+        period_end_timestamp,
+        season_param,
+        de_seasonalized_detrended_simulated_prices,
+        twap_7d,
+        slope,
+        intercept,
+        log_base_fee_len,
+        num_paths,
+        n_periods,
+    )?;
+
+    calculated_reserve_price_grid_from_simulated_log_prices(
+        &simulated_log_prices,
+        twap_7d,
+        strike_levels,
+        horizons,
+        payoff,
+        settlement,
+    )
+}
+
+// tolerance is in percentage eg: 5.0 means 5%, as in error_bound_f64
+pub fn error_bound_reserve_price_grid(
+    target: &ReservePriceGrid,
+    calculated: &ReservePriceGrid,
+    tolerance: f64,
+) -> bool {
+    target.strike_levels == calculated.strike_levels
+        && target.horizons == calculated.horizons
+        && target.reserve_prices.len() == calculated.reserve_prices.len()
+        && target
+            .reserve_prices
+            .iter()
+            .zip(&calculated.reserve_prices)
+            .all(|(target_row, calculated_row)| {
+                target_row.len() == calculated_row.len()
+                    && target_row
+                        .iter()
+                        .zip(calculated_row)
+                        .all(|(target, calculated)| {
+                            error_bound_f64(*target, *calculated, tolerance)
+                        })
+            })
+}
@@ -0,0 +1,190 @@
+use eyre::{anyhow as err, Result};
+use nalgebra::DMatrix;
+use serde::{Deserialize, Serialize};
+
+use super::calculate_quantile;
+use crate::window::RoundHorizon;
+
+/// Quantile levels scored by default: the tails, the quartiles and the median.
+pub const SCORED_QUANTILE_LEVELS: [f64; 5] = [0.05, 0.25, 0.5, 0.75, 0.95];
+
+/// How well one simulated distribution of the settlement TWAP forecast the realized one.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ForecastScore {
+    /// Continuous ranked probability score, in fees; lower is better.
+    pub crps: f64,
+    pub quantile_levels: Vec<f64>,
+    /// `pinball_losses[q]` is the quantile loss at `quantile_levels[q]`.
+    pub pinball_losses: Vec<f64>,
+    /// Probability integral transform: the simulated probability of a TWAP below the
+    /// realized one. Uniform over many rounds when the model is calibrated.
+    pub pit: f64,
+}
+
+/// Scores over a walk-forward evaluation, e.g. one per `split_dataframe_into_periods`
+/// window or backtest round.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ForecastScoreSummary {
+    pub forecasts: usize,
+    pub mean_crps: f64,
+    pub quantile_levels: Vec<f64>,
+    pub mean_pinball_losses: Vec<f64>,
+    /// Counts of the PITs in `pit_histogram.len()` equal bins of [0, 1]. Flat when the
+    /// model is calibrated, U-shaped when it is overconfident and humped when it is not
+    /// confident enough.
+    pub pit_histogram: Vec<usize>,
+}
+
+/// CRPS of the empirical distribution of `samples` against `observed`,
+/// `E|X - y| - E|X - X'| / 2`.
+pub fn calculate_crps(samples: &[f64], observed: f64) -> Result<f64> {
+    if samples.is_empty() {
+        return Err(err!("Cannot score a forecast of no samples"));
+    }
+
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let n = sorted.len() as f64;
+
+    let mean_error = sorted.iter().map(|x| (x - observed).abs()).sum::<f64>() / n;
+    // sum over all pairs of |x_i - x_j|, from the order statistics
+    let pair_spread = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, x)| (2.0 * i as f64 - n + 1.0) * x)
+        .sum::<f64>()
+        * 2.0
+        / (n * n);
+
+    Ok(mean_error - pair_spread / 2.0)
+}
+
+/// Pinball loss at each of `quantile_levels` of the quantiles of `samples`, as in
+/// `calculate_quantile`, against `observed`.
+pub fn calculate_pinball_losses(
+    samples: &[f64],
+    observed: f64,
+    quantile_levels: &[f64],
+) -> Result<Vec<f64>> {
+    quantile_levels
+        .iter()
+        .map(|level| {
+            let error = observed - calculate_quantile(samples, *level)?;
+            Ok((level * error).max((level - 1.0) * error))
+        })
+        .collect()
+}
+
+/// Share of `samples` below `observed`, counting ties as half.
+pub fn calculate_pit(samples: &[f64], observed: f64) -> Result<f64> {
+    if samples.is_empty() {
+        return Err(err!("Cannot score a forecast of no samples"));
+    }
+
+    let below = samples.iter().filter(|x| **x < observed).count() as f64;
+    let ties = samples.iter().filter(|x| **x == observed).count() as f64;
+    Ok((below + ties / 2.0) / samples.len() as f64)
+}
+
+pub fn calculate_pit_histogram(pits: &[f64], num_bins: usize) -> Result<Vec<usize>> {
+    if num_bins == 0 {
+        return Err(err!("A PIT histogram needs at least one bin"));
+    }
+    if let Some(pit) = pits.iter().find(|pit| !(0.0..=1.0).contains(*pit)) {
+        return Err(err!("PIT must be in [0, 1], got {}", pit));
+    }
+
+    let mut histogram = vec![0; num_bins];
+    for pit in pits {
+        // a PIT of 1 falls in the last bin
+        histogram[((pit * num_bins as f64) as usize).min(num_bins - 1)] += 1;
+    }
+    Ok(histogram)
+}
+
+pub fn score_forecast(
+    samples: &[f64],
+    observed: f64,
+    quantile_levels: &[f64],
+) -> Result<ForecastScore> {
+    Ok(ForecastScore {
+        crps: calculate_crps(samples, observed)?,
+        quantile_levels: quantile_levels.to_vec(),
+        pinball_losses: calculate_pinball_losses(samples, observed, quantile_levels)?,
+        pit: calculate_pit(samples, observed)?,
+    })
+}
+
+/// Scores the settlement TWAPs of the simulated log prices of a round against that of
+/// `realized_fees`, the hourly fees of the round.
+pub fn score_settlement_twap(
+    simulated_log_prices: &DMatrix<f64>,
+    realized_fees: &[f64],
+    horizon: &RoundHorizon,
+    quantile_levels: &[f64],
+) -> Result<ForecastScore> {
+    if simulated_log_prices.nrows() != horizon.n_periods {
+        return Err(err!(
+            "Expected {} simulated hours for the round, got {}",
+            horizon.n_periods,
+            simulated_log_prices.nrows()
+        ));
+    }
+    if realized_fees.len() != horizon.n_periods {
+        return Err(err!(
+            "Expected {} realized hours for the round, got {}",
+            horizon.n_periods,
+            realized_fees.len()
+        ));
+    }
+
+    let settlement_hours = horizon.n_periods - horizon.settlement_start;
+    let simulated_twaps = simulated_log_prices
+        .rows(horizon.settlement_start, settlement_hours)
+        .map(f64::exp)
+        .row_mean()
+        .iter()
+        .copied()
+        .collect::<Vec<_>>();
+    let realized_twap = realized_fees[horizon.settlement_start..]
+        .iter()
+        .sum::<f64>()
+        / settlement_hours as f64;
+
+    score_forecast(&simulated_twaps, realized_twap, quantile_levels)
+}
+
+pub fn summarize_forecast_scores(
+    scores: &[ForecastScore],
+    num_bins: usize,
+) -> Result<ForecastScoreSummary> {
+    let first = scores
+        .first()
+        .ok_or_else(|| err!("Cannot summarize no forecasts"))?;
+    if scores
+        .iter()
+        .any(|score| score.quantile_levels != first.quantile_levels)
+    {
+        return Err(err!("Forecasts are scored at different quantile levels"));
+    }
+    let n = scores.len() as f64;
+
+    Ok(ForecastScoreSummary {
+        forecasts: scores.len(),
+        mean_crps: scores.iter().map(|score| score.crps).sum::<f64>() / n,
+        quantile_levels: first.quantile_levels.clone(),
+        mean_pinball_losses: (0..first.quantile_levels.len())
+            .map(|q| {
+                scores
+                    .iter()
+                    .map(|score| score.pinball_losses[q])
+                    .sum::<f64>()
+                    / n
+            })
+            .collect(),
+        pit_histogram: calculate_pit_histogram(
+            &scores.iter().map(|score| score.pit).collect::<Vec<_>>(),
+            num_bins,
+        )?,
+    })
+}
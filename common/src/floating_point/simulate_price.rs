@@ -2,13 +2,10 @@ use std::{f64::EPSILON, ops::Add};
 
 use eyre::Result;
 use nalgebra::{DMatrix, DVector};
-use rand::thread_rng;
-use rand_distr::Distribution;
-use statrs::distribution::{Binomial, Normal};
 
 use crate::{is_saddle_point, Solution};
 
-use super::neg_log_likelihood;
+use super::{neg_log_likelihood, FeeModelKind};
 
 pub fn function_value(position: &[f64], pt: &DVector<f64>, pt_1: &DVector<f64>) -> f64 {
     neg_log_likelihood(position, pt, pt_1)
@@ -139,62 +136,33 @@ pub fn post_minimize(
     n_periods: usize,
     num_paths: usize,
 ) -> Result<(DMatrix<f64>, Vec<f64>)> {
-    let dt = 1.0 / (365.0 * 24.0);
+    post_minimize_with_model(
+        FeeModelKind::MeanRevertingJump,
+        solution,
+        de_seasonalised_detrended_log_base_fee,
+        n_periods,
+        num_paths,
+    )
+}
 
+/// Simulates `fee_model` with the fitted parameters, starting every path from the last
+/// de-seasonalised, detrended log base fee.
+pub fn post_minimize_with_model(
+    fee_model: FeeModelKind,
+    solution: &Solution<f64>,
+    de_seasonalised_detrended_log_base_fee: &DVector<f64>,
+    n_periods: usize,
+    num_paths: usize,
+) -> Result<(DMatrix<f64>, Vec<f64>)> {
     // Extract the optimized parameters
     let params = &solution.position;
 
-    let alpha = params[0] / dt;
-    let kappa = (1.0 - params[1]) / dt;
-    let mu_j = params[2];
-    let sigma = (params[3] / dt).sqrt();
-    let sigma_j = params[4].sqrt();
-    let lambda_ = params[5] / dt;
-
-    // RNG for stochastic processes
-    let mut rng = thread_rng();
-
-    // Simulate the Poisson process (jumps)
-    let binom = Binomial::new(lambda_ * dt, 1)?;
-    let mut jumps = DMatrix::zeros(n_periods, num_paths);
-    for i in 0..n_periods {
-        for j in 0..num_paths {
-            jumps[(i, j)] = binom.sample(&mut rng) as f64;
-        }
-    }
-
-    // Initialize simulated prices
-    let mut simulated_prices = DMatrix::zeros(n_periods, num_paths);
     let initial_price =
         de_seasonalised_detrended_log_base_fee[de_seasonalised_detrended_log_base_fee.len() - 1];
-    for j in 0..num_paths {
-        simulated_prices[(0, j)] = initial_price;
-    }
-
-    // Generate standard normal variables
-    let normal = Normal::new(0.0, 1.0).unwrap();
-    let mut n1 = DMatrix::zeros(n_periods, num_paths);
-    let mut n2 = DMatrix::zeros(n_periods, num_paths);
-    for i in 0..n_periods {
-        for j in 0..num_paths {
-            n1[(i, j)] = normal.sample(&mut rng);
-            n2[(i, j)] = normal.sample(&mut rng);
-        }
-    }
-    // Simulate prices over time
-    for i in 1..n_periods {
-        for j in 0..num_paths {
-            let prev_price = simulated_prices[(i - 1, j)];
-            let current_n1 = n1[(i, j)];
-            let current_n2 = n2[(i, j)];
-            let current_j = jumps[(i, j)];
-
-            simulated_prices[(i, j)] = alpha * dt
-                + (1.0 - kappa * dt) * prev_price
-                + sigma * dt.sqrt() * current_n1
-                + current_j * (mu_j + sigma_j * current_n2);
-        }
-    }
+    let simulated_prices =
+        fee_model
+            .model()
+            .simulate(params, initial_price, n_periods, num_paths)?;
 
     Ok((simulated_prices, params.to_vec()))
 }
@@ -204,19 +172,35 @@ pub fn simulate_price(
     num_paths: usize,
     n_periods: usize,
 ) -> (DMatrix<f64>, Vec<f64>) {
+    simulate_price_with_model(
+        FeeModelKind::MeanRevertingJump,
+        de_seasonalised_detrended_log_base_fee,
+        num_paths,
+        n_periods,
+    )
+    .unwrap()
+}
+
+/// Fits `fee_model` to the de-seasonalised, detrended log base fee and simulates it.
+/// Returns the simulated log fees and the fitted parameters.
+pub fn simulate_price_with_model(
+    fee_model: FeeModelKind,
+    de_seasonalised_detrended_log_base_fee: &DVector<f64>,
+    num_paths: usize,
+    n_periods: usize,
+) -> Result<(DMatrix<f64>, Vec<f64>)> {
     let (pt, pt_1, var_pt) = pre_minimize(de_seasonalised_detrended_log_base_fee);
 
-    let initial_position = vec![-3.928e-02, 2.873e-04, 4.617e-02, var_pt, var_pt, 0.2];
+    let model = fee_model.model();
+    let initial_position = model.initial_params(var_pt);
     let max_iterations = 2400;
-    let solution = minimize(initial_position, &pt, &pt_1, max_iterations);
+    let solution = model.fit(initial_position, &pt, &pt_1, max_iterations);
 
-    let (simulated_prices, params) = post_minimize(
+    post_minimize_with_model(
+        fee_model,
         &solution,
         de_seasonalised_detrended_log_base_fee,
         n_periods,
         num_paths,
     )
-    .unwrap();
-
-    (simulated_prices, params)
 }
@@ -0,0 +1,98 @@
+use eyre::{anyhow as err, Result};
+
+use super::{add_twap_30d, calculate_30d_returns};
+
+// A vault selling calls on the base fee loses when fees rise, so the tail that matters is
+// the upper tail of the returns, the same one `calculate_max_returns` looks at.
+
+/// Tail of the TWAP-30d returns at one quantile.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QuantileTailRisk {
+    pub quantile: f64,
+    /// Historical VaR: the `quantile` quantile of the returns.
+    pub value_at_risk: f64,
+    /// Historical CVaR: the mean of the returns at or above the VaR.
+    pub conditional_value_at_risk: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TailRiskMetrics {
+    pub quantiles: Vec<QuantileTailRisk>,
+    pub max_return: f64,
+    pub min_return: f64,
+    /// Largest peak-to-trough decline of the TWAP-30d, as a fraction of the peak.
+    pub max_drawdown: f64,
+}
+
+/// Empirical `quantile` of the values, interpolating linearly between order statistics.
+pub fn calculate_quantile(values: &[f64], quantile: f64) -> Result<f64> {
+    if values.is_empty() {
+        return Err(err!("Cannot calculate a quantile of no values"));
+    }
+    if !(0.0..=1.0).contains(&quantile) {
+        return Err(err!("Quantile must be in [0, 1], got {}", quantile));
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let position = quantile * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    Ok(sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64))
+}
+
+pub fn calculate_historical_var(returns: &[f64], quantile: f64) -> Result<f64> {
+    calculate_quantile(returns, quantile)
+}
+
+pub fn calculate_historical_cvar(returns: &[f64], quantile: f64) -> Result<f64> {
+    let value_at_risk = calculate_historical_var(returns, quantile)?;
+
+    // never empty, as the maximum is at or above any quantile
+    let tail = returns
+        .iter()
+        .filter(|value| **value >= value_at_risk)
+        .collect::<Vec<_>>();
+    Ok(tail.iter().copied().sum::<f64>() / tail.len() as f64)
+}
+
+pub fn calculate_max_drawdown(series: &[f64]) -> Result<f64> {
+    if series.is_empty() {
+        return Err(err!("Cannot calculate the drawdown of no values"));
+    }
+
+    let mut peak = series[0];
+    let mut max_drawdown = 0.0f64;
+    for value in series {
+        peak = peak.max(*value);
+        max_drawdown = max_drawdown.max((peak - value) / peak);
+    }
+
+    Ok(max_drawdown)
+}
+
+/// Tail profile of the returns over the same windows as `calculate_max_returns`: the
+/// TWAP-30d of the hourly fees, and its returns over 30 days (10 days for POC data).
+pub fn calculate_tail_risk(data: &Vec<f64>, quantiles: &[f64]) -> Result<TailRiskMetrics> {
+    let twap_30d = add_twap_30d(data)?;
+    let returns = calculate_30d_returns(&twap_30d)?;
+
+    let quantiles = quantiles
+        .iter()
+        .map(|quantile| {
+            Ok(QuantileTailRisk {
+                quantile: *quantile,
+                value_at_risk: calculate_historical_var(&returns, *quantile)?,
+                conditional_value_at_risk: calculate_historical_cvar(&returns, *quantile)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(TailRiskMetrics {
+        quantiles,
+        max_return: calculate_quantile(&returns, 1.0)?,
+        min_return: calculate_quantile(&returns, 0.0)?,
+        max_drawdown: calculate_max_drawdown(&twap_30d)?,
+    })
+}
@@ -0,0 +1,165 @@
+use std::ops::Range;
+
+use eyre::{anyhow as err, Result};
+use sha2::Digest;
+use starknet_core::types::Felt;
+
+use super::hash_avg_fees_in_batch;
+
+/// Leaf used to pad the tree to a power of two.
+pub const EMPTY_MERKLE_LEAF: [u8; 32] = [0u8; 32];
+
+/// Leaf hash of one `(timestamp, data_points, avg_fee)` record: SHA-256 over its three felts,
+/// laid out as in `hash_avg_fees_in_batch`. Leaves hash 96 bytes and nodes 64 bytes, so a
+/// node can't be passed off as a leaf.
+pub fn hash_avg_fees_leaf(record: &(u64, u64, Felt)) -> [u8; 32] {
+    hash_avg_fees_in_batch(std::slice::from_ref(record))
+        .try_into()
+        .unwrap()
+}
+
+pub fn hash_merkle_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = sha2::Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// SHA-256 Merkle tree over hourly `AvgFees` records, padded with `EMPTY_MERKLE_LEAF`
+/// up to the next power of two.
+pub struct AvgFeesMerkleTree {
+    leaf_count: usize,
+    // levels[0] holds the padded leaves and the last level the root
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl AvgFeesMerkleTree {
+    pub fn new(records: &[(u64, u64, Felt)]) -> Result<Self> {
+        if records.is_empty() {
+            return Err(err!("Cannot build a Merkle tree over no records"));
+        }
+
+        let mut leaves = records.iter().map(hash_avg_fees_leaf).collect::<Vec<_>>();
+        leaves.resize(records.len().next_power_of_two(), EMPTY_MERKLE_LEAF);
+
+        let mut levels = vec![leaves];
+        while levels[levels.len() - 1].len() > 1 {
+            let next = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|pair| hash_merkle_node(&pair[0], &pair[1]))
+                .collect();
+            levels.push(next);
+        }
+
+        Ok(AvgFeesMerkleTree {
+            leaf_count: records.len(),
+            levels,
+        })
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels[self.levels.len() - 1][0]
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        self.leaf_count
+    }
+
+    /// Siblings needed to recompute the root from the leaves in `range`, ordered bottom-up
+    /// and, within a level, left sibling before right sibling.
+    pub fn prove_range(&self, range: Range<usize>) -> Result<Vec<[u8; 32]>> {
+        check_leaf_range(&range, self.leaf_count)?;
+
+        let mut siblings = vec![];
+        let (mut first, mut last) = (range.start, range.end - 1);
+        for level in &self.levels[..self.levels.len() - 1] {
+            if first % 2 == 1 {
+                siblings.push(level[first - 1]);
+            }
+            if last % 2 == 0 {
+                siblings.push(level[last + 1]);
+            }
+            first /= 2;
+            last /= 2;
+        }
+
+        Ok(siblings)
+    }
+}
+
+fn check_leaf_range(range: &Range<usize>, leaf_count: usize) -> Result<()> {
+    if range.start >= range.end || range.end > leaf_count {
+        return Err(err!(
+            "Leaf range {}..{} is empty or outside the {} leaves of the tree",
+            range.start,
+            range.end,
+            leaf_count
+        ));
+    }
+
+    Ok(())
+}
+
+/// Recomputes the root of a tree over `leaf_count` records from the contiguous `records`
+/// starting at leaf `start` and the siblings returned by `AvgFeesMerkleTree::prove_range`.
+pub fn compute_avg_fees_range_root(
+    leaf_count: usize,
+    start: usize,
+    records: &[(u64, u64, Felt)],
+    siblings: &[[u8; 32]],
+) -> Result<[u8; 32]> {
+    check_leaf_range(&(start..start + records.len()), leaf_count)?;
+
+    let mut siblings = siblings.iter();
+    let mut next_sibling = || {
+        siblings
+            .next()
+            .copied()
+            .ok_or_else(|| err!("Not enough Merkle siblings for the leaf range"))
+    };
+
+    let mut nodes = records.iter().map(hash_avg_fees_leaf).collect::<Vec<_>>();
+    let mut first = start;
+    for _ in 0..leaf_count.next_power_of_two().trailing_zeros() {
+        let last = first + nodes.len() - 1;
+        if first % 2 == 1 {
+            nodes.insert(0, next_sibling()?);
+            first -= 1;
+        }
+        if last % 2 == 0 {
+            nodes.push(next_sibling()?);
+        }
+
+        nodes = nodes
+            .chunks(2)
+            .map(|pair| hash_merkle_node(&pair[0], &pair[1]))
+            .collect();
+        first /= 2;
+    }
+
+    if siblings.next().is_some() {
+        return Err(err!("Too many Merkle siblings for the leaf range"));
+    }
+
+    Ok(nodes[0])
+}
+
+/// Checks that `records` are the leaves `start..start + records.len()` of the tree with `root`.
+pub fn verify_avg_fees_range(
+    root: &[u8; 32],
+    leaf_count: usize,
+    start: usize,
+    records: &[(u64, u64, Felt)],
+    siblings: &[[u8; 32]],
+) -> Result<()> {
+    let computed_root = compute_avg_fees_range_root(leaf_count, start, records, siblings)?;
+    if computed_root != *root {
+        return Err(err!(
+            "Leaves {}..{} do not match the Merkle root",
+            start,
+            start + records.len()
+        ));
+    }
+
+    Ok(())
+}
@@ -1,14 +1,57 @@
+use eyre::{anyhow as err, Result};
+use serde::{Deserialize, Serialize};
 use sha2::Digest;
-use starknet_core::types::{Felt, U256};
+use starknet_core::types::Felt;
+use starknet_crypto::poseidon_hash_many;
+
+use crate::window::SECONDS_IN_HOUR;
+
+pub mod merkle;
+pub use merkle::*;
+
+/// Hash function used for every batch and for the hash of the batch hashes.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum HashScheme {
+    /// SHA-256 over the 32-byte words of the felts, as in the fossil hash store.
+    Sha256,
+    /// Poseidon over the felts themselves, which is cheap to recompute on Starknet.
+    Poseidon,
+}
+
+/// How a fee series is committed to. `batch_size` must match the Cairo `num_in_a_batch`
+/// and counts felts for bare fees and records for `AvgFees`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HashConfig {
+    pub batch_size: usize,
+    pub scheme: HashScheme,
+}
+
+impl Default for HashConfig {
+    fn default() -> Self {
+        HashConfig {
+            batch_size: 180,
+            scheme: HashScheme::Sha256,
+        }
+    }
+}
 
 pub fn generate_batched_hash_for_all_avg_base_fees(input: &Vec<Felt>) -> Vec<u8> {
+    generate_batched_hash_for_all_avg_base_fees_with_config(input, &HashConfig::default())
+}
+
+pub fn generate_batched_hash_for_all_avg_base_fees_with_config(
+    input: &[Felt],
+    config: &HashConfig,
+) -> Vec<u8> {
+    assert!(config.batch_size > 0, "Hash batch size must be positive");
+
     let mut avg_base_gas_fee_hashes = vec![];
-    for avg_base_fees in input.chunks(180) {
-        let hash = hash_avg_base_fees_in_batch(avg_base_fees);
+    for avg_base_fees in input.chunks(config.batch_size) {
+        let hash = hash_felts_in_batch(avg_base_fees, config.scheme);
         avg_base_gas_fee_hashes.push(hash);
     }
 
-    hash_of_hash_of_avg_base_fees(&avg_base_gas_fee_hashes)
+    hash_of_hashes(&avg_base_gas_fee_hashes, config.scheme)
 }
 
 pub fn hash_avg_base_fees_in_batch(input: &[Felt]) -> Vec<u8> {
@@ -24,6 +67,156 @@ pub fn hash_avg_base_fees_in_batch(input: &[Felt]) -> Vec<u8> {
     hash.to_vec()
 }
 
+/// 32-byte hash of one batch. Poseidon hashes are returned as the big-endian bytes of the felt.
+pub fn hash_felts_in_batch(input: &[Felt], scheme: HashScheme) -> Vec<u8> {
+    match scheme {
+        HashScheme::Sha256 => hash_avg_base_fees_in_batch(input),
+        HashScheme::Poseidon => poseidon_hash_many(input).to_bytes_be().to_vec(),
+    }
+}
+
+pub fn hash_of_hashes(hashes: &[Vec<u8>], scheme: HashScheme) -> Vec<u8> {
+    match scheme {
+        HashScheme::Sha256 => hash_of_hash_of_avg_base_fees(hashes),
+        HashScheme::Poseidon => {
+            let felts = hashes
+                .iter()
+                .map(|hash| Felt::from_bytes_be_slice(hash))
+                .collect::<Vec<_>>();
+            poseidon_hash_many(&felts).to_bytes_be().to_vec()
+        }
+    }
+}
+
+/// Batched hash of Cairo `AvgFees { timestamp, data_points, avg_fee }` records, given as
+/// `(timestamp, data_points, avg_fee)`. Each record is hashed as its three Serde felts,
+/// laid out like the felts of `generate_batched_hash_for_all_avg_base_fees`.
+pub fn generate_batched_hash_for_all_avg_fees(input: &[(u64, u64, Felt)]) -> Vec<u8> {
+    generate_batched_hash_for_all_avg_fees_with_config(input, &HashConfig::default())
+}
+
+pub fn generate_batched_hash_for_all_avg_fees_with_config(
+    input: &[(u64, u64, Felt)],
+    config: &HashConfig,
+) -> Vec<u8> {
+    hash_of_hashes(
+        &hash_avg_fees_batches_with_config(input, config),
+        config.scheme,
+    )
+}
+
+/// Hash of every batch of `AvgFees` records, before they are hashed together.
+pub fn hash_avg_fees_batches_with_config(
+    input: &[(u64, u64, Felt)],
+    config: &HashConfig,
+) -> Vec<Vec<u8>> {
+    assert!(config.batch_size > 0, "Hash batch size must be positive");
+
+    let mut avg_fees_hashes = vec![];
+    for avg_fees in input.chunks(config.batch_size) {
+        let hash = hash_felts_in_batch(&convert_avg_fees_to_felts(avg_fees), config.scheme);
+        avg_fees_hashes.push(hash);
+    }
+
+    avg_fees_hashes
+}
+
+/// Moves a window of batch hashes forward: the oldest `new_batch_hashes.len()` batches are
+/// dropped and the new ones appended, keeping the window length.
+pub fn roll_batch_hashes<T: Clone>(
+    previous_batch_hashes: &[T],
+    new_batch_hashes: &[T],
+) -> Result<Vec<T>> {
+    if new_batch_hashes.len() > previous_batch_hashes.len() {
+        return Err(err!(
+            "Cannot roll {} new batches into a window of {} batches",
+            new_batch_hashes.len(),
+            previous_batch_hashes.len()
+        ));
+    }
+
+    let mut batch_hashes = previous_batch_hashes[new_batch_hashes.len()..].to_vec();
+    batch_hashes.extend_from_slice(new_batch_hashes);
+
+    Ok(batch_hashes)
+}
+
+pub fn hash_avg_fees_in_batch(input: &[(u64, u64, Felt)]) -> Vec<u8> {
+    hash_avg_base_fees_in_batch(&convert_avg_fees_to_felts(input))
+}
+
+fn convert_avg_fees_to_felts(input: &[(u64, u64, Felt)]) -> Vec<Felt> {
+    input
+        .iter()
+        .flat_map(|(timestamp, data_points, avg_fee)| {
+            [Felt::from(*timestamp), Felt::from(*data_points), *avg_fee]
+        })
+        .collect()
+}
+
+/// Checks that `AvgFees` records cover contiguous hours, starting on an hour boundary,
+/// and that every average is taken over at least one block.
+pub fn validate_hourly_avg_fees(input: &[(u64, u64, Felt)]) -> Result<()> {
+    let (first_timestamp, _, _) = input
+        .first()
+        .ok_or_else(|| err!("No avg fees records provided"))?;
+    if first_timestamp % SECONDS_IN_HOUR as u64 != 0 {
+        return Err(err!(
+            "First timestamp {} is not aligned to an hour",
+            first_timestamp
+        ));
+    }
+
+    for (i, (timestamp, data_points, _)) in input.iter().enumerate() {
+        if *data_points == 0 {
+            return Err(err!(
+                "Avg fee at timestamp {} has no data points",
+                timestamp
+            ));
+        }
+
+        let expected_timestamp = first_timestamp + i as u64 * SECONDS_IN_HOUR as u64;
+        if *timestamp != expected_timestamp {
+            return Err(err!(
+                "Avg fees are not contiguous hourly: expected timestamp {}, got {}",
+                expected_timestamp,
+                timestamp
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that `input` holds whole batches of contiguous hourly records following the hour
+/// `previous_last_timestamp`, so that their batch hashes can be rolled into the previous window.
+pub fn validate_rolling_avg_fees(
+    previous_last_timestamp: u64,
+    input: &[(u64, u64, Felt)],
+    batch_size: usize,
+) -> Result<()> {
+    validate_hourly_avg_fees(input)?;
+
+    if batch_size == 0 || input.len() % batch_size != 0 {
+        return Err(err!(
+            "{} records do not fill whole batches of {}",
+            input.len(),
+            batch_size
+        ));
+    }
+
+    let expected_timestamp = previous_last_timestamp + SECONDS_IN_HOUR as u64;
+    if input[0].0 != expected_timestamp {
+        return Err(err!(
+            "New records start at timestamp {}, expected {}",
+            input[0].0,
+            expected_timestamp
+        ));
+    }
+
+    Ok(())
+}
+
 fn convert_felt_to_u32_array(input: Felt) -> [u32; 8] {
     let input_le_bytes = input.to_bytes_le();
     let mut input_array = [0u32; 8];
@@ -42,7 +235,7 @@ fn convert_32_bytes_words_to_hash_input(input: &Vec<u32>) -> Vec<u8> {
     result
 }
 
-pub fn hash_of_hash_of_avg_base_fees(hashes: &Vec<Vec<u8>>) -> Vec<u8> {
+pub fn hash_of_hash_of_avg_base_fees(hashes: &[Vec<u8>]) -> Vec<u8> {
     let mut input_array = vec![];
     for hash in hashes {
         input_array.append(&mut hash.to_vec());
@@ -52,10 +245,55 @@ pub fn hash_of_hash_of_avg_base_fees(hashes: &Vec<Vec<u8>>) -> Vec<u8> {
     hash.to_vec()
 }
 
-pub fn convert_felt_to_f64(input: Felt) -> f64 {
-    let input_u256 = U256::from(input);
+/// SHA-256 digest of an f64 vector, committed by the sub-guests in place of the vector itself.
+/// The length is hashed as a little-endian u64 prefix followed by every value's
+/// little-endian bytes, and the digest is returned as big-endian u32 words.
+pub fn hash_f64s(values: &[f64]) -> [u32; 8] {
+    let mut hasher = sha2::Sha256::new();
+    hasher.update((values.len() as u64).to_le_bytes());
+    for value in values {
+        hasher.update(value.to_le_bytes());
+    }
+
+    convert_hash_to_u32_array(&hasher.finalize())
+}
+
+/// `hash_f64s` of every batch of `batch_size` values. Unlike `hash_f64s` of the whole
+/// vector, these can be rolled forward with `roll_batch_hashes` by the rolling hashing guest.
+pub fn hash_f64s_in_batches(values: &[f64], batch_size: usize) -> Vec<[u32; 8]> {
+    assert!(batch_size > 0, "Hash batch size must be positive");
+
+    values.chunks(batch_size).map(hash_f64s).collect()
+}
+
+/// SHA-256 digest of the batch digests of `hash_f64s_in_batches`, laid out as in
+/// `hash_f64s` with every digest's big-endian bytes in place of the values.
+pub fn hash_f64_batch_digests(batch_digests: &[[u32; 8]]) -> [u32; 8] {
+    let mut hasher = sha2::Sha256::new();
+    hasher.update((batch_digests.len() as u64).to_le_bytes());
+    for digest in batch_digests {
+        hasher.update(convert_u32_array_to_hash(digest));
+    }
+
+    convert_hash_to_u32_array(&hasher.finalize())
+}
+
+/// Splits a 32-byte hash into the big-endian `[u32; 8]` words committed by the hashing guests.
+pub fn convert_hash_to_u32_array(hash: &[u8]) -> [u32; 8] {
+    let mut u32_result = [0u32; 8];
+    for i in 0..8 {
+        u32_result[i] = u32::from_be_bytes(hash[i * 4..(i + 1) * 4].try_into().unwrap());
+    }
+
+    u32_result
+}
+
+/// Inverse of `convert_hash_to_u32_array`.
+pub fn convert_u32_array_to_hash(words: &[u32; 8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    for i in 0..8 {
+        hash[i * 4..(i + 1) * 4].copy_from_slice(&words[i].to_be_bytes());
+    }
 
-    const TWO_POW_128: f64 = 340282366920938463463374607431768211456.0;
-    let decimal = input_u256.low() as f64 / TWO_POW_128;
-    input_u256.high() as f64 + decimal
+    hash
 }
@@ -0,0 +1,209 @@
+use eyre::{anyhow as err, Result};
+use sha3::{Digest, Keccak256};
+
+// positions of the fields we read in the RLP list of an Ethereum block header
+const PARENT_HASH_INDEX: usize = 0;
+const NUMBER_INDEX: usize = 8;
+const TIMESTAMP_INDEX: usize = 11;
+const BASE_FEE_PER_GAS_INDEX: usize = 15;
+
+/// The fields of an Ethereum block header needed to link and read fees from a header chain.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedHeader {
+    /// Keccak-256 of the RLP-encoded header
+    pub hash: [u8; 32],
+    pub parent_hash: [u8; 32],
+    pub number: u64,
+    pub timestamp: u64,
+    /// `None` for headers from before London (EIP-1559)
+    pub base_fee: Option<u64>,
+}
+
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+/// Decodes an RLP-encoded Ethereum block header. Every fork's header is a flat list of byte
+/// strings, so nested lists are rejected, as are non-canonical encodings.
+pub fn decode_block_header(rlp: &[u8]) -> Result<DecodedHeader> {
+    let fields = decode_rlp_string_list(rlp)?;
+    if fields.len() <= TIMESTAMP_INDEX {
+        return Err(err!("Block header has only {} fields", fields.len()));
+    }
+
+    let parent_hash = fields[PARENT_HASH_INDEX]
+        .try_into()
+        .map_err(|_| err!("Block header parent hash is not 32 bytes"))?;
+    let base_fee = match fields.get(BASE_FEE_PER_GAS_INDEX) {
+        Some(field) => Some(decode_rlp_u64(field)?),
+        None => None,
+    };
+
+    Ok(DecodedHeader {
+        hash: keccak256(rlp),
+        parent_hash,
+        number: decode_rlp_u64(fields[NUMBER_INDEX])?,
+        timestamp: decode_rlp_u64(fields[TIMESTAMP_INDEX])?,
+        base_fee,
+    })
+}
+
+/// Decodes a contiguous range of RLP-encoded headers, oldest first, and checks that each
+/// header's parent hash is the hash of the header before it and that the last header hashes
+/// to the trusted `anchor_hash`. The whole range is then as trusted as the anchor.
+pub fn verify_header_chain(
+    headers: &[Vec<u8>],
+    anchor_hash: &[u8; 32],
+) -> Result<Vec<DecodedHeader>> {
+    if headers.is_empty() {
+        return Err(err!("Cannot verify an empty header chain"));
+    }
+
+    let headers = headers
+        .iter()
+        .map(|header| decode_block_header(header))
+        .collect::<Result<Vec<_>>>()?;
+
+    for pair in headers.windows(2) {
+        if pair[1].number != pair[0].number + 1 {
+            return Err(err!(
+                "Headers are not consecutive: block {} follows block {}",
+                pair[1].number,
+                pair[0].number
+            ));
+        }
+        if pair[1].parent_hash != pair[0].hash {
+            return Err(err!(
+                "Block {} does not link to the hash of block {}",
+                pair[1].number,
+                pair[0].number
+            ));
+        }
+    }
+
+    let last = &headers[headers.len() - 1];
+    if last.hash != *anchor_hash {
+        return Err(err!(
+            "Block {} does not hash to the anchor hash",
+            last.number
+        ));
+    }
+
+    Ok(headers)
+}
+
+/// `(number, timestamp, base_fee)` of verified headers, as taken by `aggregate_hourly_base_fees`.
+/// Fails on headers from before London, which have no base fee.
+pub fn extract_base_fees(headers: &[DecodedHeader]) -> Result<Vec<(u64, u64, u64)>> {
+    headers
+        .iter()
+        .map(|header| {
+            let base_fee = header
+                .base_fee
+                .ok_or_else(|| err!("Block {} has no base fee", header.number))?;
+            Ok((header.number, header.timestamp, base_fee))
+        })
+        .collect()
+}
+
+// Splits an RLP item into (payload offset, payload length, is_list), checking that the
+// payload fits in `data` and that the length is canonically encoded.
+fn decode_rlp_item_header(data: &[u8]) -> Result<(usize, usize, bool)> {
+    let prefix = *data
+        .first()
+        .ok_or_else(|| err!("Unexpected end of RLP data"))?;
+
+    let (offset, length, is_list) = match prefix {
+        0x00..=0x7f => (0, 1, false),
+        0x80..=0xb7 => {
+            let length = (prefix - 0x80) as usize;
+            if length == 1 && data.get(1).is_some_and(|byte| *byte < 0x80) {
+                return Err(err!(
+                    "Non-canonical RLP: single byte below 0x80 as a string"
+                ));
+            }
+            (1, length, false)
+        }
+        0xb8..=0xbf => {
+            let length = decode_rlp_long_length(data, (prefix - 0xb7) as usize)?;
+            (1 + (prefix - 0xb7) as usize, length, false)
+        }
+        0xc0..=0xf7 => (1, (prefix - 0xc0) as usize, true),
+        0xf8..=0xff => {
+            let length = decode_rlp_long_length(data, (prefix - 0xf7) as usize)?;
+            (1 + (prefix - 0xf7) as usize, length, true)
+        }
+    };
+
+    // offset <= data.len() here, as the long form lengths were read from data
+    if length > data.len() - offset {
+        return Err(err!("RLP item of {} bytes overruns its data", length));
+    }
+
+    Ok((offset, length, is_list))
+}
+
+fn decode_rlp_long_length(data: &[u8], length_of_length: usize) -> Result<usize> {
+    let bytes = data
+        .get(1..1 + length_of_length)
+        .ok_or_else(|| err!("Unexpected end of RLP data"))?;
+    if bytes[0] == 0 || length_of_length > 8 {
+        return Err(err!("Non-canonical RLP length"));
+    }
+
+    let length = bytes
+        .iter()
+        .fold(0u64, |acc, byte| (acc << 8) | *byte as u64);
+    if length < 56 {
+        return Err(err!(
+            "Non-canonical RLP: long form for a {} byte payload",
+            length
+        ));
+    }
+
+    usize::try_from(length).map_err(|_| err!("RLP length {} is too large", length))
+}
+
+// Decodes `data` as exactly one RLP list whose items are all byte strings.
+fn decode_rlp_string_list(data: &[u8]) -> Result<Vec<&[u8]>> {
+    let (offset, length, is_list) = decode_rlp_item_header(data)?;
+    if !is_list {
+        return Err(err!("Expected an RLP list"));
+    }
+    if offset + length != data.len() {
+        return Err(err!(
+            "{} trailing bytes after the RLP list",
+            data.len() - offset - length
+        ));
+    }
+
+    let mut payload = &data[offset..];
+    let mut items = vec![];
+    while !payload.is_empty() {
+        let (offset, length, is_list) = decode_rlp_item_header(payload)?;
+        if is_list {
+            return Err(err!("Unexpected nested RLP list"));
+        }
+        items.push(&payload[offset..offset + length]);
+        payload = &payload[offset + length..];
+    }
+
+    Ok(items)
+}
+
+// Big-endian integer without leading zeros, as RLP encodes scalars.
+fn decode_rlp_u64(bytes: &[u8]) -> Result<u64> {
+    if bytes.len() > 8 {
+        return Err(err!(
+            "RLP integer of {} bytes does not fit a u64",
+            bytes.len()
+        ));
+    }
+    if bytes.first() == Some(&0) {
+        return Err(err!("Non-canonical RLP integer with leading zeros"));
+    }
+
+    Ok(bytes
+        .iter()
+        .fold(0u64, |acc, byte| (acc << 8) | *byte as u64))
+}
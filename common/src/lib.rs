@@ -1,3 +1,8 @@
+pub mod aggregation;
+pub use aggregation::*;
+pub mod backtest;
+pub mod fixed_point;
+pub use fixed_point::*;
 pub mod floating_point;
 #[cfg(feature = "original")]
 pub mod original;
@@ -6,6 +11,10 @@ pub mod solution;
 pub use solution::*;
 pub mod hashing;
 pub use hashing::*;
+pub mod headers;
+pub use headers::*;
+pub mod window;
+pub use window::*;
 
 pub mod common;
 pub mod tests;
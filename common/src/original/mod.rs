@@ -16,6 +16,8 @@ use rand::thread_rng;
 use rand_distr::Distribution;
 use simulate_price::simulate_prices;
 
+use crate::window::RoundHorizon;
+
 pub mod calculate_twap;
 pub mod max_return;
 mod simulate_price;
@@ -208,8 +210,9 @@ pub struct AllInputsToReservePrice {
 pub fn calculate_reserve_price(
     inputs: &Vec<(i64, f64)>,
     num_paths: usize,
-    n_periods: usize,
+    horizon: &RoundHorizon,
 ) -> AllInputsToReservePrice {
+    let n_periods = horizon.n_periods;
     let mut df = convert_input_to_df(inputs);
 
     let period_end_date_timestamp = df
@@ -322,9 +325,8 @@ pub fn calculate_reserve_price(
 
     let simulated_prices = simulated_log_prices.mapv(f64::exp);
 
-    let twap_start = n_periods.saturating_sub(24 * 7);
     let final_prices_twap = simulated_prices
-        .slice(s![twap_start.., ..])
+        .slice(s![horizon.settlement_start.., ..])
         .mean_axis(Axis(0))
         .unwrap();
     let twap_7d_series = df.column("TWAP_7d").unwrap();
@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod tests {
+    use starknet_core::types::Felt;
+
+    use crate::{
+        aggregate_hourly_base_fees, average_to_fixed_point, convert_felt_to_f64_checked,
+        hash_blocks, tests::mock::get_mock_blocks, validate_hourly_avg_fees,
+    };
+
+    #[test]
+    fn test_aggregate_hourly_base_fees() {
+        let blocks = get_mock_blocks(1695470400, 2);
+        let records = aggregate_hourly_base_fees(&blocks).unwrap();
+
+        // results gotten from python integer arithmetic
+        assert_eq!(
+            records,
+            vec![
+                (
+                    1695470400,
+                    300,
+                    Felt::from_hex_unchecked("0x5d7c74fc000000000000000000000000000000000")
+                ),
+                (
+                    1695474000,
+                    300,
+                    Felt::from_hex_unchecked("0x5cbdb8dc000000000000000000000000000000000")
+                ),
+            ]
+        );
+        assert!(validate_hourly_avg_fees(&records).is_ok());
+    }
+
+    #[test]
+    fn test_aggregate_hourly_base_fees_skips_partial_hours() {
+        // dropping the bounding blocks leaves the first and last hours partial
+        let blocks = get_mock_blocks(1695470400, 3);
+        let records = aggregate_hourly_base_fees(&blocks[1..blocks.len() - 1]).unwrap();
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].0, 1695474000);
+        assert_eq!(records[0].1, 300);
+    }
+
+    #[test]
+    fn test_aggregate_hourly_base_fees_rejects_invalid_blocks() {
+        let blocks = get_mock_blocks(1695470400, 2);
+
+        assert!(aggregate_hourly_base_fees(&blocks[..2]).is_err());
+        // a single hour without its bounding blocks
+        assert!(aggregate_hourly_base_fees(&get_mock_blocks(1695470400, 1)[1..]).is_err());
+
+        let mut missing_block = blocks.clone();
+        missing_block.remove(100);
+        assert!(aggregate_hourly_base_fees(&missing_block).is_err());
+
+        let mut unordered = blocks.clone();
+        unordered[100].1 = unordered[99].1;
+        assert!(aggregate_hourly_base_fees(&unordered).is_err());
+
+        // consecutive blocks, but none in the second hour
+        let mut empty_hour = blocks[..302].to_vec();
+        empty_hour[301].1 += 3600;
+        assert!(aggregate_hourly_base_fees(&empty_hour).is_err());
+    }
+
+    #[test]
+    fn test_average_to_fixed_point() {
+        assert_eq!(
+            average_to_fixed_point(7, 2),
+            Felt::from_hex_unchecked("0x380000000000000000000000000000000")
+        );
+        // 10 / 3, the fraction rounded down to 128 bits
+        let third = average_to_fixed_point(10, 3);
+        assert_eq!(
+            third,
+            Felt::from_hex_unchecked("0x355555555555555555555555555555555")
+        );
+        assert_eq!(
+            convert_felt_to_f64_checked(third).unwrap().value,
+            10.0 / 3.0
+        );
+        assert_eq!(
+            average_to_fixed_point(u64::MAX as u128 * 300, 300),
+            Felt::from(u64::MAX) * Felt::TWO.pow(128u32)
+        );
+    }
+
+    #[test]
+    fn test_hash_blocks() {
+        // sha256 of the u64 block count followed by every field's little-endian bytes
+        assert_eq!(
+            hash_blocks(&get_mock_blocks(1695470400, 1)[..3]),
+            [
+                281227420, 1070777272, 3061942120, 2384229113, 3189371963, 2965268354, 863515601,
+                554954992
+            ]
+        );
+        assert_ne!(
+            hash_blocks(&get_mock_blocks(1695470400, 1)[..3]),
+            hash_blocks(&get_mock_blocks(1695470400, 1)[..2])
+        );
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backtest::{
+            backtest_round, run_backtest, summarize_backtest, BacktestConfig, BacktestRound,
+        },
+        floating_point::{add_twap_7d, FeeModelKind, ForecastScore},
+        window::SettlementSpec,
+    };
+
+    const START: i64 = 1708833600 - 3600 * 24 * 30 * 5;
+
+    // daily seasonality around 20 gwei, with deterministic noise
+    fn synthetic_fees(hours: usize) -> Vec<f64> {
+        (0..hours)
+            .map(|hour| {
+                let t = hour as f64;
+                let season = 0.3 * (2.0 * std::f64::consts::PI * t / 24.0).sin();
+                let noise = 0.2 * (t * 12.9898).sin() * (t * 78.233).cos();
+                20e9 * f64::exp(season + noise)
+            })
+            .collect()
+    }
+
+    fn config() -> BacktestConfig {
+        BacktestConfig {
+            history_hours: 336,
+            round_hours: 192,
+            step_hours: 192,
+            // the MRJ fit of the original POC is unbounded, and finds a negative jump
+            // variance on these fees
+            fee_model: FeeModelKind::Garch,
+            num_paths: 200,
+            ..BacktestConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_run_backtest() {
+        let fees = synthetic_fees(720);
+        let config = config();
+        let rounds = run_backtest(&fees, START, &config).unwrap();
+
+        // rounds start after 336 and 528 hours; a third would run past the data
+        assert_eq!(rounds.len(), 2);
+        assert_eq!(rounds[0].round_start_timestamp, START + 336 * 3600);
+        assert_eq!(rounds[1].round_start_timestamp, START + 528 * 3600);
+        assert_eq!(rounds[1].round_end_timestamp, START + 720 * 3600);
+
+        for (round, round_start) in rounds.iter().zip([336, 528]) {
+            // struck at the TWAP of the history, settled on the last 168 realized hours
+            let twap_7d = add_twap_7d(&fees[round_start - 336..round_start].to_vec()).unwrap();
+            assert_eq!(round.strike, twap_7d[twap_7d.len() - 1]);
+            let settlement_prices = &fees[round_start + 24..round_start + 192];
+            let payout = config
+                .payoff
+                .payoff()
+                .payoff(settlement_prices, round.strike);
+            assert_eq!(round.payout, payout);
+
+            assert!(round.reserve_price > 0.0);
+            assert!((0.0..=1.0).contains(&round.payout_probability));
+            assert!(round.expected_payout >= 0.0);
+            assert_eq!(round.pnl, round.reserve_price - round.payout);
+
+            assert!(round.score.crps >= 0.0);
+            assert!((0.0..=1.0).contains(&round.score.pit));
+            assert_eq!(round.score.quantile_levels, config.quantile_levels);
+        }
+        assert_eq!(
+            summarize_backtest(&rounds, config.pit_bins)
+                .unwrap()
+                .forecast
+                .pit_histogram
+                .iter()
+                .sum::<usize>(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_run_backtest_rejects_short_history() {
+        let config = config();
+        assert!(run_backtest(&synthetic_fees(500), START, &config).is_err());
+        assert!(run_backtest(
+            &synthetic_fees(720),
+            START,
+            &BacktestConfig {
+                step_hours: 0,
+                ..config.clone()
+            }
+        )
+        .is_err());
+        // the settlement window must fit in the round
+        let fees = synthetic_fees(720);
+        assert!(backtest_round(
+            &fees[..336],
+            &fees[336..432],
+            START + 336 * 3600,
+            &BacktestConfig {
+                settlement: SettlementSpec {
+                    twap_window_hours: 168,
+                },
+                ..config
+            }
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_summarize_backtest() {
+        let round = |reserve_price: f64, payout_probability: f64, payout: f64| BacktestRound {
+            round_start_timestamp: START,
+            round_end_timestamp: START + 720 * 3600,
+            strike: 100.0,
+            reserve_price,
+            payout_probability,
+            expected_payout: 10.0,
+            settlement_twap: 100.0 + payout,
+            payout,
+            pnl: reserve_price - payout,
+            score: ForecastScore {
+                crps: 1.0,
+                quantile_levels: vec![0.5],
+                pinball_losses: vec![payout],
+                pit: payout_probability,
+            },
+        };
+        let rounds = [round(10.0, 0.5, 0.0), round(10.0, 0.5, 30.0)];
+
+        let summary = summarize_backtest(&rounds, 4).unwrap();
+        assert_eq!(summary.rounds, 2);
+        assert_eq!(summary.total_premium, 20.0);
+        assert_eq!(summary.total_payout, 30.0);
+        assert_eq!(summary.total_pnl, -10.0);
+        assert_eq!(summary.mean_pnl, -5.0);
+        assert_eq!(summary.worst_pnl, -20.0);
+        assert_eq!(summary.hit_rate, 0.5);
+        assert_eq!(summary.mean_payout_probability, 0.5);
+        assert_eq!(summary.brier_score, 0.25);
+        assert_eq!(summary.payout_ratio, 1.5);
+        assert_eq!(summary.forecast.mean_crps, 1.0);
+        assert_eq!(summary.forecast.mean_pinball_losses, vec![15.0]);
+        assert_eq!(summary.forecast.pit_histogram, vec![0, 0, 2, 0]);
+
+        assert!(summarize_backtest(&[], 4).is_err());
+    }
+}
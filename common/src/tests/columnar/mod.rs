@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use nalgebra::DMatrix;
+    use rand::thread_rng;
+
+    use crate::{
+        common::columnar::{
+            read_mrj_simulation, read_paths, write_mrj_simulation, write_paths, ColumnarFormat,
+            MrjSimulationWriter, PathWriter, PATHS_PER_BATCH,
+        },
+        floating_point::{MrjModel, MrjSimulation},
+    };
+
+    const FORMATS: [ColumnarFormat; 3] = [
+        ColumnarFormat::Parquet,
+        ColumnarFormat::ArrowIpc,
+        ColumnarFormat::Npy,
+    ];
+
+    #[test]
+    fn test_paths_round_trip() {
+        let dir = env::temp_dir().join("columnar_paths_round_trip");
+        fs::create_dir_all(&dir).unwrap();
+
+        // more paths than fit in one batch, and a last batch that is not full
+        let (n_periods, num_paths) = (30, 2 * PATHS_PER_BATCH + 7);
+        let paths = DMatrix::from_fn(n_periods, num_paths, |i, j| {
+            (i as f64 + 1.0) * 0.5 - j as f64 / 3.0
+        });
+
+        for format in FORMATS {
+            let file_name = dir
+                .join(format!("paths.{}", format.extension()))
+                .to_string_lossy()
+                .into_owned();
+            write_paths(&file_name, &paths, format).unwrap();
+            assert_eq!(read_paths(&file_name, format).unwrap(), paths);
+        }
+
+        // the NPY array is (num_paths, n_periods), path by path
+        let npy = fs::read(dir.join("paths.npy")).unwrap();
+        let header_length = u16::from_le_bytes([npy[8], npy[9]]) as usize;
+        assert_eq!((10 + header_length) % 64, 0);
+        let header = String::from_utf8(npy[10..10 + header_length].to_vec()).unwrap();
+        assert!(header.contains(&format!("'shape': ({}, {})", num_paths, n_periods)));
+        let second_value =
+            f64::from_le_bytes(npy[10 + header_length + 8..][..8].try_into().unwrap());
+        assert_eq!(second_value, paths[(1, 0)]);
+
+        assert!(read_paths(
+            &dir.join("missing.npy").to_string_lossy(),
+            ColumnarFormat::Npy
+        )
+        .is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_path_writer_batches() {
+        let dir = env::temp_dir().join("columnar_path_writer_batches");
+        fs::create_dir_all(&dir).unwrap();
+
+        let (n_periods, num_paths) = (24, PATHS_PER_BATCH + 300);
+        let paths = DMatrix::from_fn(n_periods, num_paths, |i, j| i as f64 * 0.25 + j as f64);
+
+        for format in FORMATS {
+            let file_name = dir
+                .join(format!("paths.{}", format.extension()))
+                .to_string_lossy()
+                .into_owned();
+
+            // batches as a simulation would hand them over, the first over PATHS_PER_BATCH
+            let mut writer = PathWriter::open(&file_name, n_periods, format).unwrap();
+            for batch in [0..PATHS_PER_BATCH + 100, PATHS_PER_BATCH + 100..num_paths] {
+                writer
+                    .write_batch(&paths.columns(batch.start, batch.len()).into_owned())
+                    .unwrap();
+            }
+            assert!(writer
+                .write_batch(&DMatrix::zeros(n_periods + 1, 2))
+                .is_err());
+            writer.finish().unwrap();
+
+            assert_eq!(read_paths(&file_name, format).unwrap(), paths);
+        }
+
+        // the NPY header, written before the number of paths was known, holds the final shape
+        let npy = fs::read(dir.join("paths.npy")).unwrap();
+        let header_length = u16::from_le_bytes([npy[8], npy[9]]) as usize;
+        assert_eq!((10 + header_length) % 64, 0);
+        let header = String::from_utf8(npy[10..10 + header_length].to_vec()).unwrap();
+        assert!(header.contains(&format!("'shape': ({}, {})", num_paths, n_periods)));
+        assert_eq!(npy.len(), 10 + header_length + 8 * n_periods * num_paths);
+
+        // a file with no batch is an empty array
+        let empty = dir.join("empty.npy").to_string_lossy().into_owned();
+        PathWriter::open(&empty, n_periods, ColumnarFormat::Npy)
+            .unwrap()
+            .finish()
+            .unwrap();
+        assert_eq!(
+            read_paths(&empty, ColumnarFormat::Npy).unwrap(),
+            DMatrix::<f64>::zeros(n_periods, 0)
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_mrj_simulation_round_trip() {
+        let dir = env::temp_dir().join("columnar_mrj_simulation_round_trip");
+        let positions = [0.001, 0.95, 0.1, 0.01, 0.05, 0.02];
+        let simulation = MrjModel
+            .simulate_with_draws(&positions, 0.5, 48, 20, &mut thread_rng())
+            .unwrap();
+
+        assert!(simulation
+            .simulated_prices
+            .row(0)
+            .iter()
+            .all(|price| *price == 0.5));
+        assert!(simulation
+            .jumps
+            .iter()
+            .all(|jump| *jump == 0.0 || *jump == 1.0));
+
+        for format in FORMATS {
+            let format_dir = dir.join(format.extension()).to_string_lossy().into_owned();
+            write_mrj_simulation(&format_dir, &simulation, format).unwrap();
+            assert_eq!(
+                read_mrj_simulation(&format_dir, format).unwrap(),
+                simulation
+            );
+
+            // the same simulation streamed in two batches of paths
+            let streamed_dir = dir
+                .join(format!("streamed_{}", format.extension()))
+                .to_string_lossy()
+                .into_owned();
+            let mut writer = MrjSimulationWriter::open(&streamed_dir, 48, format).unwrap();
+            for (first_path, batch_paths) in [(0, 15), (15, 5)] {
+                let batch =
+                    |paths: &DMatrix<f64>| paths.columns(first_path, batch_paths).into_owned();
+                writer
+                    .write_batch(&MrjSimulation {
+                        simulated_prices: batch(&simulation.simulated_prices),
+                        jumps: batch(&simulation.jumps),
+                        n1: batch(&simulation.n1),
+                        n2: batch(&simulation.n2),
+                    })
+                    .unwrap();
+            }
+            writer.finish().unwrap();
+            assert_eq!(
+                read_mrj_simulation(&streamed_dir, format).unwrap(),
+                simulation
+            );
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use starknet_core::types::Felt;
+
+    use crate::{
+        convert_f64_to_felt, convert_felt_to_f64, convert_felt_to_f64_checked,
+        tests::mock::get_5760_avg_base_fees_felt,
+    };
+
+    #[test]
+    fn test_convert_felt_to_f64_checked_exact() {
+        let input = Felt::from_hex_unchecked("0x6322CF2B00000000000000000000000000000000");
+        let result = convert_felt_to_f64_checked(input).unwrap();
+
+        assert_eq!(result.value, 1663225643.0);
+        assert_eq!(result.error_bound, 0.0);
+        assert_eq!(convert_f64_to_felt(result.value).unwrap(), input);
+    }
+
+    #[test]
+    fn test_convert_felt_to_f64_checked_rounds_to_nearest() {
+        // 96 fractional bits more than an f64 can hold, result gotten from python's
+        // correctly rounded float(Fraction(felt, 2**128))
+        let input = Felt::from_hex_unchecked("0xbc54d6cbfb7a1ec19542090bc27cd57bede87b06");
+        let result = convert_felt_to_f64_checked(input).unwrap();
+
+        assert_eq!(result.value, 3159676619.982332);
+        assert!(result.error_bound > 0.0);
+        assert!(result.error_bound <= result.value * f64::EPSILON);
+        assert_ne!(convert_f64_to_felt(result.value).unwrap(), input);
+
+        let input = Felt::from_hex_unchecked("0x1AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA");
+        assert_eq!(convert_felt_to_f64(input), 1.6666666666666667);
+    }
+
+    #[test]
+    fn test_convert_felt_to_f64_ties_and_sticky_bits() {
+        // 2^53 + 1 is halfway between two f64s and rounds to even
+        let halfway = Felt::from(2u128.pow(53) + 1) * Felt::TWO.pow(128u32);
+        assert_eq!(convert_felt_to_f64(halfway), 9007199254740992.0);
+
+        // one unit of 2^-128 above halfway rounds up
+        let above_halfway = halfway + Felt::ONE;
+        assert_eq!(convert_felt_to_f64(above_halfway), 9007199254740994.0);
+    }
+
+    #[test]
+    fn test_convert_felt_to_f64_checked_rejects_large_integer_part() {
+        // integer part of 2^123
+        let input = Felt::TWO.pow(251u32);
+        assert!(convert_felt_to_f64_checked(input).is_err());
+
+        let largest = Felt::TWO.pow(251u32) - Felt::ONE;
+        assert!(convert_felt_to_f64_checked(largest).is_ok());
+    }
+
+    #[test]
+    fn test_convert_f64_to_felt_round_trips() {
+        for value in [
+            0.0,
+            0.1,
+            1.5,
+            1e-20,
+            12345.6789,
+            1663225643.0,
+            2f64.powi(-128),
+            2f64.powi(122),
+            f64::MAX / 2f64.powi(901),
+        ] {
+            let felt = convert_f64_to_felt(value).unwrap();
+            let result = convert_felt_to_f64_checked(felt).unwrap();
+            assert_eq!(result.value, value);
+            assert_eq!(result.error_bound, 0.0);
+        }
+
+        assert_eq!(
+            convert_f64_to_felt(1663225643.0).unwrap(),
+            Felt::from_hex_unchecked("0x6322CF2B00000000000000000000000000000000")
+        );
+        assert_eq!(convert_f64_to_felt(-0.0).unwrap(), Felt::ZERO);
+    }
+
+    #[test]
+    fn test_convert_f64_to_felt_rejects_invalid_values() {
+        assert!(convert_f64_to_felt(-1.0).is_err());
+        assert!(convert_f64_to_felt(f64::NAN).is_err());
+        assert!(convert_f64_to_felt(f64::INFINITY).is_err());
+        assert!(convert_f64_to_felt(2f64.powi(123)).is_err());
+        // bits below 2^-128
+        assert!(convert_f64_to_felt(2f64.powi(-129)).is_err());
+        assert!(convert_f64_to_felt(1e-40).is_err());
+    }
+
+    #[test]
+    fn test_mock_fees_round_trip() {
+        for felt in get_5760_avg_base_fees_felt() {
+            let result = convert_felt_to_f64_checked(felt).unwrap();
+            assert_eq!(result.error_bound, 0.0);
+            assert_eq!(convert_f64_to_felt(result.value).unwrap(), felt);
+        }
+    }
+}
@@ -1,13 +1,28 @@
 #[cfg(test)]
 mod tests {
     use nalgebra::{DMatrix, DVector};
+    use rand::{rngs::StdRng, SeedableRng};
 
     use crate::{
         floating_point::{
-            error_bound_dvec, error_bound_f64, error_bound_matrix,
-            error_bound_simulated_log_prices, error_bound_vec, mrjpdf, neg_log_likelihood,
+            block_base_fee_samples, calculate_crps, calculate_ema, calculate_expected_payoff,
+            calculate_geometric_twap, calculate_greeks, calculate_historical_cvar,
+            calculate_historical_var, calculate_max_drawdown, calculate_max_returns,
+            calculate_median, calculate_pinball_losses, calculate_pit, calculate_pit_histogram,
+            calculate_realized_volatility, calculate_rolling_median,
+            calculate_simulated_log_prices_from_shocks, calculate_tail_risk,
+            calculate_time_weighted_twap, calculate_trend_volatility, calculate_twap,
+            calculated_fan_chart_from_simulated_log_prices, calculated_reserve_price_for_round,
+            calculated_reserve_price_grid_from_simulated_log_prices, error_bound_dvec,
+            error_bound_f64, error_bound_matrix, error_bound_reserve_price_grid,
+            error_bound_simulated_log_prices, error_bound_vec, fee_model_gradient,
+            generate_trend_shocks, gradient, minimize, mrjpdf, neg_log_likelihood, pre_minimize,
+            projected_fee_model_gradient, score_settlement_twap, CappedCall, CappedPut, Digital,
+            FanChart, FeeModel, FeeModelKind, MrjModel, MrjParams, Payoff, PayoffKind,
+            VarianceSwap, FAN_CHART_QUANTILES,
         },
-        tests::mock::generate_inputs,
+        tests::mock::{generate_inputs, get_max_return_input_data},
+        window::{round_horizon, round_horizon_from_hours, SettlementSpec},
     };
 
     #[test]
@@ -234,4 +249,770 @@ mod tests {
         let result = error_bound_f64(target, calculated, tolerance);
         assert!(!result);
     }
+
+    #[test]
+    fn test_calculate_time_weighted_twap_matches_arithmetic_on_hourly_samples() {
+        let fees = vec![10.0, 20.0, 15.0, 35.0];
+        let samples = fees
+            .iter()
+            .enumerate()
+            .map(|(i, fee)| (1708833600 + i as i64 * 3600, *fee))
+            .collect::<Vec<_>>();
+
+        let twap = calculate_time_weighted_twap(&samples, 1708833600 + 4 * 3600).unwrap();
+        assert_eq!(twap, calculate_twap(&fees));
+    }
+
+    #[test]
+    fn test_calculate_time_weighted_twap_weights_by_duration() {
+        // 10 for 12s, 40 for 36s and 20 for 12s
+        let samples = vec![(0, 10.0), (12, 40.0), (48, 20.0)];
+        let twap = calculate_time_weighted_twap(&samples, 60).unwrap();
+        assert_eq!(twap, (10.0 * 12.0 + 40.0 * 36.0 + 20.0 * 12.0) / 60.0);
+        assert_ne!(twap, calculate_twap(&vec![10.0, 40.0, 20.0]));
+    }
+
+    #[test]
+    fn test_calculate_time_weighted_twap_rejects_unordered_samples() {
+        assert!(calculate_time_weighted_twap(&[], 60).is_err());
+        assert!(calculate_time_weighted_twap(&[(0, 10.0), (0, 20.0)], 60).is_err());
+        assert!(calculate_time_weighted_twap(&[(12, 10.0), (0, 20.0)], 60).is_err());
+        // the last sample must be in effect for some time before the end
+        assert!(calculate_time_weighted_twap(&[(0, 10.0), (60, 20.0)], 60).is_err());
+    }
+
+    #[test]
+    fn test_block_base_fee_samples() {
+        let blocks = vec![(100, 0, 10), (101, 12, 40), (102, 48, 20), (103, 60, 30)];
+
+        // the fee of block 101 is in effect at the start, and block 103 ends the window
+        let samples = block_base_fee_samples(&blocks, 24, 60).unwrap();
+        assert_eq!(samples, vec![(24, 40.0), (48, 20.0)]);
+        assert_eq!(
+            calculate_time_weighted_twap(&samples, 60).unwrap(),
+            (40.0 * 24.0 + 20.0 * 12.0) / 36.0
+        );
+
+        // a block at the start is in effect from then on
+        let samples = block_base_fee_samples(&blocks, 12, 48).unwrap();
+        assert_eq!(samples, vec![(12, 40.0)]);
+    }
+
+    #[test]
+    fn test_block_base_fee_samples_rejects_incomplete_blocks() {
+        let blocks = vec![(100, 0, 10), (101, 12, 40), (102, 48, 20)];
+        assert!(block_base_fee_samples(&blocks, 12, 12).is_err());
+        // the blocks must cover the window
+        assert!(block_base_fee_samples(&blocks[1..], 0, 48).is_err());
+        assert!(block_base_fee_samples(&blocks, 0, 60).is_err());
+        assert!(block_base_fee_samples(&[], 0, 60).is_err());
+        // and be consecutive and in order
+        assert!(block_base_fee_samples(&[(100, 0, 10), (102, 48, 20)], 0, 48).is_err());
+        assert!(block_base_fee_samples(&[(100, 12, 10), (101, 0, 20)], 0, 12).is_err());
+    }
+
+    #[test]
+    fn test_calculate_geometric_twap() {
+        let twap = calculate_geometric_twap(&[1.0, 4.0, 16.0]).unwrap();
+        assert!((twap - 4.0).abs() < 1e-12);
+
+        assert!(calculate_geometric_twap(&[]).is_err());
+        assert!(calculate_geometric_twap(&[1.0, 0.0]).is_err());
+        assert!(calculate_geometric_twap(&[1.0, f64::NAN]).is_err());
+    }
+
+    #[test]
+    fn test_calculate_median() {
+        assert_eq!(calculate_median(&[3.0, 1.0, 2.0]).unwrap(), 2.0);
+        assert_eq!(calculate_median(&[4.0, 1.0, 3.0, 2.0]).unwrap(), 2.5);
+        assert!(calculate_median(&[]).is_err());
+    }
+
+    #[test]
+    fn test_calculate_rolling_median() {
+        let medians = calculate_rolling_median(&[1.0, 5.0, 3.0, 100.0, 2.0], 3).unwrap();
+        assert_eq!(medians, vec![1.0, 3.0, 3.0, 5.0, 3.0]);
+
+        assert!(calculate_rolling_median(&[1.0, 2.0], 0).is_err());
+        assert!(calculate_rolling_median(&[1.0, 2.0], 3).is_err());
+    }
+
+    #[test]
+    fn test_calculate_ema() {
+        let ema = calculate_ema(&[10.0, 20.0, 20.0], 0.5).unwrap();
+        assert_eq!(ema, vec![10.0, 15.0, 17.5]);
+
+        // alpha of 1 follows the fees
+        assert_eq!(calculate_ema(&[1.0, 2.0], 1.0).unwrap(), vec![1.0, 2.0]);
+
+        assert!(calculate_ema(&[], 0.5).is_err());
+        assert!(calculate_ema(&[1.0], 0.0).is_err());
+        assert!(calculate_ema(&[1.0], 1.5).is_err());
+    }
+
+    #[test]
+    fn test_reference_prices_resist_a_spike_hour() {
+        let mut fees = vec![20.0; 168];
+        fees[100] = 20.0 * 500.0;
+
+        // the spike moves the arithmetic mean by ~300%, the geometric mean by ~4%
+        // and the median not at all
+        assert!(calculate_twap(&fees) > 3.9 * 20.0);
+        assert!(calculate_geometric_twap(&fees).unwrap() < 1.05 * 20.0);
+        assert_eq!(calculate_rolling_median(&fees, 168).unwrap()[167], 20.0);
+    }
+
+    #[test]
+    fn test_calculate_historical_var_and_cvar() {
+        let returns = (1..=11).map(|i| i as f64 / 100.0).collect::<Vec<_>>();
+
+        // the 0.95 quantile of 11 returns sits halfway between the two largest
+        assert!((calculate_historical_var(&returns, 0.95).unwrap() - 0.105).abs() < 1e-12);
+        assert!((calculate_historical_cvar(&returns, 0.95).unwrap() - 0.11).abs() < 1e-12);
+        assert!((calculate_historical_cvar(&returns, 0.8).unwrap() - 0.1).abs() < 1e-12);
+
+        assert!(calculate_historical_var(&[], 0.95).is_err());
+        assert!(calculate_historical_var(&returns, 1.5).is_err());
+        assert!(calculate_historical_cvar(&returns, -0.1).is_err());
+    }
+
+    #[test]
+    fn test_calculate_max_drawdown() {
+        let drawdown = calculate_max_drawdown(&[10.0, 20.0, 15.0, 25.0, 5.0, 30.0]).unwrap();
+        assert!((drawdown - 0.8).abs() < 1e-12);
+
+        // a rising series has no drawdown
+        assert_eq!(calculate_max_drawdown(&[1.0, 2.0, 3.0]).unwrap(), 0.0);
+        assert!(calculate_max_drawdown(&[]).is_err());
+    }
+
+    #[test]
+    fn test_calculate_tail_risk_matches_max_return() {
+        // the 2 months of POC data
+        let data = get_max_return_input_data()
+            .into_iter()
+            .take(24 * 30 * 2)
+            .map(|(_, fee)| fee)
+            .collect::<Vec<_>>();
+        let tail_risk = calculate_tail_risk(&data, &[0.95, 0.99]).unwrap();
+
+        assert_eq!(tail_risk.max_return, calculate_max_returns(&data));
+        assert!(tail_risk.min_return <= tail_risk.quantiles[0].value_at_risk);
+        for quantile in &tail_risk.quantiles {
+            assert!(quantile.value_at_risk <= quantile.conditional_value_at_risk);
+            assert!(quantile.conditional_value_at_risk <= tail_risk.max_return);
+        }
+        assert!(tail_risk.quantiles[0].value_at_risk <= tail_risk.quantiles[1].value_at_risk);
+        assert!((0.0..1.0).contains(&tail_risk.max_drawdown));
+    }
+
+    #[test]
+    fn test_mrj_params_from_positions() {
+        let dt = 1.0 / (365.0 * 24.0);
+        let params = MrjParams::from_positions(&[0.01, 0.99, -0.2, 0.04, 0.09, 0.005]).unwrap();
+
+        assert!((params.alpha - 0.01 / dt).abs() < 1e-9);
+        assert!((params.kappa - 0.01 / dt).abs() < 1e-9);
+        assert_eq!(params.mu_j, -0.2);
+        assert!((params.sigma - (0.04 / dt).sqrt()).abs() < 1e-9);
+        assert!((params.sigma_j - 0.3).abs() < 1e-12);
+        assert!((params.lambda - 0.005 / dt).abs() < 1e-9);
+
+        assert!(MrjParams::from_positions(&[0.01, 0.99, -0.2, 0.04, 0.09]).is_err());
+    }
+
+    #[test]
+    fn test_calculate_realized_volatility() {
+        // log fees alternating by +-0.1 every hour
+        let fees = (0..101)
+            .map(|i| if i % 2 == 0 { 1.0 } else { 0.1f64.exp() })
+            .collect::<Vec<_>>();
+        let volatility = calculate_realized_volatility(&fees).unwrap();
+
+        // 100 changes of +-0.1 with a mean of 0, so a sample variance of 0.01 * 100 / 99
+        let expected = (0.01 * 100.0 / 99.0 * 365.0 * 24.0f64).sqrt();
+        assert!((volatility - expected).abs() < 1e-9);
+
+        // constant fees have no volatility
+        assert_eq!(calculate_realized_volatility(&[5.0; 10]).unwrap(), 0.0);
+
+        assert!(calculate_realized_volatility(&[1.0, 2.0]).is_err());
+        assert!(calculate_realized_volatility(&[1.0, 0.0, 2.0]).is_err());
+    }
+
+    #[test]
+    fn test_fee_models_simulate_from_initial_value() {
+        for fee_model in [
+            FeeModelKind::MeanRevertingJump,
+            FeeModelKind::RegimeSwitching,
+            FeeModelKind::Garch,
+        ] {
+            let model = fee_model.model();
+            let params = model.initial_params(0.01);
+            assert_eq!(params.len(), model.param_bounds().len());
+
+            let simulated = model.simulate(&params, 0.3, 48, 10).unwrap();
+            assert_eq!((simulated.nrows(), simulated.ncols()), (48, 10));
+            assert!(simulated.row(0).iter().all(|value| *value == 0.3));
+            assert!(simulated.iter().all(|value| value.is_finite()));
+
+            assert!(model.simulate(&params[1..], 0.3, 48, 10).is_err());
+        }
+    }
+
+    #[test]
+    fn test_mrj_model_matches_neg_log_likelihood() {
+        let (params, pt_data, pt1_data) = generate_inputs();
+        let pt = DVector::from_vec(pt_data);
+        let pt1 = DVector::from_vec(pt1_data);
+
+        assert_eq!(
+            MrjModel.neg_log_likelihood(&params, &pt, &pt1),
+            neg_log_likelihood(&params, &pt, &pt1)
+        );
+    }
+
+    #[test]
+    fn test_fit_fee_model_and_verify_params() {
+        // as in the proof composition
+        let gradient_tolerance = 5e-2;
+        for (fee_model, true_params) in [
+            (
+                FeeModelKind::RegimeSwitching,
+                vec![0.0, 0.05, 0.6, 0.001, 0.02, 0.95, 0.9],
+            ),
+            (FeeModelKind::Garch, vec![0.01, 0.6, 0.001, 0.1, 0.8]),
+        ] {
+            let model = fee_model.model();
+            let path = model.simulate(&true_params, 0.0, 2000, 1).unwrap();
+            let (pt, pt_1, var_pt) = pre_minimize(&DVector::from_column_slice(path.as_slice()));
+
+            let initial_params = model.initial_params(var_pt);
+            let initial_value = model.neg_log_likelihood(&initial_params, &pt, &pt_1);
+            let solution = model.fit(initial_params, &pt, &pt_1, 100);
+            assert!(solution.value < initial_value);
+
+            // a fit stays within bounds, which verification checks along with the gradient
+            let bounds = model.param_bounds();
+            assert!(bounds
+                .iter()
+                .zip(&solution.position)
+                .all(|((lower, upper), param)| lower <= param && param <= upper));
+            assert!(model.verify_params(&solution.position, &pt, &pt_1, gradient_tolerance));
+            assert!(!model.verify_params(&solution.position[1..], &pt, &pt_1, gradient_tolerance));
+            let mut out_of_bounds = solution.position.clone();
+            out_of_bounds[3] = -1.0;
+            assert!(!model.verify_params(&out_of_bounds, &pt, &pt_1, gradient_tolerance));
+
+            // only components pointing past an active bound are projected out
+            let gradient = fee_model_gradient(model, &solution.position, &pt, &pt_1);
+            let projected = projected_fee_model_gradient(model, &solution.position, &pt, &pt_1);
+            for (i, (g, projected)) in gradient.iter().zip(&projected).enumerate() {
+                let (lower, upper) = bounds[i];
+                let x = solution.position[i];
+                assert!(
+                    g == projected
+                        || (*projected == 0.0
+                            && ((x <= lower && *g > 0.0) || (x >= upper && *g < 0.0)))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_projected_fee_model_gradient_at_bounds() {
+        // a GARCH fit with no ARCH effect: alpha held at its lower bound of 0, where the
+        // likelihood would only improve with a negative alpha
+        let model = FeeModelKind::Garch.model();
+        let path = model
+            .simulate(&[0.01, 0.6, 0.001, 0.0, 0.0], 0.0, 2000, 1)
+            .unwrap();
+        let (pt, pt_1, _var_pt) = pre_minimize(&DVector::from_column_slice(path.as_slice()));
+
+        let mut params = vec![0.01, 0.6, 0.001, 0.0, 0.0];
+        let gradient = fee_model_gradient(model, &params, &pt, &pt_1);
+        let projected = projected_fee_model_gradient(model, &params, &pt, &pt_1);
+        for i in [3, 4] {
+            if gradient[i] > 0.0 {
+                assert_eq!(projected[i], 0.0);
+            } else {
+                assert_eq!(projected[i], gradient[i]);
+            }
+        }
+        // away from the bounds nothing is projected out
+        params[3] = 0.1;
+        params[4] = 0.5;
+        assert_eq!(
+            projected_fee_model_gradient(model, &params, &pt, &pt_1),
+            fee_model_gradient(model, &params, &pt, &pt_1)
+        );
+    }
+
+    #[test]
+    fn test_mrj_fit_and_verification_match_original() {
+        let (_, pt_data, pt1_data) = generate_inputs();
+        let pt = DVector::from_vec(pt_data);
+        let pt_1 = DVector::from_vec(pt1_data);
+        let initial_params = MrjModel.initial_params(pt.map(|x| x * x).mean());
+
+        // the MRJ fit is the original unbounded gradient descent
+        let solution = MrjModel.fit(initial_params.clone(), &pt, &pt_1, 50);
+        let original = minimize(initial_params, &pt, &pt_1, 50);
+        assert_eq!(solution.position, original.position);
+        assert_eq!(solution.value, original.value);
+
+        // and verification only checks its unbounded gradient, as the original did
+        let gradient = gradient(&solution.position, &pt, &pt_1);
+        let max_gradient = gradient.iter().fold(0.0f64, |max, g| max.max(g.abs()));
+        assert!(MrjModel.verify_params(&solution.position, &pt, &pt_1, max_gradient));
+        assert!(!MrjModel.verify_params(&solution.position, &pt, &pt_1, max_gradient / 2.0));
+    }
+
+    #[test]
+    fn test_payoffs() {
+        let strike = 10.0;
+        let call = CappedCall { cap_level: 0.3 };
+        let put = CappedPut { cap_level: 0.3 };
+        let digital = Digital { payout: 2.0 };
+
+        // settlement TWAPs of 11, 20 and 5
+        assert!((call.payoff(&[10.0, 12.0], strike) - 1.0).abs() < 1e-12);
+        assert!((call.payoff(&[20.0, 20.0], strike) - 3.0).abs() < 1e-12);
+        assert_eq!(call.payoff(&[4.0, 6.0], strike), 0.0);
+        assert_eq!(put.payoff(&[10.0, 12.0], strike), 0.0);
+        assert!((put.payoff(&[4.0, 6.0], strike) - 3.0).abs() < 1e-12);
+        assert!((put.payoff(&[9.0, 9.0], strike) - 1.0).abs() < 1e-12);
+        assert_eq!(digital.payoff(&[10.0, 12.0], strike), 2.0);
+        assert_eq!(digital.payoff(&[10.0, 10.0], strike), 0.0);
+
+        // hourly log changes of +-0.1
+        let variance = VarianceSwap.payoff(&[1.0, 0.1f64.exp(), 1.0], strike);
+        assert!((variance - 0.01 * 365.0 * 24.0).abs() < 1e-9);
+        assert_eq!(VarianceSwap.payoff(&[5.0; 10], strike), 0.0);
+    }
+
+    #[test]
+    fn test_calculate_expected_payoff() {
+        // two paths, settled on the last two hours
+        let simulated_prices =
+            DMatrix::from_column_slice(3, 2, &[100.0, 10.0, 12.0, 1.0, 4.0, 6.0]);
+
+        let call = PayoffKind::default();
+        assert_eq!(call, PayoffKind::CappedCall(CappedCall { cap_level: 0.3 }));
+        let expected = calculate_expected_payoff(call.payoff(), &simulated_prices, 1, 10.0);
+        assert!((expected - 0.5).abs() < 1e-12);
+
+        let put = PayoffKind::CappedPut(CappedPut { cap_level: 0.3 });
+        let expected = calculate_expected_payoff(put.payoff(), &simulated_prices, 1, 10.0);
+        assert!((expected - 1.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_calculate_greeks() {
+        let (n_periods, num_paths) = (240, 500);
+        let horizon =
+            round_horizon(0, 3600 * n_periods as i64, &SettlementSpec::default()).unwrap();
+        let season_param = DVector::zeros(12);
+        // hourly intercept, AR(1) coefficient, jump mean and variances, jump probability
+        let positions = [0.0, 0.95, 0.1, 1e-4, 0.01, 0.02];
+        let de_seasonalised_detrended_log_base_fee = DVector::from_vec(vec![0.1, -0.05, 0.0]);
+        let twap_7d = (0..200)
+            .map(|i| 10.0 + (i as f64 / 10.0).sin())
+            .collect::<Vec<_>>();
+        let (slope, intercept) = (0.0, 10.0f64.ln());
+        let seed = 7;
+
+        let greeks = |fee_model: FeeModelKind, positions: &[f64], payoff: &PayoffKind| {
+            calculate_greeks(
+                0,
+                3600 * 200,
+                &season_param,
+                fee_model,
+                positions,
+                &de_seasonalised_detrended_log_base_fee,
+                &horizon,
+                num_paths,
+                &twap_7d,
+                slope,
+                intercept,
+                1,
+                payoff,
+                seed,
+            )
+        };
+
+        let call = PayoffKind::default();
+        let call_greeks = greeks(FeeModelKind::MeanRevertingJump, &positions, &call).unwrap();
+        assert_eq!(
+            call_greeks,
+            greeks(FeeModelKind::MeanRevertingJump, &positions, &call).unwrap()
+        );
+
+        // the same draws give the same reserve price as the repo's pricing
+        let simulated_prices = MrjModel
+            .simulate_with_rng(
+                &positions,
+                0.0,
+                n_periods,
+                num_paths,
+                &mut StdRng::seed_from_u64(seed + 1),
+            )
+            .unwrap();
+        let shocks = generate_trend_shocks(n_periods, num_paths, seed).unwrap();
+        assert_eq!(
+            shocks,
+            generate_trend_shocks(n_periods, num_paths, seed).unwrap()
+        );
+        let simulated_log_prices = calculate_simulated_log_prices_from_shocks(
+            0,
+            3600 * 200,
+            &season_param,
+            &simulated_prices,
+            slope,
+            intercept,
+            1,
+            calculate_trend_volatility(&twap_7d),
+            &shocks,
+        )
+        .unwrap();
+        let reserve_price =
+            calculated_reserve_price_for_round(&simulated_log_prices, &twap_7d, &horizon, &call)
+                .unwrap();
+        assert!((call_greeks.reserve_price - reserve_price).abs() < 1e-9);
+
+        // a higher strike makes a call cheaper and a put dearer
+        assert!(call_greeks.dual_delta < 0.0);
+        let put_greeks = greeks(
+            FeeModelKind::MeanRevertingJump,
+            &positions,
+            &PayoffKind::CappedPut(CappedPut { cap_level: 0.3 }),
+        )
+        .unwrap();
+        assert!(put_greeks.dual_delta > 0.0);
+        assert!(call_greeks.vega.is_finite() && call_greeks.theta.is_finite());
+        assert_eq!(
+            greeks(
+                FeeModelKind::MeanRevertingJump,
+                &positions,
+                &PayoffKind::VarianceSwap(VarianceSwap)
+            )
+            .unwrap()
+            .dual_delta,
+            0.0
+        );
+
+        // the MRJ volatilities are bumped on their annualized scale, and a more volatile
+        // fee is worth more to a variance swap
+        let params = MrjParams::from_positions(&positions).unwrap();
+        assert!(params
+            .to_positions()
+            .iter()
+            .zip(positions)
+            .all(|(a, b)| (a - b).abs() < 1e-12));
+        let variance_swap_greeks = greeks(
+            FeeModelKind::MeanRevertingJump,
+            &positions,
+            &PayoffKind::VarianceSwap(VarianceSwap),
+        )
+        .unwrap();
+        assert!(variance_swap_greeks.sigma_vega.unwrap() > 0.0);
+        assert!(variance_swap_greeks.sigma_j_vega.unwrap() > 0.0);
+        assert!(call_greeks.sigma_vega.unwrap().is_finite());
+        assert!(call_greeks.sigma_j_vega.unwrap().is_finite());
+
+        // the other fee models have no MRJ volatilities
+        let garch_greeks = greeks(FeeModelKind::Garch, &[0.0, 0.9, 1e-4, 0.1, 0.8], &call).unwrap();
+        assert_eq!(garch_greeks.sigma_vega, None);
+        assert_eq!(garch_greeks.sigma_j_vega, None);
+
+        // theta settles the round a day earlier, on a window of the same length
+        let one_day_closer_horizon = round_horizon(
+            0,
+            3600 * (n_periods as i64 - 24),
+            &SettlementSpec::default(),
+        )
+        .unwrap();
+        let one_day_closer_reserve_price = calculated_reserve_price_for_round(
+            &simulated_log_prices.rows(0, n_periods - 24).into_owned(),
+            &twap_7d,
+            &one_day_closer_horizon,
+            &call,
+        )
+        .unwrap();
+        assert!((call_greeks.theta - (one_day_closer_reserve_price - reserve_price)).abs() < 1e-9);
+
+        // which needs a day of the round before its settlement window
+        assert!(calculate_greeks(
+            0,
+            3600 * 200,
+            &season_param,
+            FeeModelKind::MeanRevertingJump,
+            &positions,
+            &de_seasonalised_detrended_log_base_fee,
+            &round_horizon(0, 3600 * 180, &SettlementSpec::default()).unwrap(),
+            num_paths,
+            &twap_7d,
+            slope,
+            intercept,
+            1,
+            &call,
+            seed,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_reserve_price_grid() {
+        let (n_periods, num_paths) = (400, 300);
+        let simulated_log_prices = DMatrix::from_fn(n_periods, num_paths, |i, j| {
+            10.0f64.ln() + (j as f64 / num_paths as f64 - 0.5) * 0.001 * i as f64
+        });
+        let twap_7d = vec![9.0, 10.0];
+        let call = PayoffKind::default();
+
+        let grid = calculated_reserve_price_grid_from_simulated_log_prices(
+            &simulated_log_prices,
+            &twap_7d,
+            &[0.9, 1.0, 1.1],
+            &[200, n_periods],
+            &call,
+            &SettlementSpec::default(),
+        )
+        .unwrap();
+        assert_eq!(grid.reserve_prices.len(), 2);
+        assert!(grid.reserve_prices.iter().all(|row| row.len() == 3));
+
+        // the full horizon at 100% is the reserve price
+        let horizon = round_horizon_from_hours(n_periods, &SettlementSpec::default()).unwrap();
+        let reserve_price =
+            calculated_reserve_price_for_round(&simulated_log_prices, &twap_7d, &horizon, &call)
+                .unwrap();
+        assert!((grid.reserve_prices[1][1] - reserve_price).abs() < 1e-9);
+
+        // calls get cheaper with the strike, and dearer with the fan of a longer horizon
+        for row in &grid.reserve_prices {
+            assert!(row[0] > row[1] && row[1] > row[2]);
+        }
+        assert!(grid.reserve_prices[1][2] > grid.reserve_prices[0][2]);
+
+        assert!(error_bound_reserve_price_grid(&grid, &grid, 5.0));
+        let mut off = grid.clone();
+        off.reserve_prices[0][0] *= 1.1;
+        assert!(!error_bound_reserve_price_grid(&grid, &off, 5.0));
+        off.reserve_prices = grid.reserve_prices[..1].to_vec();
+        assert!(!error_bound_reserve_price_grid(&grid, &off, 5.0));
+
+        for (strike_levels, horizons) in [
+            (vec![0.0], vec![n_periods]),
+            (vec![1.0], vec![0]),
+            (vec![1.0], vec![n_periods + 1]),
+        ] {
+            assert!(calculated_reserve_price_grid_from_simulated_log_prices(
+                &simulated_log_prices,
+                &twap_7d,
+                &strike_levels,
+                &horizons,
+                &call,
+                &SettlementSpec::default(),
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn test_reserve_price_for_round() {
+        let day = 3600 * 24;
+        let (n_periods, num_paths) = (14 * 24, 200);
+        let simulated_log_prices = DMatrix::from_fn(n_periods, num_paths, |i, j| {
+            10.0f64.ln() + (j as f64 / num_paths as f64 - 0.5) * 0.001 * i as f64
+        });
+        let twap_7d = vec![9.0, 10.0];
+        let call = PayoffKind::default();
+
+        // a 14-day round settled on 7 days is the full horizon of the grid at 100%
+        let weekly = round_horizon(0, 14 * day, &SettlementSpec::default()).unwrap();
+        let reserve_price =
+            calculated_reserve_price_for_round(&simulated_log_prices, &twap_7d, &weekly, &call)
+                .unwrap();
+        let expected = calculated_reserve_price_grid_from_simulated_log_prices(
+            &simulated_log_prices,
+            &twap_7d,
+            &[1.0],
+            &[n_periods],
+            &call,
+            &SettlementSpec::default(),
+        )
+        .unwrap()
+        .reserve_prices[0][0];
+        assert!((reserve_price - expected).abs() < 1e-12);
+
+        // settling on the last day only is settling on a wider fan
+        let daily = SettlementSpec {
+            twap_window_hours: 24,
+        };
+        let horizon = round_horizon(0, 14 * day, &daily).unwrap();
+        let daily_reserve_price =
+            calculated_reserve_price_for_round(&simulated_log_prices, &twap_7d, &horizon, &call)
+                .unwrap();
+        assert!(daily_reserve_price > reserve_price);
+
+        // the simulation must cover the whole round
+        let longer = round_horizon(0, 60 * day, &daily).unwrap();
+        assert!(calculated_reserve_price_for_round(
+            &simulated_log_prices,
+            &twap_7d,
+            &longer,
+            &call
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_fan_chart() {
+        let round_start = 1708833600;
+        let (n_periods, num_paths) = (72, 101);
+        // path j grows at a rate of (j - 50) / 1000 an hour from a fee of 10
+        let simulated_log_prices = DMatrix::from_fn(n_periods, num_paths, |i, j| {
+            10.0f64.ln() + (j as f64 - 50.0) / 1000.0 * i as f64
+        });
+        let daily = SettlementSpec {
+            twap_window_hours: 24,
+        };
+
+        let fan_chart = calculated_fan_chart_from_simulated_log_prices(
+            &simulated_log_prices,
+            round_start,
+            round_start + 72 * 3600,
+            &daily,
+            &FAN_CHART_QUANTILES,
+        )
+        .unwrap();
+        assert_eq!(fan_chart.timestamps.len(), n_periods);
+        assert_eq!(fan_chart.timestamps[1], round_start + 3600);
+
+        // the fan opens from the current fee around the median path
+        assert!(fan_chart.fee_quantiles[0]
+            .iter()
+            .all(|fee| (fee - 10.0).abs() < 1e-9));
+        for fees in &fan_chart.fee_quantiles {
+            assert!(fees.windows(2).all(|pair| pair[0] <= pair[1]));
+            assert!((fees[2] - 10.0).abs() < 1e-9);
+        }
+        let last = &fan_chart.fee_quantiles[n_periods - 1];
+        assert!(
+            last[4] - last[0] > fan_chart.fee_quantiles[24][4] - fan_chart.fee_quantiles[24][0]
+        );
+
+        // the median settlement TWAP is that of the median path
+        assert!((fan_chart.settlement_twap_quantiles[2] - 10.0).abs() < 1e-9);
+        assert!(fan_chart.settlement_twap_mean > 10.0);
+
+        let mut csv = vec![];
+        fan_chart.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), n_periods + 1);
+        assert_eq!(
+            csv.lines().next().unwrap(),
+            "timestamp,q0.05,q0.25,q0.5,q0.75,q0.95"
+        );
+
+        let mut settlement_csv = vec![];
+        fan_chart
+            .write_settlement_twap_csv(&mut settlement_csv)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(settlement_csv).unwrap().lines().count(),
+            7
+        );
+
+        let mut json = vec![];
+        fan_chart.write_json(&mut json).unwrap();
+        let parsed: FanChart = serde_json::from_slice(&json).unwrap();
+        assert_eq!(parsed.timestamps, fan_chart.timestamps);
+        assert!(parsed
+            .fee_quantiles
+            .iter()
+            .flatten()
+            .zip(fan_chart.fee_quantiles.iter().flatten())
+            .all(|(parsed, fee)| error_bound_f64(*parsed, *fee, 1e-10)));
+
+        // the simulation must cover the round
+        assert!(calculated_fan_chart_from_simulated_log_prices(
+            &simulated_log_prices,
+            round_start,
+            round_start + 96 * 3600,
+            &daily,
+            &FAN_CHART_QUANTILES,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_crps() {
+        // a single sample scores its absolute error
+        assert_eq!(calculate_crps(&[3.0], 5.0).unwrap(), 2.0);
+
+        // mean error of 1, less half the mean spread of 20 / 16
+        let crps = calculate_crps(&[4.0, 1.0, 3.0, 2.0], 2.5).unwrap();
+        assert!((crps - 0.375).abs() < 1e-12);
+        assert!(calculate_crps(&[4.0, 1.0, 3.0, 2.0], 10.0).unwrap() > crps);
+
+        assert!(calculate_crps(&[], 1.0).is_err());
+    }
+
+    #[test]
+    fn test_pinball_losses_and_pit() {
+        let samples = (0..=100).map(|x| x as f64).collect::<Vec<_>>();
+
+        // the 90% quantile is 90
+        assert_eq!(
+            calculate_pinball_losses(&samples, 100.0, &[0.9]).unwrap(),
+            vec![9.0]
+        );
+        let losses = calculate_pinball_losses(&samples, 80.0, &[0.5, 0.9]).unwrap();
+        assert!((losses[0] - 15.0).abs() < 1e-12);
+        assert!((losses[1] - 1.0).abs() < 1e-12);
+        assert!(calculate_pinball_losses(&samples, 80.0, &[1.5]).is_err());
+
+        assert_eq!(calculate_pit(&samples, 50.0).unwrap(), 50.5 / 101.0);
+        assert_eq!(calculate_pit(&samples, -1.0).unwrap(), 0.0);
+        assert_eq!(calculate_pit(&samples, 1000.0).unwrap(), 1.0);
+
+        assert_eq!(
+            calculate_pit_histogram(&[0.0, 0.1, 0.55, 1.0], 2).unwrap(),
+            vec![2, 2]
+        );
+        assert!(calculate_pit_histogram(&[1.5], 2).is_err());
+        assert!(calculate_pit_histogram(&[0.5], 0).is_err());
+    }
+
+    #[test]
+    fn test_score_settlement_twap() {
+        let round_start = 1708833600;
+        let daily = SettlementSpec {
+            twap_window_hours: 24,
+        };
+        let horizon = round_horizon(round_start, round_start + 48 * 3600, &daily).unwrap();
+        // path j stays at a fee of j + 1
+        let simulated_log_prices = DMatrix::from_fn(48, 10, |_, j| (j as f64 + 1.0).ln());
+        // realized fees settle at 5.5 whatever they did before the window
+        let realized_fees = (0..48)
+            .map(|hour| if hour < 24 { 100.0 } else { 5.5 })
+            .collect::<Vec<_>>();
+
+        let score =
+            score_settlement_twap(&simulated_log_prices, &realized_fees, &horizon, &[0.5]).unwrap();
+        let samples = (1..=10).map(|x| x as f64).collect::<Vec<_>>();
+        assert!((score.crps - calculate_crps(&samples, 5.5).unwrap()).abs() < 1e-9);
+        assert!(score.pinball_losses[0].abs() < 1e-9);
+        assert_eq!(score.pit, 0.5);
+
+        assert!(score_settlement_twap(
+            &simulated_log_prices,
+            &realized_fees[..47],
+            &horizon,
+            &[0.5]
+        )
+        .is_err());
+    }
 }
@@ -3,8 +3,15 @@ mod tests {
     use starknet_core::types::Felt;
 
     use crate::{
-        convert_felt_to_f64, generate_batched_hash_for_all_avg_base_fees,
-        hash_of_hash_of_avg_base_fees, hashing::hash_avg_base_fees_in_batch,
+        convert_felt_to_f64, convert_hash_to_u32_array,
+        generate_batched_hash_for_all_avg_base_fees,
+        generate_batched_hash_for_all_avg_base_fees_with_config,
+        generate_batched_hash_for_all_avg_fees, generate_batched_hash_for_all_avg_fees_with_config,
+        hash_avg_fees_batches_with_config, hash_avg_fees_in_batch, hash_f64_batch_digests,
+        hash_f64s, hash_f64s_in_batches, hash_felts_in_batch, hash_of_hash_of_avg_base_fees,
+        hash_of_hashes, hashing::hash_avg_base_fees_in_batch, roll_batch_hashes,
+        tests::mock::get_5760_avg_fees, validate_hourly_avg_fees, validate_rolling_avg_fees,
+        HashConfig, HashScheme,
     };
 
     #[test]
@@ -80,4 +87,277 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_hash_f64s() {
+        // sha256 of the u64 length prefix followed by the f64 little-endian bytes
+        assert_eq!(
+            hash_f64s(&[]),
+            [
+                2941612277, 2709588858, 4153192267, 3339347471, 234167874, 3136888141, 3853660813,
+                3773316604
+            ]
+        );
+        assert_eq!(
+            hash_f64s(&[1.0, 2.0]),
+            [
+                1486902415, 1230506517, 1991462008, 4250794647, 3826528023, 2067290100, 2151143762,
+                2239885164
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hash_f64s_is_order_and_length_sensitive() {
+        assert_ne!(hash_f64s(&[1.0, 2.0]), hash_f64s(&[2.0, 1.0]));
+        assert_ne!(hash_f64s(&[0.0]), hash_f64s(&[]));
+        assert_ne!(hash_f64s(&[0.0]), hash_f64s(&[-0.0]));
+    }
+
+    fn avg_fees_records() -> Vec<(u64, u64, Felt)> {
+        (0..3)
+            .map(|i| {
+                (
+                    1695470400 + 3600 * i,
+                    300 + i,
+                    Felt::from_hex_unchecked("0x6322CF2B00000000000000000000000000000000"),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_hash_avg_fees_in_batch() {
+        let input = avg_fees_records();
+        let result = hash_avg_fees_in_batch(&input);
+
+        assert_eq!(
+            convert_hash_to_u32_array(&result),
+            [
+                3309664900, 2334415257, 2821631708, 2440876780, 324901030, 4251895669, 610059383,
+                2239226137
+            ]
+        );
+
+        // a record is hashed as its three serde felts
+        let felts = input
+            .iter()
+            .flat_map(|(timestamp, data_points, avg_fee)| {
+                [Felt::from(*timestamp), Felt::from(*data_points), *avg_fee]
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(result, hash_avg_base_fees_in_batch(&felts));
+    }
+
+    #[test]
+    fn test_generate_batched_hash_for_all_avg_fees() {
+        let hash_res = generate_batched_hash_for_all_avg_fees(&avg_fees_records());
+        assert_eq!(
+            convert_hash_to_u32_array(&hash_res),
+            [
+                3963386538, 252926900, 3622054295, 1611113262, 3767675492, 162628680, 2120635062,
+                1737236831
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_hourly_avg_fees() {
+        assert!(validate_hourly_avg_fees(&avg_fees_records()).is_ok());
+        assert!(validate_hourly_avg_fees(&get_5760_avg_fees(1695470400)).is_ok());
+    }
+
+    #[test]
+    fn test_validate_hourly_avg_fees_rejects_invalid_records() {
+        assert!(validate_hourly_avg_fees(&[]).is_err());
+
+        let mut gap = avg_fees_records();
+        gap[2].0 += 3600;
+        assert!(validate_hourly_avg_fees(&gap).is_err());
+
+        let mut unordered = avg_fees_records();
+        unordered.swap(0, 1);
+        assert!(validate_hourly_avg_fees(&unordered).is_err());
+
+        let mut empty_hour = avg_fees_records();
+        empty_hour[1].1 = 0;
+        assert!(validate_hourly_avg_fees(&empty_hour).is_err());
+
+        let unaligned = avg_fees_records()
+            .into_iter()
+            .map(|(timestamp, data_points, avg_fee)| (timestamp + 60, data_points, avg_fee))
+            .collect::<Vec<_>>();
+        assert!(validate_hourly_avg_fees(&unaligned).is_err());
+    }
+
+    #[test]
+    fn test_default_hash_config_matches_fossil_hash_store() {
+        let inputs =
+            vec![Felt::from_hex_unchecked("0x6322CF2B00000000000000000000000000000000"); 5760];
+        let config = HashConfig {
+            batch_size: 180,
+            scheme: HashScheme::Sha256,
+        };
+
+        assert_eq!(HashConfig::default(), config);
+        assert_eq!(
+            generate_batched_hash_for_all_avg_base_fees_with_config(&inputs, &config),
+            generate_batched_hash_for_all_avg_base_fees(&inputs)
+        );
+    }
+
+    #[test]
+    fn test_hash_felts_in_batch_poseidon() {
+        // poseidon_hash_many([1, 2, 3]), the starknet-crypto test vector
+        let input = vec![Felt::from(1u64), Felt::from(2u64), Felt::from(3u64)];
+        assert_eq!(
+            Felt::from_bytes_be_slice(&hash_felts_in_batch(&input, HashScheme::Poseidon)),
+            Felt::from_hex_unchecked(
+                "0x2f0d8840bcf3bc629598d8a6cc80cb7c0d9e52d93dab244bbf9cd0dca0ad082"
+            )
+        );
+    }
+
+    #[test]
+    fn test_generate_batched_hash_for_all_avg_base_fees_poseidon() {
+        let inputs =
+            vec![Felt::from_hex_unchecked("0x6322CF2B00000000000000000000000000000000"); 5760];
+        let config = HashConfig {
+            batch_size: 180,
+            scheme: HashScheme::Poseidon,
+        };
+
+        // regression values of the Rust implementation; they have not been checked against
+        // a Cairo Poseidon implementation such as the fossil hash store
+        assert_eq!(
+            Felt::from_bytes_be_slice(&hash_felts_in_batch(&inputs[..180], config.scheme)),
+            Felt::from_hex_unchecked(
+                "0x6c3a54d210e0da50eee388a9ba29c8d64006db957a57bd2170020421433cc35"
+            )
+        );
+        assert_eq!(
+            Felt::from_bytes_be_slice(&generate_batched_hash_for_all_avg_base_fees_with_config(
+                &inputs, &config
+            )),
+            Felt::from_hex_unchecked(
+                "0x5779be077d38b4d68b93ec089e27ce5c9eb1778b2bd19938b6471f710c4f711"
+            )
+        );
+    }
+
+    #[test]
+    fn test_generate_batched_hash_for_all_avg_fees_poseidon() {
+        // batches of two records, the last batch holding a single record
+        let config = HashConfig {
+            batch_size: 2,
+            scheme: HashScheme::Poseidon,
+        };
+        let hash_res =
+            generate_batched_hash_for_all_avg_fees_with_config(&avg_fees_records(), &config);
+
+        assert_eq!(
+            Felt::from_bytes_be_slice(&hash_res),
+            Felt::from_hex_unchecked(
+                "0x3a1241711df18f4cf05c4bc8176e29128965d4f5610b0735860c65cc2056a10"
+            )
+        );
+    }
+
+    #[test]
+    fn test_batch_size_changes_the_hash() {
+        let records = avg_fees_records();
+        let single_batch = HashConfig {
+            batch_size: 180,
+            scheme: HashScheme::Sha256,
+        };
+        let one_per_batch = HashConfig {
+            batch_size: 1,
+            scheme: HashScheme::Sha256,
+        };
+
+        assert_eq!(
+            generate_batched_hash_for_all_avg_fees_with_config(&records, &single_batch),
+            generate_batched_hash_for_all_avg_fees(&records)
+        );
+        assert_ne!(
+            generate_batched_hash_for_all_avg_fees_with_config(&records, &one_per_batch),
+            generate_batched_hash_for_all_avg_fees(&records)
+        );
+    }
+
+    #[test]
+    fn test_rolling_window_matches_full_rehash() {
+        // 8 months of hours plus the next day, in daily batches
+        let mut records = get_5760_avg_fees(1695470400);
+        records.extend((5760..5784).map(|i| {
+            (
+                1695470400 + 3600 * i,
+                300,
+                Felt::from_hex_unchecked("0x6322CF2B00000000000000000000000000000000"),
+            )
+        }));
+        let config = HashConfig {
+            batch_size: 24,
+            scheme: HashScheme::Sha256,
+        };
+
+        let previous = hash_avg_fees_batches_with_config(&records[..5760], &config);
+        let new_batches = hash_avg_fees_batches_with_config(&records[5760..5784], &config);
+        let rolled = roll_batch_hashes(&previous, &new_batches).unwrap();
+
+        assert_eq!(
+            hash_of_hashes(&rolled, config.scheme),
+            generate_batched_hash_for_all_avg_fees_with_config(&records[24..5784], &config)
+        );
+    }
+
+    #[test]
+    fn test_roll_batch_hashes_keeps_window_length() {
+        let previous = vec![vec![1u8; 32], vec![2u8; 32], vec![3u8; 32]];
+
+        assert_eq!(
+            roll_batch_hashes(&previous, &[vec![4u8; 32]]).unwrap(),
+            vec![vec![2u8; 32], vec![3u8; 32], vec![4u8; 32]]
+        );
+        assert_eq!(roll_batch_hashes(&previous, &[]).unwrap(), previous);
+        assert!(roll_batch_hashes(&previous[..1], &previous[1..]).is_err());
+    }
+
+    #[test]
+    fn test_roll_f64_batch_digests() {
+        let values = (0..120).map(|i| i as f64 * 0.5).collect::<Vec<_>>();
+
+        let previous = hash_f64s_in_batches(&values[..96], 24);
+        assert_eq!(previous.len(), 4);
+        assert_eq!(previous[1], hash_f64s(&values[24..48]));
+
+        // rolling one day forward digests the same batches as the shifted window
+        let rolled =
+            roll_batch_hashes(&previous, &hash_f64s_in_batches(&values[96..], 24)).unwrap();
+        assert_eq!(rolled, hash_f64s_in_batches(&values[24..], 24));
+        assert_eq!(
+            hash_f64_batch_digests(&rolled),
+            hash_f64_batch_digests(&hash_f64s_in_batches(&values[24..], 24))
+        );
+        assert_ne!(
+            hash_f64_batch_digests(&rolled),
+            hash_f64_batch_digests(&previous)
+        );
+    }
+
+    #[test]
+    fn test_validate_rolling_avg_fees() {
+        let records = avg_fees_records();
+        let previous_last_timestamp = records[0].0 - 3600;
+
+        assert!(validate_rolling_avg_fees(previous_last_timestamp, &records, 3).is_ok());
+        assert!(validate_rolling_avg_fees(previous_last_timestamp, &records, 1).is_ok());
+        // partial batch
+        assert!(validate_rolling_avg_fees(previous_last_timestamp, &records, 2).is_err());
+        assert!(validate_rolling_avg_fees(previous_last_timestamp, &records, 0).is_err());
+        // gap or overlap with the previous window
+        assert!(validate_rolling_avg_fees(previous_last_timestamp - 3600, &records, 3).is_err());
+        assert!(validate_rolling_avg_fees(records[0].0, &records, 3).is_err());
+        assert!(validate_rolling_avg_fees(previous_last_timestamp, &[], 3).is_err());
+    }
 }
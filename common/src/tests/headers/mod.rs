@@ -0,0 +1,278 @@
+#[cfg(test)]
+mod tests {
+    use crate::{decode_block_header, extract_base_fees, keccak256, verify_header_chain};
+
+    use std::{env, fs, path::Path};
+
+    // RLP-encoded headers, one hex string per line: the mainnet genesis header, and a
+    // synthetic chain of four Cancun-format headers of blocks 19000000 to 19000003
+    const MAINNET_GENESIS: &str = include_str!("fixtures/mainnet_genesis.hex");
+    const SYNTHETIC_CHAIN: &str = include_str!("fixtures/synthetic_chain.hex");
+    // Synthetic pairs of headers across a fork, each header in the format of its block:
+    // London (16 fields) to Shanghai (17) at the Shanghai fork block and timestamp, Shanghai
+    // to Cancun (20) at the Cancun fork block and timestamp, and Cancun to Prague (21)
+    const SYNTHETIC_SHANGHAI_FORK: &str = include_str!("fixtures/synthetic_shanghai_fork.hex");
+    const SYNTHETIC_CANCUN_FORK: &str = include_str!("fixtures/synthetic_cancun_fork.hex");
+    const SYNTHETIC_PRAGUE_FORK: &str = include_str!("fixtures/synthetic_prague_fork.hex");
+    // Mainnet headers are not vendored. `test_verify_mainnet_header_chain` reads consecutive
+    // post-London headers from this file, one `debug_getRawHeader` result per line
+    const MAINNET_CHAIN_ENV: &str = "PITCHLAKE_MAINNET_HEADERS";
+
+    fn decode_hex(hex: &str) -> Vec<u8> {
+        let hex = hex.trim().trim_start_matches("0x");
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn decode_hash(hex: &str) -> [u8; 32] {
+        decode_hex(hex).try_into().unwrap()
+    }
+
+    fn synthetic_chain() -> Vec<Vec<u8>> {
+        SYNTHETIC_CHAIN.lines().map(decode_hex).collect()
+    }
+
+    fn synthetic_anchor() -> [u8; 32] {
+        decode_hash("04272ced4176b2c8a5fd6202484f8879c25ef4909238909b0a850c26cf2faca9")
+    }
+
+    // number of fields in the RLP list of a header
+    fn field_count(rlp: &[u8]) -> usize {
+        let mut fields = 0;
+        // skip the list prefix, then every string, all of which are short or long strings
+        let mut position = (rlp[0] - 0xf7) as usize + 1;
+        while position < rlp.len() {
+            let prefix = rlp[position];
+            position += match prefix {
+                0x00..=0x7f => 1,
+                0x80..=0xb7 => 1 + (prefix - 0x80) as usize,
+                _ => {
+                    let length_of_length = (prefix - 0xb7) as usize;
+                    let length = rlp[position + 1..position + 1 + length_of_length]
+                        .iter()
+                        .fold(0, |length, byte| length * 256 + *byte as usize);
+                    1 + length_of_length + length
+                }
+            };
+            fields += 1;
+        }
+        fields
+    }
+
+    #[test]
+    fn test_keccak256() {
+        assert_eq!(
+            keccak256(&[]),
+            decode_hash("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")
+        );
+    }
+
+    #[test]
+    fn test_decode_mainnet_genesis_header() {
+        let header = decode_block_header(&decode_hex(MAINNET_GENESIS)).unwrap();
+
+        assert_eq!(
+            header.hash,
+            decode_hash("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3")
+        );
+        assert_eq!(header.parent_hash, [0u8; 32]);
+        assert_eq!(header.number, 0);
+        assert_eq!(header.timestamp, 0);
+        // genesis predates London
+        assert_eq!(header.base_fee, None);
+        assert!(extract_base_fees(&[header]).is_err());
+    }
+
+    #[test]
+    fn test_verify_header_chain() {
+        let headers = verify_header_chain(&synthetic_chain(), &synthetic_anchor()).unwrap();
+
+        // parent hash of the first header is keccak256("pitchlake synthetic parent")
+        assert_eq!(
+            headers[0].parent_hash,
+            keccak256(b"pitchlake synthetic parent")
+        );
+        assert_eq!(
+            headers[0].hash,
+            decode_hash("9a50aa12368a7b06417bc81fc29422ebc5a22b158138e6204da42e33c1fc617b")
+        );
+        assert_eq!(headers[3].hash, synthetic_anchor());
+
+        // a 1 byte base fee is encoded as a single RLP byte
+        assert_eq!(
+            extract_base_fees(&headers).unwrap(),
+            vec![
+                (19000000, 1708833588, 23712345678),
+                (19000001, 1708833600, 24001002003),
+                (19000002, 1708833612, 21000000007),
+                (19000003, 1708833624, 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_verify_header_chain_across_forks() {
+        let forks = [
+            (
+                SYNTHETIC_SHANGHAI_FORK,
+                (16, 17),
+                17034870,
+                1681338455,
+                "250c5aecaa201240b1357d89dadea15379ac916b68ddeacc9db41c56f3d52c76",
+                "c325ed34083addfa304e1e38a0631e935f0ef8523f01eec2c9134bf2d1490f6d",
+            ),
+            (
+                SYNTHETIC_CANCUN_FORK,
+                (17, 20),
+                19426587,
+                1710338135,
+                "d2832e901674b1d2523cdf5aa426e83f2eb91f4fdeeb340f0b9249966103a7e2",
+                "758312e906602549f8069d1cec752f3e360ab1f83c33846eb90a7c6bebb544cf",
+            ),
+            (
+                SYNTHETIC_PRAGUE_FORK,
+                (20, 21),
+                22431084,
+                1746612311,
+                "5d32aee76b4d8a96c3f5f8ee148f9a0a203bd4584064606a8b2482d1005bdd05",
+                "e3757210a23c4a6d60599ba0b4cd2716bfa8e91ba4d4c3ae170d680b0b058744",
+            ),
+        ];
+
+        for (fixture, (fields_before, fields_after), fork_block, fork_timestamp, hash, anchor) in
+            forks
+        {
+            let chain = fixture.lines().map(decode_hex).collect::<Vec<_>>();
+            assert_eq!(field_count(&chain[0]), fields_before);
+            assert_eq!(field_count(&chain[1]), fields_after);
+
+            let headers = verify_header_chain(&chain, &decode_hash(anchor)).unwrap();
+            assert_eq!(headers[0].hash, decode_hash(hash));
+            assert_eq!(headers[1].number, fork_block);
+            assert_eq!(headers[1].timestamp, fork_timestamp);
+            // the base fee stays the 16th field whatever follows it
+            assert!(extract_base_fees(&headers).is_ok());
+        }
+    }
+
+    // Run with `PITCHLAKE_MAINNET_HEADERS=<file> cargo test -- --ignored` on consecutive
+    // mainnet headers from London on. The chain is anchored on its own last hash, so this
+    // checks decoding and linking of real headers, not which chain they come from.
+    #[test]
+    #[ignore = "needs mainnet headers in the file named by PITCHLAKE_MAINNET_HEADERS"]
+    fn test_verify_mainnet_header_chain() {
+        let file_name = env::var(MAINNET_CHAIN_ENV).unwrap();
+        let chain = fs::read_to_string(Path::new(&file_name))
+            .unwrap()
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(decode_hex)
+            .collect::<Vec<_>>();
+        let anchor = keccak256(chain.last().unwrap());
+
+        let headers = verify_header_chain(&chain, &anchor).unwrap();
+        for (rlp, header) in chain.iter().zip(&headers) {
+            // London adds the base fee, Shanghai the withdrawals root, Cancun the blob gas
+            // fields and the beacon root, Prague the requests hash
+            let fields = match header.number {
+                ..=12964999 => panic!("Block {} predates London", header.number),
+                12965000..=17034869 => 16,
+                17034870..=19426586 => 17,
+                19426587..=22431083 => 20,
+                _ => 21,
+            };
+            assert_eq!(field_count(rlp), fields, "block {}", header.number);
+            // EIP-1559 started at a base fee of 1 gwei
+            if header.number == 12965000 {
+                assert_eq!(header.base_fee, Some(1_000_000_000));
+            }
+        }
+        assert_eq!(extract_base_fees(&headers).unwrap().len(), chain.len());
+    }
+
+    #[test]
+    fn test_verify_header_chain_sub_range() {
+        // any suffix of the chain verifies against the same anchor
+        let chain = synthetic_chain();
+        let headers = verify_header_chain(&chain[2..], &synthetic_anchor()).unwrap();
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers[0].number, 19000002);
+
+        // a prefix needs its own last hash as the anchor
+        assert!(verify_header_chain(&chain[..3], &synthetic_anchor()).is_err());
+        let anchor =
+            decode_hash("9e480014408aadc78d2a9039251495adc1fe99978ec0f69c5369b47232a140d5");
+        assert!(verify_header_chain(&chain[..3], &anchor).is_ok());
+    }
+
+    #[test]
+    fn test_verify_header_chain_rejects_wrong_anchor() {
+        let mut anchor = synthetic_anchor();
+        anchor[31] ^= 1;
+        assert!(verify_header_chain(&synthetic_chain(), &anchor).is_err());
+    }
+
+    #[test]
+    fn test_verify_header_chain_rejects_broken_links() {
+        let chain = synthetic_chain();
+
+        // a missing header
+        let gapped = vec![chain[0].clone(), chain[2].clone(), chain[3].clone()];
+        assert!(verify_header_chain(&gapped, &synthetic_anchor()).is_err());
+
+        // swapped headers
+        let swapped = vec![
+            chain[0].clone(),
+            chain[2].clone(),
+            chain[1].clone(),
+            chain[3].clone(),
+        ];
+        assert!(verify_header_chain(&swapped, &synthetic_anchor()).is_err());
+
+        assert!(verify_header_chain(&[], &synthetic_anchor()).is_err());
+    }
+
+    #[test]
+    fn test_verify_header_chain_rejects_tampered_header() {
+        // changing the last byte of the first header changes its hash, so the second header
+        // no longer links to it, while the header itself still decodes
+        let mut chain = synthetic_chain();
+        let last = chain[0].len() - 1;
+        chain[0][last] ^= 1;
+        assert!(decode_block_header(&chain[0]).is_ok());
+        assert!(verify_header_chain(&chain, &synthetic_anchor()).is_err());
+
+        // changing the fee of the last header breaks the anchor
+        let mut chain = synthetic_chain();
+        let position = chain[3]
+            .windows(2)
+            .rposition(|bytes| bytes == [0x07, 0xa0])
+            .unwrap();
+        chain[3][position] = 0x08;
+        assert_eq!(decode_block_header(&chain[3]).unwrap().base_fee, Some(8));
+        assert!(verify_header_chain(&chain, &synthetic_anchor()).is_err());
+    }
+
+    #[test]
+    fn test_decode_block_header_rejects_malformed_rlp() {
+        let header = synthetic_chain().remove(0);
+
+        // truncated
+        assert!(decode_block_header(&header[..header.len() - 1]).is_err());
+
+        // trailing bytes
+        let mut trailing = header.clone();
+        trailing.push(0x80);
+        assert!(decode_block_header(&trailing).is_err());
+
+        // not a list
+        assert!(decode_block_header(&[0x83, 0x01, 0x02, 0x03]).is_err());
+
+        // too few fields
+        assert!(decode_block_header(&[0xc3, 0x80, 0x80, 0x80]).is_err());
+
+        assert!(decode_block_header(&[]).is_err());
+    }
+}
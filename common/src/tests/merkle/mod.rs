@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod tests {
+    use starknet_core::types::Felt;
+
+    use crate::{
+        compute_avg_fees_range_root, convert_hash_to_u32_array, convert_u32_array_to_hash,
+        hash_avg_fees_in_batch, hash_avg_fees_leaf, hash_merkle_node,
+        tests::mock::get_5760_avg_fees, verify_avg_fees_range, AvgFeesMerkleTree,
+        EMPTY_MERKLE_LEAF,
+    };
+
+    fn avg_fees_records(count: u64) -> Vec<(u64, u64, Felt)> {
+        (0..count)
+            .map(|i| {
+                (
+                    1695470400 + 3600 * i,
+                    300 + i,
+                    Felt::from_hex_unchecked("0x6322CF2B00000000000000000000000000000000"),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_avg_fees_merkle_root() {
+        let records = avg_fees_records(3);
+        let tree = AvgFeesMerkleTree::new(&records).unwrap();
+
+        // result gotten from hashlib, with the leaves padded to four
+        assert_eq!(
+            convert_hash_to_u32_array(&tree.root()),
+            [
+                829066782, 3990515226, 1511074481, 1089810115, 1372310150, 1434922290, 3411921626,
+                166398084
+            ]
+        );
+        assert_eq!(
+            tree.root(),
+            hash_merkle_node(
+                &hash_merkle_node(
+                    &hash_avg_fees_leaf(&records[0]),
+                    &hash_avg_fees_leaf(&records[1])
+                ),
+                &hash_merkle_node(&hash_avg_fees_leaf(&records[2]), &EMPTY_MERKLE_LEAF)
+            )
+        );
+        assert_eq!(
+            hash_avg_fees_leaf(&records[0]).to_vec(),
+            hash_avg_fees_in_batch(&records[..1])
+        );
+    }
+
+    #[test]
+    fn test_single_record_root_is_its_leaf() {
+        let records = avg_fees_records(1);
+        let tree = AvgFeesMerkleTree::new(&records).unwrap();
+
+        assert_eq!(tree.root(), hash_avg_fees_leaf(&records[0]));
+        assert!(tree.prove_range(0..1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_every_range_verifies() {
+        for count in 1..=9 {
+            let records = avg_fees_records(count);
+            let tree = AvgFeesMerkleTree::new(&records).unwrap();
+
+            for start in 0..records.len() {
+                for end in start + 1..=records.len() {
+                    let siblings = tree.prove_range(start..end).unwrap();
+                    verify_avg_fees_range(
+                        &tree.root(),
+                        records.len(),
+                        start,
+                        &records[start..end],
+                        &siblings,
+                    )
+                    .unwrap();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_30_day_window_of_8_months() {
+        let records = get_5760_avg_fees(1695470400);
+        let tree = AvgFeesMerkleTree::new(&records).unwrap();
+
+        let window = 5760 - 720..5760;
+        let siblings = tree.prove_range(window.clone()).unwrap();
+
+        // at most two siblings per level of the 8192 leaf tree, however many hours are left out
+        assert!(siblings.len() <= 2 * 13);
+        assert!(verify_avg_fees_range(
+            &tree.root(),
+            records.len(),
+            window.start,
+            &records[window],
+            &siblings
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_range_proof_rejects_tampering() {
+        let records = avg_fees_records(7);
+        let tree = AvgFeesMerkleTree::new(&records).unwrap();
+        let siblings = tree.prove_range(2..5).unwrap();
+        let root = tree.root();
+
+        let mut tampered = records[2..5].to_vec();
+        tampered[1].1 += 1;
+        assert!(verify_avg_fees_range(&root, 7, 2, &tampered, &siblings).is_err());
+
+        // same records claimed at another position
+        assert!(verify_avg_fees_range(&root, 7, 3, &records[2..5], &siblings).is_err());
+
+        let mut extra = siblings.clone();
+        extra.push(EMPTY_MERKLE_LEAF);
+        assert!(verify_avg_fees_range(&root, 7, 2, &records[2..5], &extra).is_err());
+        assert!(verify_avg_fees_range(&root, 7, 2, &records[2..5], &siblings[1..]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_ranges() {
+        let records = avg_fees_records(4);
+        let tree = AvgFeesMerkleTree::new(&records).unwrap();
+
+        assert!(AvgFeesMerkleTree::new(&[]).is_err());
+        assert!(tree.prove_range(2..2).is_err());
+        assert!(tree.prove_range(3..5).is_err());
+        assert!(compute_avg_fees_range_root(4, 3, &records[..2], &[]).is_err());
+    }
+
+    #[test]
+    fn test_convert_u32_array_to_hash() {
+        let root = AvgFeesMerkleTree::new(&avg_fees_records(3)).unwrap().root();
+        assert_eq!(
+            convert_u32_array_to_hash(&convert_hash_to_u32_array(&root)),
+            root
+        );
+    }
+}
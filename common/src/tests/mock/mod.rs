@@ -1,5 +1,7 @@
 use starknet_core::types::Felt;
 
+use crate::{convert_f64_to_felt, convert_felt_to_f64};
+
 // 2160 data
 pub fn get_first_period_data() -> Vec<(i64, f64)> {
     return vec![
@@ -10850,13 +10852,38 @@ pub fn get_5760_avg_base_fees_felt() -> Vec<Felt> {
     let mut res = Vec::with_capacity(5760);
     for i in 0..5760 {
         let index = i % len;
+        // rounded to the nearest f64, so that the fees round-trip through f64 exactly
         let felt = Felt::from_hex_unchecked(&hexes[index]);
-        res.push(felt);
+        res.push(convert_f64_to_felt(convert_felt_to_f64(felt)).unwrap());
     }
 
     res
 }
 
+/// Mock `AvgFees` records as `(timestamp, data_points, avg_fee)` for the 5760 hourly fees,
+/// starting at `start_timestamp` with 300 blocks (one hour of 12s blocks) per record.
+pub fn get_5760_avg_fees(start_timestamp: u64) -> Vec<(u64, u64, Felt)> {
+    get_5760_avg_base_fees_felt()
+        .into_iter()
+        .enumerate()
+        .map(|(i, avg_fee)| (start_timestamp + 3600 * i as u64, 300, avg_fee))
+        .collect()
+}
+
+/// Mock `(number, timestamp, base_fee)` blocks, 12 seconds apart, covering `hours` full hours
+/// from `start_timestamp` plus one bounding block in the hours before and after.
+pub fn get_mock_blocks(start_timestamp: u64, hours: u64) -> Vec<(u64, u64, u64)> {
+    (0..hours * 300 + 2)
+        .map(|i| {
+            let number = 19_000_000 + i;
+            let timestamp = start_timestamp - 12 + 12 * i;
+            // between 10 and 40 gwei
+            let base_fee = 10_000_000_000 + (i * 7919 % 3000) * 10_000_000;
+            (number, timestamp, base_fee)
+        })
+        .collect()
+}
+
 pub fn convert_data_to_vec_of_tuples(data: Vec<f64>, start_timestamp: i64) -> Vec<(i64, f64)> {
     data.iter()
         .enumerate()
@@ -1,5 +1,13 @@
+mod aggregation;
+mod backtest;
+#[cfg(feature = "columnar")]
+mod columnar;
+mod fixed_point;
 mod floating_point;
 mod hashing;
+mod headers;
+mod merkle;
 pub mod mock;
 #[cfg(feature = "original")]
 mod original;
+mod window;
@@ -10,9 +10,9 @@ mod tests {
         convert_felt_to_f64,
         floating_point::{
             self, add_twap_7d, calculate_remove_seasonality,
-            calculate_twap as calculate_twap_floating,
-            calculated_reserve_price_from_simulated_log_prices, error_bound_dvec, error_bound_f64,
-            error_bound_vec, pre_minimize,
+            calculate_twap as calculate_twap_floating, calculated_reserve_price_for_round,
+            error_bound_dvec, error_bound_f64, error_bound_vec, pre_minimize, FeeModelKind,
+            PayoffKind,
         },
         original::{
             calculate_reserve_price, calculate_twap::calculate_twap, convert_array1_to_dvec,
@@ -22,8 +22,14 @@ mod tests {
             convert_data_to_vec_of_tuples, get_5760_avg_base_fees_felt, get_first_period_data,
             get_max_return_input_data,
         },
+        window::{round_horizon_from_hours, RoundHorizon, SettlementSpec},
     };
 
+    // the 30-day round the POC simulates, settled on its last 7 days
+    fn thirty_day_horizon() -> RoundHorizon {
+        round_horizon_from_hours(720, &SettlementSpec::default()).unwrap()
+    }
+
     #[test]
     fn test_convert_input_to_df() {
         let data = get_first_period_data();
@@ -78,7 +84,7 @@ mod tests {
     fn test_calculate_reserve_price() {
         let data = get_first_period_data();
 
-        let res = calculate_reserve_price(&data, 15000, 720);
+        let res = calculate_reserve_price(&data, 15000, &thirty_day_horizon());
 
         println!("res: {:?}", res);
     }
@@ -102,7 +108,7 @@ mod tests {
         let _end_timestamp = 1708833600 + (3600 * 24 * 30 * 3); // as long as start to end timestamp is 90 days
         let data = convert_data_to_vec_of_tuples(data.clone(), start_timestamp);
 
-        let res = calculate_reserve_price(&data, 15000, 720);
+        let res = calculate_reserve_price(&data, 15000, &thirty_day_horizon());
         println!("res.reserve_price: {:?}", res.reserve_price);
         // res.reserve_price: 1755519897.514507
 
@@ -111,14 +117,15 @@ mod tests {
         let pt = convert_array1_to_dvec(res.pt);
         let pt_1 = convert_array1_to_dvec(res.pt_1);
         let num_paths = 4000;
-        let n_periods = 720;
+        let horizon = thirty_day_horizon();
         let (is_saddle_point, simulated_price) = floating_point::simulate_price_verify_position(
+            FeeModelKind::MeanRevertingJump,
             &res.positions,
             &pt,
             &pt_1,
             5e-2,
             &de_seasonalised_detrended_log_base_fee,
-            n_periods,
+            horizon.n_periods,
             num_paths,
         );
         assert!(is_saddle_point);
@@ -140,7 +147,7 @@ mod tests {
             res.intercept,
             data.len(),
             num_paths,
-            n_periods,
+            &horizon,
         )
         .unwrap();
 
@@ -152,10 +159,11 @@ mod tests {
         // reserve_price: 1765847736.6691935 (num_paths: 15,000)
         // reserve_price: 1710956542.6769266 (num_paths: 4,000)
 
-        let original_reserve_price = calculated_reserve_price_from_simulated_log_prices(
+        let original_reserve_price = calculated_reserve_price_for_round(
             &convert_array2_to_dmatrix(res.simulated_log_prices),
             &res.twap_7d,
-            n_periods,
+            &horizon,
+            &PayoffKind::default(),
         )
         .unwrap();
 
@@ -187,17 +195,18 @@ mod tests {
     }
 
     #[test]
-    fn test_compare_calculated_reserve_price_from_simulated_log_prices() {
+    fn test_compare_calculated_reserve_price_for_round() {
         let num_paths = 4000;
-        let n_periods = 720;
+        let horizon = thirty_day_horizon();
         let data = get_first_period_data();
-        let res = calculate_reserve_price(&data, num_paths, n_periods);
+        let res = calculate_reserve_price(&data, num_paths, &horizon);
         println!("original reserve_price: {:?}", res.reserve_price);
 
-        let reserve_price = floating_point::calculated_reserve_price_from_simulated_log_prices(
+        let reserve_price = calculated_reserve_price_for_round(
             &convert_array2_to_dmatrix(res.simulated_log_prices),
             &res.twap_7d,
-            n_periods,
+            &horizon,
+            &PayoffKind::default(),
         )
         .unwrap();
 
@@ -207,7 +216,7 @@ mod tests {
     #[test]
     fn test_compare_add_twap_7d() {
         let data = get_first_period_data();
-        let res = calculate_reserve_price(&data, 15000, 720);
+        let res = calculate_reserve_price(&data, 15000, &thirty_day_horizon());
         let twap_7d = add_twap_7d(&data.iter().map(|x| x.1).collect()).unwrap();
 
         let percentage_tolerance = 0.00001;
@@ -218,7 +227,7 @@ mod tests {
     #[test]
     fn test_compare_remove_seasonality() {
         let data = get_first_period_data();
-        let res = calculate_reserve_price(&data, 15000, 720);
+        let res = calculate_reserve_price(&data, 15000, &thirty_day_horizon());
 
         let (slope, intercept, de_seasonalised_detrended_log_base_fee, season_param) =
             calculate_remove_seasonality(&data.iter().map(|x| x.1).collect()).unwrap();
@@ -247,7 +256,7 @@ mod tests {
     #[test]
     fn test_compare_calculate_pt_pt1() {
         let data = get_first_period_data();
-        let res = calculate_reserve_price(&data, 15000, 720);
+        let res = calculate_reserve_price(&data, 15000, &thirty_day_horizon());
 
         let (pt, pt_1, _var_pt) = pre_minimize(&convert_array1_to_dvec(
             res.de_seasonalised_detrended_log_base_fee,
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use crate::window::{
+        hourly_window_length, hourly_window_range, round_horizon, round_horizon_from_hours,
+        RoundHorizon, SettlementSpec,
+    };
+
+    const DATA_START: i64 = 1708833600 - 3600 * 24 * 30 * 5;
+    const DATA_END: i64 = 1708833600 + 3600 * 24 * 30 * 3;
+
+    #[test]
+    fn test_hourly_window_length() {
+        assert_eq!(hourly_window_length(DATA_START, DATA_END).unwrap(), 5760);
+        assert_eq!(hourly_window_length(0, 3600).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_hourly_window_length_rejects_unaligned_and_empty_windows() {
+        assert!(hourly_window_length(1800, 7200).is_err());
+        assert!(hourly_window_length(0, 5400).is_err());
+        assert!(hourly_window_length(3600, 3600).is_err());
+        assert!(hourly_window_length(7200, 3600).is_err());
+    }
+
+    #[test]
+    fn test_hourly_window_range() {
+        // last 90 days of the 8 months of data
+        let range = hourly_window_range(DATA_START, DATA_END, 1708833600, DATA_END).unwrap();
+        assert_eq!(range, 3600..5760);
+
+        let range = hourly_window_range(DATA_START, DATA_END, DATA_START, DATA_END).unwrap();
+        assert_eq!(range, 0..5760);
+
+        let range = hourly_window_range(
+            DATA_START,
+            DATA_END,
+            DATA_START + 3600,
+            DATA_START + 3 * 3600,
+        )
+        .unwrap();
+        assert_eq!(range, 1..3);
+    }
+
+    #[test]
+    fn test_hourly_window_range_rejects_windows_outside_the_data() {
+        assert!(hourly_window_range(DATA_START, DATA_END, DATA_START - 3600, DATA_END).is_err());
+        assert!(hourly_window_range(DATA_START, DATA_END, DATA_START, DATA_END + 3600).is_err());
+        assert!(hourly_window_range(DATA_START, DATA_END, DATA_START + 60, DATA_END).is_err());
+    }
+
+    #[test]
+    fn test_round_horizon() {
+        let day = 3600 * 24;
+        let settlement = SettlementSpec::default();
+
+        // the 30-day round n_periods = 720 has stood for
+        let horizon = round_horizon(DATA_END, DATA_END + 30 * day, &settlement).unwrap();
+        assert_eq!(
+            horizon,
+            RoundHorizon {
+                n_periods: 720,
+                settlement_start: 552,
+            }
+        );
+
+        let horizon = round_horizon(DATA_END, DATA_END + 14 * day, &settlement).unwrap();
+        assert_eq!((horizon.n_periods, horizon.settlement_start), (336, 168));
+
+        let daily = SettlementSpec {
+            twap_window_hours: 24,
+        };
+        let horizon = round_horizon(DATA_END, DATA_END + 60 * day, &daily).unwrap();
+        assert_eq!((horizon.n_periods, horizon.settlement_start), (1440, 1416));
+
+        // rounds shorter than their settlement window, unaligned or empty rounds
+        assert!(round_horizon(DATA_END, DATA_END + 6 * day, &settlement).is_err());
+        assert!(round_horizon(DATA_END, DATA_END + day + 60, &daily).is_err());
+        assert!(round_horizon(DATA_END, DATA_END, &daily).is_err());
+        let empty = SettlementSpec {
+            twap_window_hours: 0,
+        };
+        assert!(round_horizon(DATA_END, DATA_END + day, &empty).is_err());
+    }
+
+    #[test]
+    fn test_round_horizon_from_hours() {
+        let settlement = SettlementSpec::default();
+        let horizon = round_horizon_from_hours(720, &settlement).unwrap();
+        assert_eq!(
+            horizon,
+            round_horizon(DATA_END, DATA_END + 720 * 3600, &settlement).unwrap()
+        );
+
+        assert!(round_horizon_from_hours(100, &settlement).is_err());
+    }
+}
@@ -0,0 +1,110 @@
+use std::ops::Range;
+
+use eyre::{anyhow as err, Result};
+use serde::{Deserialize, Serialize};
+
+pub const SECONDS_IN_HOUR: i64 = 3600;
+
+/// Number of hourly data points in `[start_timestamp, end_timestamp)`.
+/// Both timestamps must fall on an hour boundary and the window must not be empty.
+pub fn hourly_window_length(start_timestamp: i64, end_timestamp: i64) -> Result<usize> {
+    for timestamp in [start_timestamp, end_timestamp] {
+        if timestamp.rem_euclid(SECONDS_IN_HOUR) != 0 {
+            return Err(err!("Timestamp {} is not aligned to an hour", timestamp));
+        }
+    }
+
+    if end_timestamp <= start_timestamp {
+        return Err(err!(
+            "Window end {} is not after its start {}",
+            end_timestamp,
+            start_timestamp
+        ));
+    }
+
+    Ok(((end_timestamp - start_timestamp) / SECONDS_IN_HOUR) as usize)
+}
+
+/// Index range of the hourly window `[start_timestamp, end_timestamp)` inside hourly data
+/// covering `[data_start_timestamp, data_end_timestamp)`, where the first data point is the
+/// hour starting at `data_start_timestamp`.
+pub fn hourly_window_range(
+    data_start_timestamp: i64,
+    data_end_timestamp: i64,
+    start_timestamp: i64,
+    end_timestamp: i64,
+) -> Result<Range<usize>> {
+    hourly_window_length(data_start_timestamp, data_end_timestamp)?;
+    let length = hourly_window_length(start_timestamp, end_timestamp)?;
+
+    if start_timestamp < data_start_timestamp || end_timestamp > data_end_timestamp {
+        return Err(err!(
+            "Window [{}, {}) is not contained in the data range [{}, {})",
+            start_timestamp,
+            end_timestamp,
+            data_start_timestamp,
+            data_end_timestamp
+        ));
+    }
+
+    let offset = ((start_timestamp - data_start_timestamp) / SECONDS_IN_HOUR) as usize;
+    Ok(offset..offset + length)
+}
+
+/// How an option round settles: on the TWAP of its last `twap_window_hours` hours.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SettlementSpec {
+    pub twap_window_hours: usize,
+}
+
+impl Default for SettlementSpec {
+    /// The 7-day TWAP rounds have always settled on.
+    fn default() -> Self {
+        SettlementSpec {
+            twap_window_hours: 24 * 7,
+        }
+    }
+}
+
+/// Simulation horizon of an option round, in hours from the start of the round.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RoundHorizon {
+    /// Hours simulated, one per hour of the round.
+    pub n_periods: usize,
+    /// First simulated hour of the settlement window.
+    pub settlement_start: usize,
+}
+
+/// Horizon of the round `[round_start_timestamp, round_end_timestamp)` settled under
+/// `settlement`. The round must be hour aligned and at least as long as its settlement
+/// window.
+pub fn round_horizon(
+    round_start_timestamp: i64,
+    round_end_timestamp: i64,
+    settlement: &SettlementSpec,
+) -> Result<RoundHorizon> {
+    round_horizon_from_hours(
+        hourly_window_length(round_start_timestamp, round_end_timestamp)?,
+        settlement,
+    )
+}
+
+/// Horizon of a round of `n_periods` hours settled under `settlement`. The round must be
+/// at least as long as its settlement window.
+pub fn round_horizon_from_hours(
+    n_periods: usize,
+    settlement: &SettlementSpec,
+) -> Result<RoundHorizon> {
+    if settlement.twap_window_hours == 0 || settlement.twap_window_hours > n_periods {
+        return Err(err!(
+            "Settlement window of {} hours does not fit in a round of {} hours",
+            settlement.twap_window_hours,
+            n_periods
+        ));
+    }
+
+    Ok(RoundHorizon {
+        n_periods,
+        settlement_start: n_periods - settlement.twap_window_hours,
+    })
+}
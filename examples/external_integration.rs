@@ -6,18 +6,22 @@
 use add_twap_7d_error_bound_floating::add_twap_7d_error_bound;
 use calculate_pt_pt1_error_bound_floating::calculate_pt_pt1_error_bound_floating;
 use common::{
+    convert_felt_to_f64,
     common::dataframe::{read_data_from_file, replace_timestamp_with_date},
     floating_point,
     original::{self, convert_array1_to_dvec},
-    tests::mock::{convert_data_to_vec_of_tuples, get_5760_avg_base_fees_felt},
+    tests::mock::{convert_data_to_vec_of_tuples, get_5760_avg_base_fees_felt, get_5760_avg_fees},
+    window::{round_horizon, SettlementSpec},
+    HashConfig,
 };
 use core::{
-    AddTwap7dErrorBoundFloatingInput, CalculatePtPt1ErrorBoundFloatingInput, HashingFeltInput,
+    AddTwap7dErrorBoundFloatingInput, AvgFees, CalculatePtPt1ErrorBoundFloatingInput,
+    DataHashingProof, HashingAvgFeesInput,
     MaxReturnInput, ProofCompositionInput, ProofCompositionOutput,
     RemoveSeasonalityErrorBoundFloatingInput, SimulatePriceVerifyPositionInput,
-    TwapErrorBoundInput,
+    TwapErrorBoundInput, TwapKind,
 };
-use hashing_felts::hash_felts;
+use hashing_avg_fees::hash_avg_fees;
 use max_return_floating::max_return;
 use proof_composition_twap_maxreturn_reserveprice_floating_hashing_methods::{
     PROOF_COMPOSITION_TWAP_MAXRETURN_RESERVEPRICE_FLOATING_HASHING_GUEST_ELF,
@@ -90,10 +94,28 @@ pub fn generate_reserve_price_proof(
     // ═══════════════════════════════════════════════════════════════════════
     println!("🔐 Step 1/7: Hashing gas fee data...");
 
-    let inputs_felt = get_5760_avg_base_fees_felt(); // Convert to Starknet field elements
-    let (hashing_receipt, hashing_res) = hash_felts(HashingFeltInput {
-        inputs: inputs_felt,
+    // The 8 months of data end with the 3-month analysis period
+    let data_8_months_start_timestamp = start_timestamp - (3600 * 24 * 30 * 5);
+
+    // Hourly AvgFees records (timestamp, blocks averaged, fee as a Starknet field element)
+    let inputs: Vec<AvgFees> = get_5760_avg_fees(data_8_months_start_timestamp as u64)
+        .into_iter()
+        .map(|(timestamp, data_points, avg_fee)| AvgFees {
+            timestamp,
+            data_points,
+            avg_fee,
+        })
+        .collect();
+    let (hashing_receipt, hashing_res) = hash_avg_fees(HashingAvgFeesInput {
+        inputs: inputs.clone(),
+        hash_config: HashConfig::default(),
+        aggregation: None,
     });
+    // The hashing journal only commits a digest of the decoded values
+    let hashed_data_8_months = inputs
+        .iter()
+        .map(|x| convert_felt_to_f64(x.avg_fee))
+        .collect::<Vec<f64>>();
 
     println!(
         "   ✓ Data hash: {:?}",
@@ -106,18 +128,18 @@ pub fn generate_reserve_price_proof(
     println!("📈 Step 2/7: Calculating maximum return (volatility)...");
 
     let (max_return_receipt, max_return_res) = max_return(MaxReturnInput {
-        data: hashing_res.f64_inputs.clone(),
+        data: hashed_data_8_months.clone(),
     });
 
-    println!("   ✓ Maximum return: {:.4}%", max_return_res.1 * 100.0);
+    println!("   ✓ Maximum return: {:.4}%", max_return_res.max_return * 100.0);
 
     // ═══════════════════════════════════════════════════════════════════════
     // STEP 3: Extract 3-month subset for reserve price calculation
     // ═══════════════════════════════════════════════════════════════════════
     println!("📅 Extracting 3-month data subset...");
 
-    let data_3_months = hashing_res.f64_inputs[hashing_res.f64_inputs.len().saturating_sub(2160)..]
-        .to_vec();
+    let data_3_months =
+        hashed_data_8_months[hashed_data_8_months.len().saturating_sub(2160)..].to_vec();
 
     println!(
         "   ✓ Using {} data points (last 90 days)",
@@ -144,11 +166,17 @@ pub fn generate_reserve_price_proof(
     // ═══════════════════════════════════════════════════════════════════════
     println!("🎲 Calculating reserve price (this may take 1-2 minutes)...");
 
-    let n_periods = 720; // 720 three-hour periods in 90 days
+    // 30-day round from the end of the data, settled on the TWAP of its last 7 days
+    let round_start_timestamp = end_timestamp;
+    let round_end_timestamp = end_timestamp + 3600 * 24 * 30;
+    let settlement = SettlementSpec::default();
+    // one simulated period per hour of the round
+    let horizon = round_horizon(round_start_timestamp, round_end_timestamp, &settlement).unwrap();
+    let n_periods = horizon.n_periods;
     let data_with_timestamps =
         convert_data_to_vec_of_tuples(data_3_months.clone(), start_timestamp);
 
-    let res = original::calculate_reserve_price(&data_with_timestamps, strike_price, n_periods);
+    let res = original::calculate_reserve_price(&data_with_timestamps, strike_price, &horizon);
 
     println!("   ✓ Reserve price: {:.2} gwei", res.reserve_price);
     println!(
@@ -225,7 +253,11 @@ pub fn generate_reserve_price_proof(
     let (simulate_price_receipt, _) = simulate_price_verify_position(SimulatePriceVerifyPositionInput {
         start_timestamp,
         end_timestamp,
+        round_start_timestamp,
+        round_end_timestamp,
+        settlement,
         data_length: data_3_months.len(),
+        fee_model: floating_point::FeeModelKind::MeanRevertingJump,
         positions: res.positions.clone(),
         pt: convert_array1_to_dvec(res.pt.clone()),
         pt_1: convert_array1_to_dvec(res.pt_1.clone()),
@@ -239,7 +271,9 @@ pub fn generate_reserve_price_proof(
         twap_7d: res.twap_7d.clone(),
         slope: res.slope,
         intercept: res.intercept,
+        payoff: floating_point::PayoffKind::default(),
         reserve_price: res.reserve_price,
+        reserve_price_grid: None,
         tolerance: reserve_price_tolerance,
     });
 
@@ -250,11 +284,14 @@ pub fn generate_reserve_price_proof(
     // ═══════════════════════════════════════════════════════════════════════
     println!("🔗 Composing final proof input...");
 
-    let data_8_months_start_timestamp = start_timestamp - (3600 * 24 * 30 * 5);
-
     let input = ProofCompositionInput {
         data_8_months_hash: hashing_res.hash,
-        data_8_months: hashing_res.f64_inputs,
+        data_8_months_hash_config: hashing_res.hash_config,
+        data_8_months_hashing: DataHashingProof::Full {
+            merkle_root: hashing_res.merkle_root,
+        },
+        data_8_months_block_range: hashing_res.block_range,
+        data_8_months: hashed_data_8_months,
         data_8_months_start_timestamp,
         data_8_months_end_timestamp: end_timestamp,
         start_timestamp,
@@ -265,6 +302,10 @@ pub fn generate_reserve_price_proof(
         reserve_price_end_timestamp: end_timestamp,
         max_return_start_timestamp: data_8_months_start_timestamp,
         max_return_end_timestamp: end_timestamp,
+        round_start_timestamp,
+        round_end_timestamp,
+        settlement,
+        fee_model: floating_point::FeeModelKind::MeanRevertingJump,
         positions: res.positions,
         pt: convert_array1_to_dvec(res.pt),
         pt_1: convert_array1_to_dvec(res.pt_1),
@@ -272,18 +313,26 @@ pub fn generate_reserve_price_proof(
         de_seasonalised_detrended_log_base_fee: convert_array1_to_dvec(
             res.de_seasonalised_detrended_log_base_fee,
         ),
-        n_periods,
         num_paths,
         season_param: convert_array1_to_dvec(res.season_param),
         twap_7d: res.twap_7d,
         slope: res.slope,
         intercept: res.intercept,
+        payoff: floating_point::PayoffKind::default(),
         reserve_price: res.reserve_price,
+        reserve_price_grid: None,
         floating_point_tolerance,
         reserve_price_tolerance,
         twap_tolerance: 1.0,
         twap_result: twap_original,
-        max_return: max_return_res.1,
+        // the example proves the arithmetic mean of the hourly fees
+        twap_kind: TwapKind::Arithmetic,
+        twap_aggregation: None,
+        geometric_twap_result: None,
+        rolling_median: None,
+        ema: None,
+        max_return: max_return_res.max_return,
+        tail_risk: None,
     };
 
     // ═══════════════════════════════════════════════════════════════════════
@@ -350,7 +399,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("   ✓ Loaded {} hours of gas price data", data_8_months_f64.len());
 
     // Define time periods
-    let end_timestamp = chrono::Utc::now().timestamp();
+    // The proof composition guest only accepts windows aligned to hour boundaries
+    let end_timestamp = chrono::Utc::now().timestamp() / 3600 * 3600;
     let start_timestamp = end_timestamp - (90 * 24 * 3600); // 90 days ago
 
     println!("   ✓ Analysis period: 90 days");
@@ -1,12 +1,12 @@
 use add_twap_7d_error_bound_floating_methods::ADD_TWAP_7D_ERROR_BOUND_FLOATING_GUEST_ELF;
-use core::AddTwap7dErrorBoundFloatingInput;
+use core::{AddTwap7dErrorBoundFloatingInput, AddTwap7dErrorBoundFloatingOutput};
 use risc0_zkvm::{default_prover, ExecutorEnv, Receipt};
 use std::thread;
 use std::time::Duration;
 
 pub fn add_twap_7d_error_bound(
     input: AddTwap7dErrorBoundFloatingInput,
-) -> (Receipt, AddTwap7dErrorBoundFloatingInput) {
+) -> (Receipt, AddTwap7dErrorBoundFloatingOutput) {
     let prover = default_prover();
 
     const MAX_RETRIES: u32 = 10;
@@ -28,7 +28,7 @@ pub fn add_twap_7d_error_bound(
         match prover.prove(env, ADD_TWAP_7D_ERROR_BOUND_FLOATING_GUEST_ELF) {
             Ok(prove_info) => {
                 let receipt = prove_info.receipt;
-                let res: AddTwap7dErrorBoundFloatingInput = receipt.journal.decode().unwrap();
+                let res: AddTwap7dErrorBoundFloatingOutput = receipt.journal.decode().unwrap();
                 eprintln!(
                     "add_twap_7d_error_bound: Proof generation succeeded on attempt {}",
                     attempt
@@ -1,6 +1,10 @@
 use add_twap_7d_error_bound_floating::add_twap_7d_error_bound;
 use add_twap_7d_error_bound_floating_methods::ADD_TWAP_7D_ERROR_BOUND_FLOATING_GUEST_ID;
-use common::{original, tests::mock::get_first_period_data};
+use common::{
+    original,
+    tests::mock::get_first_period_data,
+    window::{round_horizon_from_hours, SettlementSpec},
+};
 use core::AddTwap7dErrorBoundFloatingInput;
 
 fn main() {
@@ -8,7 +12,8 @@ fn main() {
     let data = get_first_period_data();
     // run rust code in host
     // ensure convergence in host
-    let res = original::calculate_reserve_price(&data, 15000, 720);
+    let horizon = round_horizon_from_hours(720, &SettlementSpec::default()).unwrap();
+    let res = original::calculate_reserve_price(&data, 15000, &horizon);
 
     let input = AddTwap7dErrorBoundFloatingInput {
         data: data.iter().map(|x| x.1).collect(),
@@ -0,0 +1,45 @@
+use avg_fees_range::prove_avg_fees_range;
+use avg_fees_range_methods::AVG_FEES_RANGE_GUEST_ID;
+use common::{convert_hash_to_u32_array, tests::mock::get_5760_avg_fees, AvgFeesMerkleTree};
+use core::{AvgFees, AvgFeesRangeInput};
+
+fn main() {
+    // 8 months of hourly records ending on February 25, 2024 + 90 days
+    let start_timestamp = 1708833600 - (3600 * 24 * 30 * 5);
+    let records = get_5760_avg_fees(start_timestamp);
+
+    // the root is what gets stored on-chain, built once over the whole dataset
+    let tree = AvgFeesMerkleTree::new(&records).unwrap();
+
+    // prove only the last 30 days, e.g. for a TWAP
+    let window = records.len() - 24 * 30..records.len();
+    let siblings = tree.prove_range(window.clone()).unwrap();
+
+    let input = AvgFeesRangeInput {
+        merkle_root: convert_hash_to_u32_array(&tree.root()),
+        leaf_count: tree.leaf_count() as u64,
+        start_index: window.start as u64,
+        inputs: records[window]
+            .iter()
+            .map(|(timestamp, data_points, avg_fee)| AvgFees {
+                timestamp: *timestamp,
+                data_points: *data_points,
+                avg_fee: *avg_fee,
+            })
+            .collect(),
+        siblings: siblings
+            .iter()
+            .map(|sibling| convert_hash_to_u32_array(sibling))
+            .collect(),
+    };
+
+    let (receipt, res) = prove_avg_fees_range(input);
+
+    receipt.verify(AVG_FEES_RANGE_GUEST_ID).unwrap();
+
+    println!("merkle_root: {:?}", res.merkle_root);
+    println!(
+        "first_timestamp: {}, last_timestamp: {}",
+        res.first_timestamp, res.last_timestamp
+    );
+}
@@ -0,0 +1,150 @@
+use common::{
+    backtest::{run_backtest, summarize_backtest, BacktestConfig},
+    common::csv::{close_csv_file, open_backtest_csv_writer, write_backtest_round_to_csv},
+    convert_felt_to_f64,
+    floating_point::FeeModelKind,
+    tests::mock::get_5760_avg_base_fees_felt,
+    SECONDS_IN_HOUR,
+};
+use eyre::{anyhow as err, Result};
+use std::{env, process};
+
+// the 8 months of hourly fees of the mock data start 5 months before the POC round
+const MOCK_START_TIMESTAMP: i64 = 1708833600 - 3600 * 24 * 30 * 5;
+const DEFAULT_OUTPUT: &str = "backtest.csv";
+const USAGE: &str = "usage: backtest [--data <fees.csv>] [--output <backtest.csv>] \
+[--history-hours <usize>] [--round-hours <usize>] [--step-hours <usize>] \
+[--paths <usize>] [--model mrj|regime-switching|garch] [--quantiles <f64,...>] \
+[--pit-bins <usize>]";
+
+fn main() {
+    if let Err(e) = run(env::args().skip(1).collect()) {
+        eprintln!("backtest failed: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run(args: Vec<String>) -> Result<()> {
+    let mut data_path = None;
+    let mut output_path = DEFAULT_OUTPUT.to_owned();
+    let mut config = BacktestConfig::default();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let value = iter
+            .next()
+            .ok_or_else(|| err!("missing value for '{}'\n{}", arg, USAGE))?;
+        match arg.as_str() {
+            "--data" => data_path = Some(value.clone()),
+            "--output" => output_path = value.clone(),
+            "--history-hours" => config.history_hours = parse_value(arg, value)?,
+            "--round-hours" => config.round_hours = parse_value(arg, value)?,
+            "--step-hours" => config.step_hours = parse_value(arg, value)?,
+            "--paths" => config.num_paths = parse_value(arg, value)?,
+            "--model" => config.fee_model = parse_fee_model(value)?,
+            "--quantiles" => {
+                config.quantile_levels = value
+                    .split(',')
+                    .map(|level| parse_value(arg, level.trim()))
+                    .collect::<Result<_>>()?
+            }
+            "--pit-bins" => config.pit_bins = parse_value(arg, value)?,
+            _ => return Err(err!("unknown option '{}'\n{}", arg, USAGE)),
+        }
+    }
+
+    let (start_timestamp, fees) = match data_path {
+        Some(path) => read_hourly_fees(&path)?,
+        None => (
+            MOCK_START_TIMESTAMP,
+            get_5760_avg_base_fees_felt()
+                .iter()
+                .map(|x| convert_felt_to_f64(*x))
+                .collect(),
+        ),
+    };
+
+    let rounds = run_backtest(&fees, start_timestamp, &config)?;
+
+    let mut wtr = open_backtest_csv_writer(&output_path, &config.quantile_levels);
+    for round in &rounds {
+        write_backtest_round_to_csv(&mut wtr, round);
+    }
+    close_csv_file(&mut wtr);
+    println!("wrote {} rounds to {}", rounds.len(), output_path);
+
+    let summary = summarize_backtest(&rounds, config.pit_bins)?;
+    println!("total_premium: {}", summary.total_premium);
+    println!("total_payout: {}", summary.total_payout);
+    println!("total_pnl: {}", summary.total_pnl);
+    println!("mean_pnl: {}", summary.mean_pnl);
+    println!("worst_pnl: {}", summary.worst_pnl);
+    println!("hit_rate: {}", summary.hit_rate);
+    println!(
+        "mean_payout_probability: {}",
+        summary.mean_payout_probability
+    );
+    println!("brier_score: {}", summary.brier_score);
+    println!("payout_ratio: {}", summary.payout_ratio);
+    println!("mean_crps: {}", summary.forecast.mean_crps);
+    for (level, loss) in summary
+        .forecast
+        .quantile_levels
+        .iter()
+        .zip(&summary.forecast.mean_pinball_losses)
+    {
+        println!("mean_pinball_q{}: {}", level, loss);
+    }
+    println!("pit_histogram: {:?}", summary.forecast.pit_histogram);
+
+    Ok(())
+}
+
+// `timestamp,base_fee` rows, one per consecutive hour, after a header
+fn read_hourly_fees(file_name: &str) -> Result<(i64, Vec<f64>)> {
+    let mut rdr = csv::Reader::from_path(file_name)
+        .map_err(|e| err!("Cannot open fee file '{}': {}", file_name, e))?;
+
+    let mut rows = vec![];
+    for record in rdr.records() {
+        let record = record?;
+        let timestamp = parse_value::<i64>("timestamp", record.get(0).unwrap_or_default())?;
+        let base_fee = parse_value::<f64>("base_fee", record.get(1).unwrap_or_default())?;
+        rows.push((timestamp, base_fee));
+    }
+
+    let start_timestamp = rows
+        .first()
+        .map(|row| row.0)
+        .ok_or_else(|| err!("No fees in '{}'", file_name))?;
+    if let Some(pair) = rows
+        .windows(2)
+        .find(|pair| pair[1].0 - pair[0].0 != SECONDS_IN_HOUR)
+    {
+        return Err(err!(
+            "Fees must be hourly, got {} after {}",
+            pair[1].0,
+            pair[0].0
+        ));
+    }
+
+    Ok((start_timestamp, rows.into_iter().map(|row| row.1).collect()))
+}
+
+fn parse_fee_model(value: &str) -> Result<FeeModelKind> {
+    match value {
+        "mrj" => Ok(FeeModelKind::MeanRevertingJump),
+        "regime-switching" => Ok(FeeModelKind::RegimeSwitching),
+        "garch" => Ok(FeeModelKind::Garch),
+        _ => Err(err!("unknown fee model '{}'\n{}", value, USAGE)),
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(name: &str, value: &str) -> Result<T>
+where
+    T::Err: std::fmt::Display,
+{
+    value
+        .parse()
+        .map_err(|e| err!("invalid value '{}' for '{}': {}", value, name, e))
+}
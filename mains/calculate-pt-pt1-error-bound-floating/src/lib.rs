@@ -1,12 +1,12 @@
 use calculate_pt_pt1_error_bound_floating_methods::CALCULATE_PT_PT1_ERROR_BOUND_FLOATING_GUEST_ELF;
-use core::CalculatePtPt1ErrorBoundFloatingInput;
+use core::{CalculatePtPt1ErrorBoundFloatingInput, CalculatePtPt1ErrorBoundFloatingOutput};
 use risc0_zkvm::{default_prover, ExecutorEnv, Receipt};
 use std::thread;
 use std::time::Duration;
 
 pub fn calculate_pt_pt1_error_bound_floating(
     input: CalculatePtPt1ErrorBoundFloatingInput,
-) -> (Receipt, CalculatePtPt1ErrorBoundFloatingInput) {
+) -> (Receipt, CalculatePtPt1ErrorBoundFloatingOutput) {
     let prover = default_prover();
 
     const MAX_RETRIES: u32 = 10;
@@ -28,7 +28,7 @@ pub fn calculate_pt_pt1_error_bound_floating(
         match prover.prove(env, CALCULATE_PT_PT1_ERROR_BOUND_FLOATING_GUEST_ELF) {
             Ok(prove_info) => {
                 let receipt = prove_info.receipt;
-                let res: CalculatePtPt1ErrorBoundFloatingInput = receipt.journal.decode().unwrap();
+                let res: CalculatePtPt1ErrorBoundFloatingOutput = receipt.journal.decode().unwrap();
                 eprintln!(
                     "calculate_pt_pt1_error_bound_floating: Proof generation succeeded on attempt {}",
                     attempt
@@ -3,6 +3,7 @@ use calculate_pt_pt1_error_bound_floating_methods::CALCULATE_PT_PT1_ERROR_BOUND_
 use common::{
     original::{self, convert_array1_to_dvec},
     tests::mock::get_first_period_data,
+    window::{round_horizon_from_hours, SettlementSpec},
 };
 use core::CalculatePtPt1ErrorBoundFloatingInput;
 
@@ -11,7 +12,8 @@ fn main() {
     let data = get_first_period_data();
     // run rust code in host
     // ensure convergence in host
-    let res = original::calculate_reserve_price(&data, 15000, 720);
+    let horizon = round_horizon_from_hours(720, &SettlementSpec::default()).unwrap();
+    let res = original::calculate_reserve_price(&data, 15000, &horizon);
 
     let input = CalculatePtPt1ErrorBoundFloatingInput {
         de_seasonalised_detrended_log_base_fee: convert_array1_to_dvec(
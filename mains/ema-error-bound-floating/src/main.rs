@@ -0,0 +1,29 @@
+use common::{floating_point::calculate_ema, tests::mock::get_first_period_data};
+use core::EmaErrorBoundInput;
+use ema_error_bound_floating::calculate_ema as prove_ema;
+use ema_error_bound_floating_methods::EMA_ERROR_BOUND_FLOATING_GUEST_ID;
+
+fn main() {
+    let avg_hourly_gas_fee = get_first_period_data()
+        .iter()
+        .map(|x| x.1)
+        .collect::<Vec<f64>>();
+
+    // smoothing factor of a 7 day EMA
+    let alpha = 2.0 / (24.0 * 7.0 + 1.0);
+    let ema = calculate_ema(&avg_hourly_gas_fee, alpha).unwrap();
+    let ema_result = ema[ema.len() - 1];
+
+    println!("ema: {:?}", ema_result);
+
+    let input = EmaErrorBoundInput {
+        avg_hourly_gas_fee,
+        alpha,
+        tolerance: 1.0,
+        ema_result,
+    };
+
+    let (receipt, _res) = prove_ema(input);
+
+    receipt.verify(EMA_ERROR_BOUND_FLOATING_GUEST_ID).unwrap();
+}
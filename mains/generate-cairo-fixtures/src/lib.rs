@@ -0,0 +1,134 @@
+use core::ProofCompositionOutput;
+use eyre::{anyhow as err, Result};
+use risc0_zkvm::{
+    sha::{Digest, Digestible},
+    Receipt,
+};
+
+/// Reads the image ID out of the receipt claim, so fixtures generated from a saved
+/// receipt always match the guest that actually produced it.
+pub fn receipt_image_id(receipt: &Receipt) -> Result<Digest> {
+    let claim = receipt
+        .claim()
+        .map_err(|e| err!("Cannot read the receipt claim: {}", e))?;
+    let claim = claim
+        .as_value()
+        .map_err(|_| err!("The receipt claim is pruned, the image ID is not available"))?;
+
+    Ok(claim.pre.digest())
+}
+
+/// Renders `pitchlake_verifier/src/fixtures.cairo` from a Groth16 receipt and its calldata.
+pub fn render_fixtures(receipt: &Receipt, image_id: Digest, calldata: &[String]) -> Result<String> {
+    let journal: ProofCompositionOutput = receipt
+        .journal
+        .decode()
+        .map_err(|e| err!("Journal is not a ProofCompositionOutput: {}", e))?;
+
+    let mut out = String::new();
+    out.push_str("// Generated by `cargo run -p generate-cairo-fixtures`, do not edit by hand.\n");
+    out.push_str("use pitchlake_verifier::Journal;\n\n");
+
+    out.push_str(&format!(
+        "pub const IMAGE_ID: [u32; 8] = [{}];\n\n",
+        join_words(image_id.as_words())
+    ));
+    out.push_str(&format!(
+        "pub const IMAGE_ID_DIGEST: u256 = 0x{};\n\n",
+        image_id
+    ));
+
+    // Fields in the order `decode_journal` reads them, which is the order of
+    // `ProofCompositionOutput`
+    out.push_str("pub const JOURNAL_DECODED: Journal = Journal {\n");
+    out.push_str(&format!(
+        "    data_8_months_hash: [\n        {},\n    ],\n",
+        join_words(&journal.data_8_months_hash)
+    ));
+    for (name, value) in [
+        ("start_timestamp", journal.start_timestamp.to_string()),
+        ("end_timestamp", journal.end_timestamp.to_string()),
+        (
+            "reserve_price_start_timestamp",
+            journal.reserve_price_start_timestamp.to_string(),
+        ),
+        (
+            "reserve_price_end_timestamp",
+            journal.reserve_price_end_timestamp.to_string(),
+        ),
+        ("reserve_price", journal.reserve_price),
+        (
+            "twap_start_timestamp",
+            journal.twap_start_timestamp.to_string(),
+        ),
+        ("twap_end_timestamp", journal.twap_end_timestamp.to_string()),
+        ("twap_result", journal.twap_result),
+        (
+            "max_return_start_timestamp",
+            journal.max_return_start_timestamp.to_string(),
+        ),
+        (
+            "max_return_end_timestamp",
+            journal.max_return_end_timestamp.to_string(),
+        ),
+        ("max_return", journal.max_return),
+        ("floating_point_tolerance", journal.floating_point_tolerance),
+        ("reserve_price_tolerance", journal.reserve_price_tolerance),
+        ("twap_tolerance", journal.twap_tolerance),
+        ("gradient_tolerance", journal.gradient_tolerance),
+        (
+            "round_start_timestamp",
+            journal.round_start_timestamp.to_string(),
+        ),
+        (
+            "round_end_timestamp",
+            journal.round_end_timestamp.to_string(),
+        ),
+        (
+            "settlement_twap_window_hours",
+            journal.settlement.twap_window_hours.to_string(),
+        ),
+    ] {
+        out.push_str(&format!("    {}: {},\n", name, value));
+    }
+    out.push_str(&match &journal.data_8_months_merkle_root {
+        Some(merkle_root) => format!(
+            "    data_8_months_merkle_root: Option::Some(\n        [{}],\n    ),\n",
+            join_words(merkle_root)
+        ),
+        None => "    data_8_months_merkle_root: Option::None,\n".to_string(),
+    });
+    out.push_str("};\n\n");
+
+    let journal_bytes: Vec<String> = receipt
+        .journal
+        .bytes
+        .iter()
+        .map(|b| b.to_string())
+        .collect();
+    out.push_str(&render_array_fn("JOURNAL_BYTES", "u8", &journal_bytes));
+    out.push('\n');
+    out.push_str(&render_array_fn("PROOF_CALLDATA", "felt252", calldata));
+
+    Ok(out)
+}
+
+fn join_words(words: &[u32]) -> String {
+    words
+        .iter()
+        .map(|word| word.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn render_array_fn(name: &str, item_type: &str, items: &[String]) -> String {
+    let mut out = format!(
+        "pub fn {}() -> Array<{}> {{\n    array![\n",
+        name, item_type
+    );
+    for item in items {
+        out.push_str(&format!("        {},\n", item));
+    }
+    out.push_str("    ]\n}\n");
+    out
+}
@@ -0,0 +1,76 @@
+use eyre::{anyhow as err, Result};
+use generate_cairo_fixtures::{receipt_image_id, render_fixtures};
+use mock_proof_composition::{
+    groth16_calldata, mock_proof_composition_input, prove_mock_proof_composition,
+};
+use mock_proof_composition_methods::MOCK_PROOF_COMPOSITION_GUEST_ELF;
+use risc0_zkvm::compute_image_id;
+use std::{env, fs, process};
+use verify_proof_composition::load_receipt;
+
+const DEFAULT_OUTPUT: &str = "pitchlake_verifier/src/fixtures.cairo";
+const USAGE: &str =
+    "usage: generate-cairo-fixtures [--receipt <receipt.bin>] [--output <fixtures.cairo>]";
+
+fn main() {
+    dotenv::dotenv().ok();
+
+    if let Err(e) = run(env::args().skip(1).collect()) {
+        eprintln!("fixture generation failed: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run(args: Vec<String>) -> Result<()> {
+    let mut receipt_path = None;
+    let mut output_path = DEFAULT_OUTPUT.to_owned();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let value = iter
+            .next()
+            .ok_or_else(|| err!("missing value for '{}'\n{}", arg, USAGE))?;
+        match arg.as_str() {
+            "--receipt" => receipt_path = Some(value.clone()),
+            "--output" => output_path = value.clone(),
+            _ => return Err(err!("unknown option '{}'\n{}", arg, USAGE)),
+        }
+    }
+
+    // Without a saved receipt, the mock composition is proven with Groth16 so the
+    // fixtures follow whatever the mock guest currently commits.
+    let (receipt, image_id) = match receipt_path {
+        Some(path) => {
+            let receipt = load_receipt(&path)?;
+            let image_id = receipt_image_id(&receipt)?;
+            (receipt, image_id)
+        }
+        None => {
+            // IMAGE_ID must be the one pinned in `image_ids.json`, which only the docker
+            // build reproduces
+            if env::var_os("RISC0_USE_DOCKER").is_none() {
+                return Err(err!(
+                    "RISC0_USE_DOCKER is not set (the image ID of a local toolchain build does not match the docker build): run `RISC0_USE_DOCKER=1 cargo run -p generate-cairo-fixtures`"
+                ));
+            }
+            let receipt = prove_mock_proof_composition(&mock_proof_composition_input());
+            let image_id = compute_image_id(MOCK_PROOF_COMPOSITION_GUEST_ELF)
+                .map_err(|e| err!("Cannot compute the mock image ID: {}", e))?;
+            (receipt, image_id)
+        }
+    };
+
+    receipt
+        .inner
+        .groth16()
+        .map_err(|_| err!("Calldata can only be generated from a Groth16 receipt"))?;
+
+    let calldata = groth16_calldata(&receipt, image_id);
+    let fixtures = render_fixtures(&receipt, image_id, &calldata)?;
+
+    fs::write(&output_path, fixtures)
+        .map_err(|e| err!("Cannot write fixtures to '{}': {}", output_path, e))?;
+    println!("wrote {} (image ID {})", output_path, image_id);
+
+    Ok(())
+}
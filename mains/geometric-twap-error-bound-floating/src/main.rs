@@ -0,0 +1,26 @@
+use common::{floating_point::calculate_geometric_twap, tests::mock::get_first_period_data};
+use core::GeometricTwapErrorBoundInput;
+use geometric_twap_error_bound_floating::calculate_geometric_twap as prove_geometric_twap;
+use geometric_twap_error_bound_floating_methods::GEOMETRIC_TWAP_ERROR_BOUND_FLOATING_GUEST_ID;
+
+fn main() {
+    let avg_hourly_gas_fee = get_first_period_data()
+        .iter()
+        .map(|x| x.1)
+        .collect::<Vec<f64>>();
+    let geometric_twap_result = calculate_geometric_twap(&avg_hourly_gas_fee).unwrap();
+
+    println!("geometric twap: {:?}", geometric_twap_result);
+
+    let input = GeometricTwapErrorBoundInput {
+        avg_hourly_gas_fee,
+        tolerance: 1.0,
+        geometric_twap_result,
+    };
+
+    let (receipt, _res) = prove_geometric_twap(input);
+
+    receipt
+        .verify(GEOMETRIC_TWAP_ERROR_BOUND_FLOATING_GUEST_ID)
+        .unwrap();
+}
@@ -0,0 +1,65 @@
+use core::{GreeksInput, GreeksOutput};
+use greeks_floating_methods::GREEKS_FLOATING_GUEST_ELF;
+use risc0_zkvm::{default_prover, ExecutorEnv, Receipt};
+use std::thread;
+use std::time::Duration;
+
+pub fn greeks(input: GreeksInput) -> (Receipt, GreeksOutput) {
+    eprintln!(
+        "greeks: Received {} positions to simulate {} paths of the round [{}, {}) for the Greeks",
+        input.positions.len(),
+        input.num_paths,
+        input.round_start_timestamp,
+        input.round_end_timestamp
+    );
+
+    let prover = default_prover();
+
+    const MAX_RETRIES: u32 = 10;
+    const INITIAL_DELAY_MS: u64 = 5000;
+
+    let mut last_error = None;
+    for attempt in 1..=MAX_RETRIES {
+        eprintln!(
+            "greeks: Proof generation attempt {}/{}",
+            attempt, MAX_RETRIES
+        );
+
+        let env = ExecutorEnv::builder()
+            .write(&input)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        match prover.prove(env, GREEKS_FLOATING_GUEST_ELF) {
+            Ok(prove_info) => {
+                let receipt = prove_info.receipt;
+                let res: GreeksOutput = receipt.journal.decode().unwrap();
+                eprintln!("greeks: Proof generation succeeded on attempt {}", attempt);
+                return (receipt, res);
+            }
+            Err(e) => {
+                eprintln!("greeks: Attempt {}/{} failed: {}", attempt, MAX_RETRIES, e);
+
+                last_error = Some(e);
+
+                if attempt == MAX_RETRIES {
+                    // Final attempt - fail
+                    break;
+                }
+
+                // Exponential backoff: 5s, 10s, 20s, 40s, etc.
+                let delay = INITIAL_DELAY_MS * 2u64.pow(attempt - 1);
+                eprintln!("greeks: Retrying in {}ms...", delay);
+                thread::sleep(Duration::from_millis(delay));
+            }
+        }
+    }
+
+    // All retries failed
+    panic!(
+        "greeks: Failed after {} attempts. Last error: {:?}",
+        MAX_RETRIES,
+        last_error.unwrap()
+    );
+}
@@ -0,0 +1,94 @@
+use common::{
+    convert_felt_to_f64,
+    floating_point::{
+        add_twap_7d, calculate_greeks, calculate_remove_seasonality, simulate_price_with_model,
+        FeeModelKind, PayoffKind,
+    },
+    tests::mock::get_5760_avg_base_fees_felt,
+    window::{round_horizon, SettlementSpec},
+};
+use core::GreeksInput;
+use greeks_floating::greeks;
+use greeks_floating_methods::GREEKS_FLOATING_GUEST_ID;
+
+fn main() {
+    let data_8_months = get_5760_avg_base_fees_felt()
+        .iter()
+        .map(|x| convert_felt_to_f64(*x))
+        .collect::<Vec<f64>>();
+    let data = data_8_months[data_8_months.len().saturating_sub(2160)..].to_vec();
+
+    let start_timestamp = 1708833600;
+    let end_timestamp = 1708833600 + (3600 * 24 * 30 * 3); // as long as start to end timestamp is 90 days
+
+    // a 30-day round starting when the data ends, settled on its last 7 days
+    let round_start_timestamp = end_timestamp;
+    let round_end_timestamp = end_timestamp + 3600 * 24 * 30;
+    let settlement = SettlementSpec::default();
+    let horizon = round_horizon(round_start_timestamp, round_end_timestamp, &settlement).unwrap();
+
+    // fewer paths than the reserve price, as the guest reprices them several times
+    let num_paths = 1000;
+    let twap_7d = add_twap_7d(&data).unwrap();
+    let (slope, intercept, de_seasonalised_detrended_log_base_fee, season_param) =
+        calculate_remove_seasonality(&data).unwrap();
+    let fee_model = FeeModelKind::default();
+    // the guest simulates the fitted model itself, so only the positions are kept
+    let (_simulated_prices, positions) = simulate_price_with_model(
+        fee_model,
+        &de_seasonalised_detrended_log_base_fee,
+        num_paths,
+        horizon.n_periods,
+    )
+    .unwrap();
+
+    let input = GreeksInput {
+        start_timestamp,
+        end_timestamp,
+        season_param,
+        fee_model,
+        positions,
+        gradient_tolerance: 5e-2,
+        de_seasonalised_detrended_log_base_fee,
+        round_start_timestamp,
+        round_end_timestamp,
+        settlement,
+        num_paths,
+        twap_7d,
+        slope,
+        intercept,
+        data_length: data.len(),
+        payoff: PayoffKind::default(),
+        seed: 42,
+    };
+
+    // run rust code in host
+    let host_greeks = calculate_greeks(
+        input.start_timestamp,
+        input.end_timestamp,
+        &input.season_param,
+        input.fee_model,
+        &input.positions,
+        &input.de_seasonalised_detrended_log_base_fee,
+        &horizon,
+        input.num_paths,
+        &input.twap_7d,
+        input.slope,
+        input.intercept,
+        input.data_length,
+        &input.payoff,
+        input.seed,
+    )
+    .unwrap();
+    println!("host greeks: {:?}", host_greeks);
+
+    let (receipt, res) = greeks(input);
+
+    receipt.verify(GREEKS_FLOATING_GUEST_ID).unwrap();
+    println!("reserve_price: {:?}", res.reserve_price);
+    println!("dual_delta: {:?}", res.dual_delta);
+    println!("vega: {:?}", res.vega);
+    println!("theta: {:?}", res.theta);
+    println!("sigma_vega: {:?}", res.sigma_vega);
+    println!("sigma_j_vega: {:?}", res.sigma_j_vega);
+}
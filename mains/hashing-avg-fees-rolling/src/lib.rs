@@ -0,0 +1,69 @@
+use hashing_avg_fees_rolling_methods::HASHING_AVG_FEES_ROLLING_GUEST_ELF;
+use risc0_zkvm::{default_prover, ExecutorEnv, Receipt};
+use std::thread;
+use std::time::Duration;
+
+use core::{HashingAvgFeesRollingInput, HashingAvgFeesRollingOutput};
+
+/// Proves one rolling round. `previous_receipt` is the receipt whose journal is `input.previous`,
+/// added as an assumption for the guest to verify.
+pub fn hash_avg_fees_rolling(
+    input: HashingAvgFeesRollingInput,
+    previous_receipt: Option<Receipt>,
+) -> (Receipt, HashingAvgFeesRollingOutput) {
+    let prover = default_prover();
+
+    const MAX_RETRIES: u32 = 10;
+    const INITIAL_DELAY_MS: u64 = 5000;
+
+    let mut last_error = None;
+    for attempt in 1..=MAX_RETRIES {
+        eprintln!(
+            "hash_avg_fees_rolling: Proof generation attempt {}/{}",
+            attempt, MAX_RETRIES
+        );
+
+        let mut builder = ExecutorEnv::builder();
+        if let Some(previous_receipt) = &previous_receipt {
+            builder.add_assumption(previous_receipt.clone());
+        }
+        let env = builder.write(&input).unwrap().build().unwrap();
+
+        match prover.prove(env, HASHING_AVG_FEES_ROLLING_GUEST_ELF) {
+            Ok(prove_info) => {
+                let receipt = prove_info.receipt;
+                let res: HashingAvgFeesRollingOutput = receipt.journal.decode().unwrap();
+                eprintln!(
+                    "hash_avg_fees_rolling: Proof generation succeeded on attempt {}",
+                    attempt
+                );
+                return (receipt, res);
+            }
+            Err(e) => {
+                eprintln!(
+                    "hash_avg_fees_rolling: Attempt {}/{} failed: {}",
+                    attempt, MAX_RETRIES, e
+                );
+
+                last_error = Some(e);
+
+                if attempt == MAX_RETRIES {
+                    // Final attempt - fail
+                    break;
+                }
+
+                // Exponential backoff: 5s, 10s, 20s, 40s, etc.
+                let delay = INITIAL_DELAY_MS * 2u64.pow(attempt - 1);
+                eprintln!("hash_avg_fees_rolling: Retrying in {}ms...", delay);
+                thread::sleep(Duration::from_millis(delay));
+            }
+        }
+    }
+
+    // All retries failed
+    panic!(
+        "hash_avg_fees_rolling: Failed after {} attempts. Last error: {:?}",
+        MAX_RETRIES,
+        last_error.unwrap()
+    );
+}
@@ -0,0 +1,62 @@
+use common::{tests::mock::get_5760_avg_fees, HashConfig, HashScheme};
+use core::{AvgFees, HashingAvgFeesRollingInput};
+use hashing_avg_fees_rolling::hash_avg_fees_rolling;
+use hashing_avg_fees_rolling_methods::HASHING_AVG_FEES_ROLLING_GUEST_ID;
+
+fn to_avg_fees(records: &[(u64, u64, starknet_core::types::Felt)]) -> Vec<AvgFees> {
+    records
+        .iter()
+        .map(|(timestamp, data_points, avg_fee)| AvgFees {
+            timestamp: *timestamp,
+            data_points: *data_points,
+            avg_fee: *avg_fee,
+        })
+        .collect()
+}
+
+fn main() {
+    // daily batches, so that a daily round only hashes one new batch
+    let hash_config = HashConfig {
+        batch_size: 24,
+        scheme: HashScheme::Sha256,
+    };
+
+    // first round: the full 8 months of hourly records
+    let start_timestamp = 1708833600 - (3600 * 24 * 30 * 5);
+    let window = get_5760_avg_fees(start_timestamp);
+    let (first_receipt, first_res) = hash_avg_fees_rolling(
+        HashingAvgFeesRollingInput {
+            inputs: to_avg_fees(&window),
+            hash_config,
+            previous: None,
+            rolling_image_id: HASHING_AVG_FEES_ROLLING_GUEST_ID,
+        },
+        None,
+    );
+    first_receipt
+        .verify(HASHING_AVG_FEES_ROLLING_GUEST_ID)
+        .unwrap();
+    println!("first round hash: {:?}", first_res.hash);
+
+    // next round: only the following day is hashed
+    let next_day = get_5760_avg_fees(first_res.last_timestamp + 3600)[..24].to_vec();
+    let (receipt, res) = hash_avg_fees_rolling(
+        HashingAvgFeesRollingInput {
+            inputs: to_avg_fees(&next_day),
+            hash_config,
+            previous: Some(first_res),
+            rolling_image_id: HASHING_AVG_FEES_ROLLING_GUEST_ID,
+        },
+        Some(first_receipt),
+    );
+
+    // the image ID committed in the journal is the one the whole chain of rounds was proven with
+    receipt.verify(HASHING_AVG_FEES_ROLLING_GUEST_ID).unwrap();
+    assert_eq!(res.rolling_image_id, HASHING_AVG_FEES_ROLLING_GUEST_ID);
+
+    println!("rolled hash: {:?}", res.hash);
+    println!(
+        "first_timestamp: {}, last_timestamp: {}",
+        res.first_timestamp, res.last_timestamp
+    );
+}
@@ -0,0 +1,33 @@
+use common::{tests::mock::get_5760_avg_fees, HashConfig};
+use core::{AvgFees, HashingAvgFeesInput};
+use hashing_avg_fees::hash_avg_fees;
+use hashing_avg_fees_methods::HASHING_AVG_FEES_GUEST_ID;
+
+fn main() {
+    // 8 months of hourly records ending on February 25, 2024 + 90 days
+    let start_timestamp = 1708833600 - (3600 * 24 * 30 * 5);
+    let inputs = get_5760_avg_fees(start_timestamp)
+        .into_iter()
+        .map(|(timestamp, data_points, avg_fee)| AvgFees {
+            timestamp,
+            data_points,
+            avg_fee,
+        })
+        .collect();
+
+    let input = HashingAvgFeesInput {
+        inputs,
+        hash_config: HashConfig::default(),
+        aggregation: None,
+    };
+
+    let (receipt, res) = hash_avg_fees(input);
+
+    receipt.verify(HASHING_AVG_FEES_GUEST_ID).unwrap();
+
+    println!("hash: {:?}", res.hash);
+    println!(
+        "first_timestamp: {}, last_timestamp: {}",
+        res.first_timestamp, res.last_timestamp
+    );
+}
@@ -1,4 +1,4 @@
-use common::tests::mock::get_5760_avg_base_fees_felt;
+use common::{tests::mock::get_5760_avg_base_fees_felt, HashConfig};
 use core::HashingFeltInput;
 use hashing_felts::hash_felts;
 use hashing_felts_methods::HASHING_FELTS_GUEST_ID;
@@ -14,11 +14,15 @@ fn main() {
     //     .collect::<Vec<f64>>();
     // println!("inputs_f64: {:?}", inputs_f64);
 
-    let input = HashingFeltInput { inputs };
+    let input = HashingFeltInput {
+        inputs,
+        hash_config: HashConfig::default(),
+    };
 
     let (receipt, res) = hash_felts(input);
 
     receipt.verify(HASHING_FELTS_GUEST_ID).unwrap();
 
     println!("hash: {:?}", res.hash);
+    println!("hash_config: {:?}", res.hash_config);
 }
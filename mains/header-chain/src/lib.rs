@@ -0,0 +1,93 @@
+use eyre::{anyhow as err, Result};
+use header_chain_methods::HEADER_CHAIN_GUEST_ELF;
+use risc0_zkvm::{default_prover, ExecutorEnv, Receipt};
+use std::fs;
+use std::thread;
+use std::time::Duration;
+
+use core::{HeaderChainInput, HeaderChainOutput};
+
+pub fn prove_header_chain(input: HeaderChainInput) -> (Receipt, HeaderChainOutput) {
+    let prover = default_prover();
+
+    const MAX_RETRIES: u32 = 10;
+    const INITIAL_DELAY_MS: u64 = 5000;
+
+    let mut last_error = None;
+    for attempt in 1..=MAX_RETRIES {
+        eprintln!(
+            "prove_header_chain: Proof generation attempt {}/{}",
+            attempt, MAX_RETRIES
+        );
+
+        let env = ExecutorEnv::builder()
+            .write(&input)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        match prover.prove(env, HEADER_CHAIN_GUEST_ELF) {
+            Ok(prove_info) => {
+                let receipt = prove_info.receipt;
+                let res: HeaderChainOutput = receipt.journal.decode().unwrap();
+                eprintln!(
+                    "prove_header_chain: Proof generation succeeded on attempt {}",
+                    attempt
+                );
+                return (receipt, res);
+            }
+            Err(e) => {
+                eprintln!(
+                    "prove_header_chain: Attempt {}/{} failed: {}",
+                    attempt, MAX_RETRIES, e
+                );
+
+                last_error = Some(e);
+
+                if attempt == MAX_RETRIES {
+                    // Final attempt - fail
+                    break;
+                }
+
+                // Exponential backoff: 5s, 10s, 20s, 40s, etc.
+                let delay = INITIAL_DELAY_MS * 2u64.pow(attempt - 1);
+                eprintln!("prove_header_chain: Retrying in {}ms...", delay);
+                thread::sleep(Duration::from_millis(delay));
+            }
+        }
+    }
+
+    // All retries failed
+    panic!(
+        "prove_header_chain: Failed after {} attempts. Last error: {:?}",
+        MAX_RETRIES,
+        last_error.unwrap()
+    );
+}
+
+/// Reads RLP-encoded headers from a file with one hex-encoded header per line, oldest first.
+pub fn load_headers(path: &str) -> Result<Vec<Vec<u8>>> {
+    let contents =
+        fs::read_to_string(path).map_err(|e| err!("Failed to read '{}': {}", path, e))?;
+
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(decode_hex)
+        .collect()
+}
+
+pub fn decode_hex(value: &str) -> Result<Vec<u8>> {
+    let stripped = value.trim().trim_start_matches("0x");
+    if stripped.len() % 2 != 0 || !stripped.is_ascii() {
+        return Err(err!("Invalid hex string '{}'", value));
+    }
+
+    (0..stripped.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&stripped[i..i + 2], 16)
+                .map_err(|e| err!("Invalid hex string '{}': {}", value, e))
+        })
+        .collect()
+}
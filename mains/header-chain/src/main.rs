@@ -0,0 +1,46 @@
+use common::convert_hash_to_u32_array;
+use core::HeaderChainInput;
+use eyre::{anyhow as err, Result};
+use header_chain::{decode_hex, load_headers, prove_header_chain};
+use header_chain_methods::HEADER_CHAIN_GUEST_ID;
+use std::{env, process};
+
+const USAGE: &str = "usage: header-chain <headers.hex> <anchor-hash>";
+
+fn main() {
+    if let Err(e) = run(env::args().skip(1).collect()) {
+        eprintln!("header chain proof failed: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run(args: Vec<String>) -> Result<()> {
+    let [headers_path, anchor_hash] = args.as_slice() else {
+        return Err(err!("expected 2 arguments\n{}", USAGE));
+    };
+
+    let headers = load_headers(headers_path)?;
+    let anchor_hash: [u8; 32] = decode_hex(anchor_hash)?
+        .try_into()
+        .map_err(|_| err!("anchor hash must be 32 bytes\n{}", USAGE))?;
+
+    let (receipt, res) = prove_header_chain(HeaderChainInput {
+        headers,
+        anchor_hash: convert_hash_to_u32_array(&anchor_hash),
+    });
+    receipt.verify(HEADER_CHAIN_GUEST_ID)?;
+
+    println!(
+        "verified blocks {} to {}",
+        res.blocks[0].number,
+        res.blocks[res.blocks.len() - 1].number
+    );
+    for block in &res.blocks {
+        println!(
+            "block {}: timestamp {}, base fee {}",
+            block.number, block.timestamp, block.base_fee
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use common::{tests::mock::get_mock_blocks, HashConfig};
+use core::{BlockBaseFee, HashingAvgFeesInput, HourlyAggregationInput};
+use hashing_avg_fees::hash_avg_fees_with_aggregation;
+use hourly_aggregation::aggregate_hourly_fees;
+use hourly_aggregation_methods::HOURLY_AGGREGATION_GUEST_ID;
+
+fn main() {
+    // one day of 12s blocks, plus a bounding block on either side
+    let blocks = get_mock_blocks(1708833600, 24)
+        .into_iter()
+        .map(|(number, timestamp, base_fee)| BlockBaseFee {
+            number,
+            timestamp,
+            base_fee,
+        })
+        .collect();
+
+    let (aggregation_receipt, aggregation_res) =
+        aggregate_hourly_fees(HourlyAggregationInput { blocks });
+    aggregation_receipt
+        .verify(HOURLY_AGGREGATION_GUEST_ID)
+        .unwrap();
+    println!(
+        "aggregated blocks {} to {} into {} hours",
+        aggregation_res.block_range.first_block,
+        aggregation_res.block_range.last_block,
+        aggregation_res.avg_fees.len()
+    );
+
+    // the hashing guest verifies the aggregation receipt and commits the block range
+    let (_hashing_receipt, hashing_res) = hash_avg_fees_with_aggregation(
+        HashingAvgFeesInput {
+            inputs: aggregation_res.avg_fees.clone(),
+            hash_config: HashConfig::default(),
+            aggregation: Some(aggregation_res),
+        },
+        Some(aggregation_receipt),
+    );
+
+    println!("hash: {:?}", hashing_res.hash);
+    println!("block_range: {:?}", hashing_res.block_range);
+}
@@ -0,0 +1,235 @@
+use add_twap_7d_error_bound_floating_methods::{
+    ADD_TWAP_7D_ERROR_BOUND_FLOATING_GUEST_ELF, ADD_TWAP_7D_ERROR_BOUND_FLOATING_GUEST_ID,
+};
+use avg_fees_range_methods::{AVG_FEES_RANGE_GUEST_ELF, AVG_FEES_RANGE_GUEST_ID};
+use calculate_pt_pt1_error_bound_floating_methods::{
+    CALCULATE_PT_PT1_ERROR_BOUND_FLOATING_GUEST_ELF, CALCULATE_PT_PT1_ERROR_BOUND_FLOATING_GUEST_ID,
+};
+use ema_error_bound_floating_methods::{
+    EMA_ERROR_BOUND_FLOATING_GUEST_ELF, EMA_ERROR_BOUND_FLOATING_GUEST_ID,
+};
+use eyre::{anyhow as err, Result};
+use geometric_twap_error_bound_floating_methods::{
+    GEOMETRIC_TWAP_ERROR_BOUND_FLOATING_GUEST_ELF, GEOMETRIC_TWAP_ERROR_BOUND_FLOATING_GUEST_ID,
+};
+use greeks_floating_methods::{GREEKS_FLOATING_GUEST_ELF, GREEKS_FLOATING_GUEST_ID};
+use hashing_avg_fees_methods::{HASHING_AVG_FEES_GUEST_ELF, HASHING_AVG_FEES_GUEST_ID};
+use hashing_avg_fees_rolling_methods::{
+    HASHING_AVG_FEES_ROLLING_GUEST_ELF, HASHING_AVG_FEES_ROLLING_GUEST_ID,
+};
+use hashing_felts_methods::{HASHING_FELTS_GUEST_ELF, HASHING_FELTS_GUEST_ID};
+use header_chain_methods::{HEADER_CHAIN_GUEST_ELF, HEADER_CHAIN_GUEST_ID};
+use hourly_aggregation_methods::{HOURLY_AGGREGATION_GUEST_ELF, HOURLY_AGGREGATION_GUEST_ID};
+use max_return_floating_methods::{MAX_RETURN_FLOATING_GUEST_ELF, MAX_RETURN_FLOATING_GUEST_ID};
+use mock_proof_composition_methods::{
+    MOCK_PROOF_COMPOSITION_GUEST_ELF, MOCK_PROOF_COMPOSITION_GUEST_ID,
+};
+use proof_composition_twap_maxreturn_reserveprice_floating_hashing_methods::{
+    PROOF_COMPOSITION_TWAP_MAXRETURN_RESERVEPRICE_FLOATING_HASHING_GUEST_ELF,
+    PROOF_COMPOSITION_TWAP_MAXRETURN_RESERVEPRICE_FLOATING_HASHING_GUEST_ID,
+};
+use remove_seasonality_error_bound_floating_methods::{
+    REMOVE_SEASONALITY_ERROR_BOUND_FLOATING_GUEST_ELF,
+    REMOVE_SEASONALITY_ERROR_BOUND_FLOATING_GUEST_ID,
+};
+use risc0_zkvm::sha::Digest;
+use rolling_median_error_bound_floating_methods::{
+    ROLLING_MEDIAN_ERROR_BOUND_FLOATING_GUEST_ELF, ROLLING_MEDIAN_ERROR_BOUND_FLOATING_GUEST_ID,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+use simulate_price_verify_position_floating_methods::{
+    SIMULATE_PRICE_VERIFY_POSITION_FLOATING_GUEST_ELF,
+    SIMULATE_PRICE_VERIFY_POSITION_FLOATING_GUEST_ID,
+};
+use std::fs;
+use tail_risk_floating_methods::{TAIL_RISK_FLOATING_GUEST_ELF, TAIL_RISK_FLOATING_GUEST_ID};
+use time_weighted_twap_error_bound_floating_methods::{
+    TIME_WEIGHTED_TWAP_ERROR_BOUND_FLOATING_GUEST_ELF,
+    TIME_WEIGHTED_TWAP_ERROR_BOUND_FLOATING_GUEST_ID,
+};
+use twap_error_bound_floating_methods::{
+    TWAP_ERROR_BOUND_FLOATING_GUEST_ELF, TWAP_ERROR_BOUND_FLOATING_GUEST_ID,
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GuestManifestEntry {
+    pub name: String,
+    pub image_id: String,
+    pub image_id_words: [u32; 8],
+    pub elf_sha256: String,
+}
+
+/// Every guest method in the workspace. New guests must be added here so that the
+/// committed manifest keeps covering all of them.
+pub fn guest_methods() -> Vec<(&'static str, &'static [u8], [u32; 8])> {
+    vec![
+        (
+            "add-twap-7d-error-bound-floating-guest",
+            ADD_TWAP_7D_ERROR_BOUND_FLOATING_GUEST_ELF,
+            ADD_TWAP_7D_ERROR_BOUND_FLOATING_GUEST_ID,
+        ),
+        (
+            "avg-fees-range-guest",
+            AVG_FEES_RANGE_GUEST_ELF,
+            AVG_FEES_RANGE_GUEST_ID,
+        ),
+        (
+            "calculate-pt-pt1-error-bound-floating-guest",
+            CALCULATE_PT_PT1_ERROR_BOUND_FLOATING_GUEST_ELF,
+            CALCULATE_PT_PT1_ERROR_BOUND_FLOATING_GUEST_ID,
+        ),
+        (
+            "ema-error-bound-floating-guest",
+            EMA_ERROR_BOUND_FLOATING_GUEST_ELF,
+            EMA_ERROR_BOUND_FLOATING_GUEST_ID,
+        ),
+        (
+            "geometric-twap-error-bound-floating-guest",
+            GEOMETRIC_TWAP_ERROR_BOUND_FLOATING_GUEST_ELF,
+            GEOMETRIC_TWAP_ERROR_BOUND_FLOATING_GUEST_ID,
+        ),
+        (
+            "greeks-floating-guest",
+            GREEKS_FLOATING_GUEST_ELF,
+            GREEKS_FLOATING_GUEST_ID,
+        ),
+        (
+            "hashing-avg-fees-guest",
+            HASHING_AVG_FEES_GUEST_ELF,
+            HASHING_AVG_FEES_GUEST_ID,
+        ),
+        (
+            "hashing-avg-fees-rolling-guest",
+            HASHING_AVG_FEES_ROLLING_GUEST_ELF,
+            HASHING_AVG_FEES_ROLLING_GUEST_ID,
+        ),
+        (
+            "hashing-felts-guest",
+            HASHING_FELTS_GUEST_ELF,
+            HASHING_FELTS_GUEST_ID,
+        ),
+        (
+            "header-chain-guest",
+            HEADER_CHAIN_GUEST_ELF,
+            HEADER_CHAIN_GUEST_ID,
+        ),
+        (
+            "hourly-aggregation-guest",
+            HOURLY_AGGREGATION_GUEST_ELF,
+            HOURLY_AGGREGATION_GUEST_ID,
+        ),
+        (
+            "max-return-floating-guest",
+            MAX_RETURN_FLOATING_GUEST_ELF,
+            MAX_RETURN_FLOATING_GUEST_ID,
+        ),
+        (
+            "mock-proof-composition-guest",
+            MOCK_PROOF_COMPOSITION_GUEST_ELF,
+            MOCK_PROOF_COMPOSITION_GUEST_ID,
+        ),
+        (
+            "proof-composition-twap-maxreturn-reserveprice-floating-hashing-guest",
+            PROOF_COMPOSITION_TWAP_MAXRETURN_RESERVEPRICE_FLOATING_HASHING_GUEST_ELF,
+            PROOF_COMPOSITION_TWAP_MAXRETURN_RESERVEPRICE_FLOATING_HASHING_GUEST_ID,
+        ),
+        (
+            "remove-seasonality-error-bound-floating-guest",
+            REMOVE_SEASONALITY_ERROR_BOUND_FLOATING_GUEST_ELF,
+            REMOVE_SEASONALITY_ERROR_BOUND_FLOATING_GUEST_ID,
+        ),
+        (
+            "rolling-median-error-bound-floating-guest",
+            ROLLING_MEDIAN_ERROR_BOUND_FLOATING_GUEST_ELF,
+            ROLLING_MEDIAN_ERROR_BOUND_FLOATING_GUEST_ID,
+        ),
+        (
+            "simulate-price-verify-position-floating-guest",
+            SIMULATE_PRICE_VERIFY_POSITION_FLOATING_GUEST_ELF,
+            SIMULATE_PRICE_VERIFY_POSITION_FLOATING_GUEST_ID,
+        ),
+        (
+            "tail-risk-floating-guest",
+            TAIL_RISK_FLOATING_GUEST_ELF,
+            TAIL_RISK_FLOATING_GUEST_ID,
+        ),
+        (
+            "time-weighted-twap-error-bound-floating-guest",
+            TIME_WEIGHTED_TWAP_ERROR_BOUND_FLOATING_GUEST_ELF,
+            TIME_WEIGHTED_TWAP_ERROR_BOUND_FLOATING_GUEST_ID,
+        ),
+        (
+            "twap-error-bound-floating-guest",
+            TWAP_ERROR_BOUND_FLOATING_GUEST_ELF,
+            TWAP_ERROR_BOUND_FLOATING_GUEST_ID,
+        ),
+    ]
+}
+
+pub fn build_manifest() -> Vec<GuestManifestEntry> {
+    guest_methods()
+        .into_iter()
+        .map(|(name, elf, image_id)| GuestManifestEntry {
+            name: name.to_owned(),
+            image_id: format!("0x{}", Digest::from(image_id)),
+            image_id_words: image_id,
+            elf_sha256: format!("0x{}", to_hex(&Sha256::digest(elf))),
+        })
+        .collect()
+}
+
+pub fn write_manifest(path: &str, manifest: &[GuestManifestEntry]) -> Result<()> {
+    let json = serde_json::to_string_pretty(manifest)?;
+    fs::write(path, json + "\n").map_err(|e| err!("Cannot write manifest '{}': {}", path, e))
+}
+
+pub fn read_manifest(path: &str) -> Result<Vec<GuestManifestEntry>> {
+    let json =
+        fs::read_to_string(path).map_err(|e| err!("Cannot read manifest '{}': {}", path, e))?;
+    serde_json::from_str(&json).map_err(|e| err!("Cannot parse manifest '{}': {}", path, e))
+}
+
+/// Lists every difference between the committed manifest and the current build.
+/// An empty result means no image ID or ELF changed.
+pub fn diff_manifests(
+    committed: &[GuestManifestEntry],
+    current: &[GuestManifestEntry],
+) -> Vec<String> {
+    let mut differences = vec![];
+
+    for entry in current {
+        match committed.iter().find(|c| c.name == entry.name) {
+            None => differences.push(format!("{}: new guest, not in the manifest", entry.name)),
+            Some(committed_entry) => {
+                if committed_entry.image_id != entry.image_id {
+                    differences.push(format!(
+                        "{}: image ID changed from {} to {}",
+                        entry.name, committed_entry.image_id, entry.image_id
+                    ));
+                }
+                if committed_entry.elf_sha256 != entry.elf_sha256 {
+                    differences.push(format!(
+                        "{}: ELF digest changed from {} to {}",
+                        entry.name, committed_entry.elf_sha256, entry.elf_sha256
+                    ));
+                }
+            }
+        }
+    }
+
+    for committed_entry in committed {
+        if !current.iter().any(|e| e.name == committed_entry.name) {
+            differences.push(format!(
+                "{}: listed in the manifest but no longer built",
+                committed_entry.name
+            ));
+        }
+    }
+
+    differences
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
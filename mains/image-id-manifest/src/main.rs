@@ -0,0 +1,64 @@
+use eyre::{anyhow as err, Result};
+use image_id_manifest::{build_manifest, diff_manifests, read_manifest, write_manifest};
+use std::{env, path::Path, process};
+
+const DEFAULT_MANIFEST: &str = "image_ids.json";
+const USAGE: &str = "usage: image-id-manifest <generate|check> [manifest.json]";
+const GENERATE: &str = "RISC0_USE_DOCKER=1 cargo run -p image-id-manifest -- generate";
+// image IDs of a local toolchain build depend on the machine, so the committed ones must
+// come from the docker build
+const DOCKER_ONLY: &str =
+    "image IDs must come from the RISC Zero docker build, not from a local toolchain build";
+
+fn main() {
+    if let Err(e) = run(env::args().skip(1).collect()) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+fn run(args: Vec<String>) -> Result<()> {
+    let command = args.first().ok_or_else(|| err!("{}", USAGE))?;
+    let path = args.get(1).map(String::as_str).unwrap_or(DEFAULT_MANIFEST);
+
+    let manifest = build_manifest();
+
+    match command.as_str() {
+        "generate" => {
+            if env::var_os("RISC0_USE_DOCKER").is_none() {
+                return Err(err!(
+                    "RISC0_USE_DOCKER is not set ({}): run `{}`",
+                    DOCKER_ONLY,
+                    GENERATE
+                ));
+            }
+            write_manifest(path, &manifest)?;
+            println!("wrote {} guests to {}", manifest.len(), path);
+        }
+        "check" => {
+            if !Path::new(path).exists() {
+                return Err(err!(
+                    "{} has not been generated yet: run `{}` and commit it ({})",
+                    path,
+                    GENERATE,
+                    DOCKER_ONLY
+                ));
+            }
+            let committed = read_manifest(path)?;
+            let differences = diff_manifests(&committed, &manifest);
+            if !differences.is_empty() {
+                return Err(err!(
+                    "{} does not match the built guests:\n{}\n{}: check with RISC0_USE_DOCKER=1, and run `{}` if the change is intended",
+                    path,
+                    differences.join("\n"),
+                    DOCKER_ONLY,
+                    GENERATE
+                ));
+            }
+            println!("{} matches all {} built guests", path, manifest.len());
+        }
+        _ => return Err(err!("unknown command '{}'\n{}", command, USAGE)),
+    }
+
+    Ok(())
+}
@@ -1,10 +1,10 @@
-use core::MaxReturnInput;
+use core::{MaxReturnInput, MaxReturnOutput};
 use max_return_floating_methods::MAX_RETURN_FLOATING_GUEST_ELF;
 use risc0_zkvm::{default_prover, ExecutorEnv, Receipt};
 use std::thread;
 use std::time::Duration;
 
-pub fn max_return(input: MaxReturnInput) -> (Receipt, (MaxReturnInput, f64)) {
+pub fn max_return(input: MaxReturnInput) -> (Receipt, MaxReturnOutput) {
     eprintln!(
         "max_return: Received {} data points for max return calculation",
         input.data.len()
@@ -31,12 +31,12 @@ pub fn max_return(input: MaxReturnInput) -> (Receipt, (MaxReturnInput, f64)) {
         match prover.prove(env, MAX_RETURN_FLOATING_GUEST_ELF) {
             Ok(prove_info) => {
                 let receipt = prove_info.receipt;
-                let (input, max_return): (MaxReturnInput, f64) = receipt.journal.decode().unwrap();
+                let res: MaxReturnOutput = receipt.journal.decode().unwrap();
                 eprintln!(
                     "max_return: Proof generation succeeded on attempt {}",
                     attempt
                 );
-                return (receipt, (input, max_return));
+                return (receipt, res);
             }
             Err(e) => {
                 eprintln!(
@@ -11,5 +11,5 @@ fn main() {
     let (receipt, res) = max_return(input);
 
     receipt.verify(MAX_RETURN_FLOATING_GUEST_ID).unwrap();
-    println!("max_return: {:?}", res.1);
+    println!("max_return: {:?}", res.max_return);
 }
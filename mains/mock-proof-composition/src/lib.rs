@@ -0,0 +1,115 @@
+use common::{
+    convert_felt_to_f64,
+    floating_point::{FeeModelKind, PayoffKind},
+    tests::mock::get_5760_avg_base_fees_felt,
+    window::SettlementSpec,
+    HashConfig,
+};
+use core::{DataHashingProof, ProofCompositionInput, TwapKind};
+use garaga_rs::{
+    calldata::full_proof_with_hints::groth16::{
+        get_groth16_calldata_felt, risc0_utils::get_risc0_vk, Groth16Proof,
+    },
+    definitions::CurveID,
+};
+use mock_proof_composition_methods::MOCK_PROOF_COMPOSITION_GUEST_ELF;
+use nalgebra::DVector;
+use risc0_ethereum_contracts::encode_seal;
+use risc0_zkvm::{default_prover, sha::Digest, ExecutorEnv, ProverOpts, Receipt, VerifierContext};
+
+pub fn mock_proof_composition_input() -> ProofCompositionInput {
+    ProofCompositionInput {
+        // one fee per hour of [data_8_months_start_timestamp, data_8_months_end_timestamp)
+        data_8_months: get_5760_avg_base_fees_felt()
+            .iter()
+            .map(|felt| convert_felt_to_f64(*felt))
+            .collect(),
+        data_8_months_hash: [
+            0x12345678, 0x23456789, 0x3456789a, 0x456789ab, 0x56789abc, 0x6789abcd, 0x789abcde,
+            0x89abcdef,
+        ],
+        data_8_months_hash_config: HashConfig::default(),
+        data_8_months_hashing: DataHashingProof::Full {
+            merkle_root: [
+                0x9abcdef0, 0xabcdef01, 0xbcdef012, 0xcdef0123, 0xdef01234, 0xef012345, 0xf0123456,
+                0x01234567,
+            ],
+        },
+        data_8_months_block_range: None,
+        data_8_months_start_timestamp: 1695873600, // 2023-09-28 (5760 hours earlier)
+        data_8_months_end_timestamp: 1716609600,   // 2024-05-25
+        start_timestamp: 1708833600,               // 2024-02-25 (90 days)
+        end_timestamp: 1716609600,                 // 2024-05-25
+        twap_start_timestamp: 1708833600,          // 2024-02-25
+        twap_end_timestamp: 1716609600,            // 2024-05-25
+        reserve_price_start_timestamp: 1708833600, // 2024-02-25
+        reserve_price_end_timestamp: 1716609600,   // 2024-05-25
+        max_return_start_timestamp: 1708833600,    // 2024-02-25
+        max_return_end_timestamp: 1716609600,      // 2024-05-25
+        round_start_timestamp: 1716609600,         // 2024-05-25
+        round_end_timestamp: 1719201600,           // 2024-06-24 (30-day round)
+        settlement: SettlementSpec::default(),
+        fee_model: FeeModelKind::MeanRevertingJump,
+        // alpha, phi, mu_j, sigma^2, sigma_j^2 and lambda of the hourly MRJ fit
+        positions: vec![0.001, 0.95, 0.1, 0.01, 0.05, 0.02],
+        pt: DVector::from_vec(vec![0.1, 0.2, 0.3]),
+        pt_1: DVector::from_vec(vec![0.2, 0.3, 0.4]),
+        gradient_tolerance: 0.001,
+        de_seasonalised_detrended_log_base_fee: DVector::from_vec(vec![0.5, 0.6, 0.7]),
+        num_paths: 100,
+        season_param: DVector::from_vec(vec![0.8, 0.9, 1.0]),
+        twap_7d: vec![1.1, 1.2, 1.3],
+        slope: 0.05,
+        intercept: 1.5,
+        payoff: PayoffKind::default(),
+        reserve_price: 2.5,
+        reserve_price_grid: None,
+        floating_point_tolerance: 0.0001,
+        reserve_price_tolerance: 0.01,
+        twap_tolerance: 0.05,
+        twap_result: 1.25,
+        twap_kind: TwapKind::Arithmetic,
+        twap_aggregation: None,
+        geometric_twap_result: None,
+        rolling_median: None,
+        ema: None,
+        max_return: 0.3,
+        tail_risk: None,
+    }
+}
+
+/// Proves the mock composition guest with a Groth16 receipt, which is what the
+/// Starknet verifier consumes.
+pub fn prove_mock_proof_composition(input: &ProofCompositionInput) -> Receipt {
+    let env = ExecutorEnv::builder()
+        .write(input)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    default_prover()
+        .prove_with_ctx(
+            env,
+            &VerifierContext::default(),
+            MOCK_PROOF_COMPOSITION_GUEST_ELF,
+            &ProverOpts::groth16(),
+        )
+        .unwrap()
+        .receipt
+}
+
+/// Builds the Garaga `full_proof_with_hints` calldata for a Groth16 receipt, with every
+/// felt rendered as a `0x` prefixed lowercase hex string.
+pub fn groth16_calldata(receipt: &Receipt, image_id: Digest) -> Vec<String> {
+    let encoded_seal = encode_seal(receipt).unwrap();
+    let journal = receipt.journal.bytes.clone();
+
+    let groth16_proof =
+        Groth16Proof::from_risc0(encoded_seal, image_id.as_bytes().to_vec(), journal);
+
+    get_groth16_calldata_felt(&groth16_proof, &get_risc0_vk(), CurveID::BN254)
+        .unwrap()
+        .iter()
+        .map(|felt| format!("{:#x}", felt))
+        .collect()
+}
@@ -5,29 +5,52 @@ use add_twap_7d_error_bound_floating::add_twap_7d_error_bound;
 use common::{
     // Functions to read CSV data and convert timestamps to dates
     common::dataframe::{read_data_from_file, replace_timestamp_with_date},
+    // Fixed point felt decoding, matching the conversion done inside the hashing guest
+    convert_felt_to_f64,
     // Floating-point arithmetic utilities for gas fee calculations
     floating_point,
     // Original algorithm implementations and array conversion utilities
     original::{self, convert_array1_to_dvec},
     // Mock data utilities for testing with 5760 average base fees (240 days * 24 hours)
-    tests::mock::{convert_data_to_vec_of_tuples, get_5760_avg_base_fees_felt},
+    // and with blocks to aggregate hourly fees from
+    tests::mock::{get_5760_avg_fees, get_mock_blocks},
+    // Simulation horizon of an option round, from its timestamps and settlement window
+    window::{round_horizon, SettlementSpec},
+    // Batch size and hash function used for the data commitment
+    HashConfig,
 };
 
 // Import core input structures for various computation stages
 use core::{
     AddTwap7dErrorBoundFloatingInput, // Input for 7-day TWAP error bound calculation
+    AvgFees,                          // Hourly average base fee record, as stored on-chain
+    BlockBaseFee,                     // Base fee of a block, as aggregated into hourly records
     CalculatePtPt1ErrorBoundFloatingInput, // Input for price transition probability calculations
-    HashingFeltInput,                 // Input for Starknet field element hashing
+    DataHashingProof,                 // Hashing guest the data commitment was proven by
+    EmaErrorBoundInput,               // Input for EMA reference price verification
+    EmaReference,                     // EMA reference price passed to the composition
+    GeometricTwapErrorBoundInput,     // Input for geometric TWAP verification
+    HashingAvgFeesInput,              // Input for hashing the AvgFees records
+    HourlyAggregationInput,           // Input for aggregating blocks into hourly records
     MaxReturnInput,                   // Input for maximum return calculation
     ProofCompositionInput,            // Main input structure combining all computations
     RemoveSeasonalityErrorBoundFloatingInput, // Input for deseasonalizing time series data
+    RollingMedianErrorBoundInput,     // Input for rolling median verification
+    RollingMedianReference,           // Rolling median reference price passed to the composition
     SimulatePriceVerifyPositionInput, // Input for Monte Carlo price simulation
+    TailRiskInput,                    // Input for tail-risk calculation
+    TailRiskReport,                   // Tail-risk metrics passed to the composition
+    TimeWeightedTwapErrorBoundInput,  // Input for time-weighted TWAP verification
     TwapErrorBoundInput,              // Input for TWAP verification with tolerance
+    TwapKind,                         // TWAP definition proven by the composition
 };
 
 // Import maximum return calculation function (analyzes historical gas fee volatility)
 use max_return_floating::max_return;
 
+// Import tail-risk calculation (VaR, CVaR and max drawdown of the same returns)
+use tail_risk_floating::tail_risk;
+
 // Import the RISC Zero guest program ELF binary and ID for ZK proof generation
 use proof_composition_twap_maxreturn_reserveprice_floating_hashing_methods::{
     PROOF_COMPOSITION_TWAP_MAXRETURN_RESERVEPRICE_FLOATING_HASHING_GUEST_ELF,
@@ -39,6 +62,7 @@ use remove_seasonality_error_bound_floating::remove_seasonality_error_bound;
 
 // Import RISC Zero ZK-VM utilities for proof generation
 use risc0_zkvm::{default_prover, ExecutorEnv};
+use std::fs::File;
 use std::thread;
 use std::time::Duration;
 
@@ -48,29 +72,96 @@ use simulate_price_verify_position_floating::simulate_price_verify_position as s
 // Import TWAP calculation with error bounds for gas fee averaging
 use twap_error_bound_floating::calculate_twap;
 
+// Import time-weighted TWAP calculation with error bounds
+use time_weighted_twap_error_bound_floating::calculate_time_weighted_twap;
+
+// Import alternative reference price calculations with error bounds
+use ema_error_bound_floating::calculate_ema;
+use geometric_twap_error_bound_floating::calculate_geometric_twap;
+use rolling_median_error_bound_floating::calculate_rolling_median;
+
 // Import transition probability calculation for Markov chain price model
 use calculate_pt_pt1_error_bound_floating::calculate_pt_pt1_error_bound_floating;
 
-// Import Starknet field element hashing (Pedersen/Poseidon hash for on-chain verification)
-use hashing_felts::hash_felts;
+// Import hashing of the AvgFees records (same layout as the on-chain fossil hash store)
+use hashing_avg_fees::hash_avg_fees;
+
+// Import aggregation of blocks into hourly AvgFees records
+use hourly_aggregation::aggregate_hourly_fees;
+
+// Import receipt persistence so the final proof can be verified offline
+use verify_proof_composition::save_receipt;
 
 fn main() {
     // ========== STEP 1: HASH GAS FEE DATA ==========
-    // Get 5760 hourly average base fees (8 months of data: 240 days * 24 hours)
-    // These represent Ethereum gas fees as Starknet field elements (felts)
-    let inputs_felt = get_5760_avg_base_fees_felt();
-
-    // Hash the gas fee data using Starknet-compatible hashing (Pedersen/Poseidon)
-    // This creates a commitment to the data that can be verified on-chain
-    // The hashing will eventually be performed on-chain for trustless verification
-    let (hashing_receipt, hashing_res) = hash_felts(HashingFeltInput {
-        inputs: inputs_felt,
+    // Define the time period for reserve price calculation
+    let start_timestamp = 1708833600; // February 25, 2024, 00:00:00 UTC
+                                      // Calculate end timestamp: 90 days later (3 months)
+    let end_timestamp = 1708833600 + (3600 * 24 * 30 * 3);
+    // The 8-month dataset starts 5 months before the 3-month reserve price calculation period
+    let data_8_months_start_timestamp = start_timestamp - (3600 * 24 * 30 * 5);
+
+    // Get 5760 hourly AvgFees records (8 months of data: 240 days * 24 hours)
+    // Each record holds its hour, the number of blocks averaged and the average fee as a felt
+    let mut inputs: Vec<AvgFees> = get_5760_avg_fees(data_8_months_start_timestamp as u64)
+        .into_iter()
+        .map(|(timestamp, data_points, avg_fee)| AvgFees {
+            timestamp,
+            data_points,
+            avg_fee,
+        })
+        .collect();
+
+    // Settlement is specified as a time-weighted average, so that is the TWAP definition
+    // proven by default. The arithmetic mean is still available, and the chosen definition
+    // is committed in the composition journal as twap_kind
+    let twap_kind = TwapKind::TimeWeighted;
+
+    // A time-weighted TWAP is proven from the blocks of the TWAP window (the last 90 days),
+    // which the composition checks to average to the hashed records of the window. The mock
+    // records are not aggregated from blocks, so those of the window are replaced with the
+    // hourly aggregation of mock blocks
+    let twap_blocks = match twap_kind {
+        TwapKind::Arithmetic => None,
+        TwapKind::TimeWeighted => {
+            let blocks = get_mock_blocks(start_timestamp as u64, 2160)
+                .into_iter()
+                .map(|(number, timestamp, base_fee)| BlockBaseFee {
+                    number,
+                    timestamp,
+                    base_fee,
+                })
+                .collect::<Vec<_>>();
+            let (aggregation_receipt, aggregation_res) =
+                aggregate_hourly_fees(HourlyAggregationInput {
+                    blocks: blocks.clone(),
+                });
+
+            let twap_start = inputs.len() - aggregation_res.avg_fees.len();
+            inputs.splice(twap_start.., aggregation_res.avg_fees.clone());
+            Some((blocks, aggregation_receipt, aggregation_res))
+        }
+    };
+
+    // Hash the records using the fossil hash store layout
+    // This creates a commitment to the data that can be verified on-chain, and proves
+    // that the records are contiguous hours starting at data_8_months_start_timestamp
+    // The default config matches the fossil hash store (SHA-256 over batches of 180 records)
+    let (hashing_receipt, hashing_res) = hash_avg_fees(HashingAvgFeesInput {
+        inputs: inputs.clone(),
+        hash_config: HashConfig::default(),
+        // The mock records are not aggregated from blocks, see the hourly-aggregation crate
+        aggregation: None,
     });
 
     // ========== STEP 2: CALCULATE MAXIMUM RETURN ==========
     // Extract the f64 (floating-point) representation of the gas fee data
     // This converts the Starknet field elements back to standard floating-point numbers
-    let data_8_months = hashing_res.f64_inputs;
+    // The hashing journal only commits a digest of these values, so they are recomputed here
+    let data_8_months = inputs
+        .iter()
+        .map(|x| convert_felt_to_f64(x.avg_fee))
+        .collect::<Vec<f64>>();
 
     // Calculate the maximum return (volatility measure) from 8 months of historical data
     // This analyzes the largest price swings and is used for option pricing parameters
@@ -79,6 +170,13 @@ fn main() {
     };
     let (max_return_receipt, max_return_res) = max_return(input);
 
+    // Tail of the same returns: historical VaR and CVaR at the 95% and 99% quantiles, the
+    // minimum return and the max drawdown of the TWAP-30d
+    let (tail_risk_receipt, tail_risk_res) = tail_risk(TailRiskInput {
+        data: data_8_months.clone(),
+        quantiles: vec![0.95, 0.99],
+    });
+
     // ========== STEP 3: CALCULATE TIME-WEIGHTED AVERAGE PRICE (TWAP) ==========
     // TWAP compares the time-weighted average using:
     //   1. Actual block-by-block data (host computation)
@@ -102,41 +200,112 @@ fn main() {
 
     // Calculate TWAP using the floating-point algorithm on hourly averaged data
     // This is the "ground truth" that will be verified in the ZK proof
-    let twap_original = floating_point::calculate_twap(&data);
-    println!("twap_original: {:?}", twap_original);
-
-    // Prepare input for TWAP verification with error bounds
     // The ZK proof will verify that the calculated TWAP is within tolerance (1.0%)
-    let input = TwapErrorBoundInput {
-        avg_hourly_gas_fee: data.clone(), // 90 days of hourly gas fees
-        twap_tolerance: 1.0,              // 1% tolerance for TWAP calculation
-        twap_result: twap_original,       // Expected TWAP result to verify against
+    let (twap_original, calculate_twap_receipt) = match twap_kind {
+        TwapKind::Arithmetic => {
+            let twap_original = floating_point::calculate_twap(&data);
+
+            // Prepare input for TWAP verification with error bounds
+            let input = TwapErrorBoundInput {
+                avg_hourly_gas_fee: data.clone(), // 90 days of hourly gas fees
+                twap_tolerance: 1.0,              // 1% tolerance for TWAP calculation
+                twap_result: twap_original,       // Expected TWAP result to verify against
+            };
+
+            // Generate a ZK proof receipt that TWAP was calculated correctly
+            let (calculate_twap_receipt, _calculate_twap_res) = calculate_twap(input);
+            (twap_original, calculate_twap_receipt)
+        }
+        TwapKind::TimeWeighted => {
+            // Each block's base fee holds until the next block
+            let (blocks, _, _) = twap_blocks.as_ref().unwrap();
+            let samples = floating_point::block_base_fee_samples(
+                &blocks
+                    .iter()
+                    .map(|x| (x.number, x.timestamp, x.base_fee))
+                    .collect::<Vec<_>>(),
+                start_timestamp,
+                end_timestamp,
+            )
+            .unwrap();
+            let twap_original =
+                floating_point::calculate_time_weighted_twap(&samples, end_timestamp).unwrap();
+
+            let input = TimeWeightedTwapErrorBoundInput {
+                blocks: blocks.clone(), // 90 days of blocks, plus a bounding block on either side
+                start_timestamp,        // Start of the TWAP window
+                end_timestamp,          // End of the TWAP window
+                twap_tolerance: 1.0,    // 1% tolerance for TWAP calculation
+                twap_result: twap_original,
+            };
+
+            let (calculate_twap_receipt, _calculate_twap_res) = calculate_time_weighted_twap(input);
+            (twap_original, calculate_twap_receipt)
+        }
     };
+    println!("twap_original ({:?}): {:?}", twap_kind, twap_original);
+
+    // ========== STEP 3A: PROVE ALTERNATIVE REFERENCE PRICES ==========
+    // Manipulation-resistant references over the same hourly fees as the TWAP, which a
+    // vault can settle against instead. Each is optional in the composition
+    let reference_window = 24 * 7; // Median window and EMA span: 7 days of hours
+
+    let geometric_twap_result = floating_point::calculate_geometric_twap(&data).unwrap();
+    let (geometric_twap_receipt, _geometric_twap_res) =
+        calculate_geometric_twap(GeometricTwapErrorBoundInput {
+            avg_hourly_gas_fee: data.clone(),
+            tolerance: 1.0, // Same 1% tolerance as the TWAP
+            geometric_twap_result,
+        });
+
+    let rolling_median = floating_point::calculate_rolling_median(&data, reference_window)
+        .unwrap()
+        .pop()
+        .unwrap();
+    let (rolling_median_receipt, _rolling_median_res) =
+        calculate_rolling_median(RollingMedianErrorBoundInput {
+            avg_hourly_gas_fee: data.clone(),
+            window: reference_window,
+            tolerance: 1.0,
+            rolling_median_result: rolling_median,
+        });
 
-    // Generate a ZK proof receipt that TWAP was calculated correctly
-    let (calculate_twap_receipt, _calculate_twap_res) = calculate_twap(input);
+    let ema_alpha = 2.0 / (reference_window as f64 + 1.0);
+    let ema = floating_point::calculate_ema(&data, ema_alpha)
+        .unwrap()
+        .pop()
+        .unwrap();
+    let (ema_receipt, _ema_res) = calculate_ema(EmaErrorBoundInput {
+        avg_hourly_gas_fee: data.clone(),
+        alpha: ema_alpha,
+        tolerance: 1.0,
+        ema_result: ema,
+    });
+    println!(
+        "geometric twap: {:?}, rolling median: {:?}, ema: {:?}",
+        geometric_twap_result, rolling_median, ema
+    );
 
     // ========== STEP 4: CALCULATE RESERVE PRICE (HOST COMPUTATION) ==========
     // The reserve price is the minimum price at which gas fee options can be sold
     // This is computed on the host (not in ZK) to ensure numerical convergence
     // The ZK proof will verify the calculation is correct within tolerances
 
-    // Define the time period for reserve price calculation
-    let start_timestamp = 1708833600; // February 25, 2024, 00:00:00 UTC
-                                      // Calculate end timestamp: 90 days later (3 months)
-    let end_timestamp = 1708833600 + (3600 * 24 * 30 * 3);
-
-    // n_periods: number of 3-hour periods in 90 days (720 = 90 days * 24 hours / 3 hours)
-    // This is used for Markov chain state transitions in the price model
-    let n_periods = 720;
+    // The option round starts when the data ends and lasts 30 days, settling on the TWAP
+    // of its last 7 days. The simulation runs one period per hour of the round
+    let round_start_timestamp = end_timestamp;
+    let round_end_timestamp = end_timestamp + 3600 * 24 * 30;
+    let settlement = SettlementSpec::default();
+    let horizon = round_horizon(round_start_timestamp, round_end_timestamp, &settlement).unwrap();
+    let n_periods = horizon.n_periods;
 
     // Convert hourly gas fee data to (timestamp, fee) tuples starting from start_timestamp
     let data_with_timestamps = convert_data_to_vec_of_tuples(data.clone(), start_timestamp);
 
     // Calculate reserve price using the original algorithm on the host
-    // Parameters: data, strike price (15000 gwei), and number of periods (720)
+    // Parameters: data, strike price (15000 gwei), and the horizon of the round
     // This performs time series decomposition, Monte Carlo simulation, and gradient descent
-    let res = original::calculate_reserve_price(&data_with_timestamps, 15000, n_periods);
+    let res = original::calculate_reserve_price(&data_with_timestamps, 15000, &horizon);
 
     // ========== STEP 5: DEFINE TOLERANCES FOR ZK VERIFICATION ==========
     // These tolerances determine how precisely the ZK proof must match the host computation
@@ -146,6 +315,38 @@ fn main() {
     let floating_point_tolerance = 0.00001; // 0.00001% tolerance for floating-point arithmetic
     let reserve_price_tolerance = 5.0; // 5% tolerance for final reserve price
 
+    // Reserve prices at 90%, 100% and 110% of the 7-day TWAP, for half and the full
+    // horizon, priced on the same simulated paths as the reserve price
+    let reserve_price_grid =
+        floating_point::calculated_reserve_price_grid_from_simulated_log_prices(
+            &original::convert_array2_to_dmatrix(res.simulated_log_prices.clone()),
+            &res.twap_7d,
+            &[0.9, 1.0, 1.1],
+            &[n_periods / 2, n_periods],
+            &floating_point::PayoffKind::default(),
+            &settlement,
+        )
+        .unwrap();
+
+    // Forecast fan chart of the same paths, for the front end to show what the model expects
+    let fan_chart = floating_point::calculated_fan_chart_from_simulated_log_prices(
+        &original::convert_array2_to_dmatrix(res.simulated_log_prices.clone()),
+        round_start_timestamp,
+        round_end_timestamp,
+        &settlement,
+        &floating_point::FAN_CHART_QUANTILES,
+    )
+    .unwrap();
+    fan_chart
+        .write_csv(File::create("fan_chart.csv").unwrap())
+        .unwrap();
+    fan_chart
+        .write_settlement_twap_csv(File::create("fan_chart_settlement_twap.csv").unwrap())
+        .unwrap();
+    fan_chart
+        .write_json(File::create("fan_chart.json").unwrap())
+        .unwrap();
+
     // ========== STEP 6: VERIFY SEASONALITY REMOVAL ==========
     // This generates a ZK proof that the time series decomposition was performed correctly
     // Time series decomposition separates the data into:
@@ -200,9 +401,14 @@ fn main() {
     //   3. The option positions (vega, theta, delta) are within acceptable bounds
     let (simulate_price_verify_position_receipt, _simulate_price_verify_position_res) =
         simulate_price_verify_position_receipt(SimulatePriceVerifyPositionInput {
-            start_timestamp,                                // Start of 90-day period
-            end_timestamp,                                  // End of 90-day period
-            data_length: data.len(),                        // Number of hourly data points (2160)
+            start_timestamp,         // Start of 90-day period
+            end_timestamp,           // End of 90-day period
+            round_start_timestamp,   // Start of the option round
+            round_end_timestamp,     // End of the option round
+            settlement,              // Settlement window of the round
+            data_length: data.len(), // Number of hourly data points (2160)
+            // Fee model the original fit uses
+            fee_model: floating_point::FeeModelKind::MeanRevertingJump,
             positions: res.positions.clone(), // Optimized option positions (vega, theta, delta)
             pt: convert_array1_to_dvec(res.pt.clone()), // Markov transition matrix t
             pt_1: convert_array1_to_dvec(res.pt_1.clone()), // Markov transition matrix t-1
@@ -211,13 +417,15 @@ fn main() {
             de_seasonalised_detrended_log_base_fee: convert_array1_to_dvec(
                 res.de_seasonalised_detrended_log_base_fee.clone(),
             ),
-            n_periods, // 720 three-hour periods
+            n_periods, // One period per hour of the round
             num_paths, // 4000 Monte Carlo paths
             season_param: convert_array1_to_dvec(res.season_param.clone()), // Hourly seasonality
             twap_7d: res.twap_7d.clone(), // 7-day TWAP (mean reversion level)
             slope: res.slope, // Linear trend slope
             intercept: res.intercept, // Linear trend intercept
+            payoff: floating_point::PayoffKind::default(), // Capped call, as in the original
             reserve_price: res.reserve_price, // Calculated reserve price
+            reserve_price_grid: Some(reserve_price_grid.clone()), // Other strikes and horizons
             tolerance: reserve_price_tolerance, // 5% tolerance for reserve price
         });
 
@@ -227,11 +435,19 @@ fn main() {
     let input = ProofCompositionInput {
         // Hash of the 8-month data for on-chain verification
         data_8_months_hash: hashing_res.hash,
+        // How the hash was computed, checked against the hashing journal
+        data_8_months_hash_config: hashing_res.hash_config,
+        // Hashed at once, with the Merkle root checked against the hashing journal
+        data_8_months_hashing: DataHashingProof::Full {
+            merkle_root: hashing_res.merkle_root,
+        },
+        // Blocks the records were aggregated from, checked against the hashing journal
+        data_8_months_block_range: hashing_res.block_range,
         // Full 8 months of hourly gas fee data
         data_8_months,
         // Timestamp range for the full 8-month dataset
         // Starts 5 months before the 3-month reserve price calculation period
-        data_8_months_start_timestamp: start_timestamp - (3600 * 24 * 30 * 5),
+        data_8_months_start_timestamp,
         data_8_months_end_timestamp: end_timestamp,
         // Timestamp range for the 3-month reserve price calculation
         start_timestamp,
@@ -241,9 +457,15 @@ fn main() {
         twap_end_timestamp: end_timestamp,
         reserve_price_start_timestamp: start_timestamp,
         reserve_price_end_timestamp: end_timestamp,
-        max_return_start_timestamp: start_timestamp - (3600 * 24 * 30 * 5),
+        max_return_start_timestamp: data_8_months_start_timestamp,
         max_return_end_timestamp: end_timestamp,
+        // Option round the reserve price is for, and how it settles
+        round_start_timestamp,
+        round_end_timestamp,
+        settlement,
         // Results from reserve price calculation
+        // Fee model the original fit uses
+        fee_model: floating_point::FeeModelKind::MeanRevertingJump,
         positions: res.positions,               // Optimized option positions
         pt: convert_array1_to_dvec(res.pt),     // Markov transition matrix t
         pt_1: convert_array1_to_dvec(res.pt_1), // Markov transition matrix t-1
@@ -252,36 +474,71 @@ fn main() {
         de_seasonalised_detrended_log_base_fee: convert_array1_to_dvec(
             res.de_seasonalised_detrended_log_base_fee,
         ),
-        // Simulation parameters
-        n_periods, // 720 three-hour periods
+        // Simulation parameters, n_periods following from the round
         num_paths, // 4000 Monte Carlo paths
         // Seasonal and trend parameters
         season_param: convert_array1_to_dvec(res.season_param), // 24 hourly values
         twap_7d: res.twap_7d,                                   // 7-day rolling TWAP
         slope: res.slope,                                       // Linear trend slope
         intercept: res.intercept,                               // Linear trend intercept
+        payoff: floating_point::PayoffKind::default(),          // Capped call, as in the original
         reserve_price: res.reserve_price,                       // Final reserve price
+        reserve_price_grid: Some(reserve_price_grid), // Same strikes and horizons as above
         // Tolerances for verification
         floating_point_tolerance, // 0.00001% for intermediate calculations
         reserve_price_tolerance,  // 5% for reserve price
         // TWAP verification parameters
         twap_result: twap_original, // Expected TWAP value
         twap_tolerance: 1.0,        // 1% tolerance for TWAP
+        twap_kind,                  // TWAP definition twap_result is proven under
+        // Blocks of the TWAP window, checked against the hashed records of the window
+        twap_aggregation: twap_blocks
+            .as_ref()
+            .map(|(_, _, aggregation_res)| aggregation_res.clone()),
+        // Alternative reference prices, verified within the TWAP tolerance
+        geometric_twap_result: Some(geometric_twap_result),
+        rolling_median: Some(RollingMedianReference {
+            window: reference_window,
+            rolling_median_result: rolling_median,
+        }),
+        ema: Some(EmaReference {
+            alpha: ema_alpha,
+            ema_result: ema,
+        }),
         // Maximum return (volatility measure)
-        max_return: max_return_res.1, // Maximum return from historical data
+        max_return: max_return_res.max_return, // Maximum return from historical data
+        // Tail-risk metrics of the same returns
+        tail_risk: Some(TailRiskReport {
+            quantiles: tail_risk_res.quantiles,
+            value_at_risk: tail_risk_res.value_at_risk,
+            conditional_value_at_risk: tail_risk_res.conditional_value_at_risk,
+            min_return: tail_risk_res.min_return,
+            max_drawdown: tail_risk_res.max_drawdown,
+        }),
     };
 
     // ========== STEP 11: BUILD EXECUTION ENVIRONMENT WITH PROOF ASSUMPTIONS ==========
     // The ExecutorEnv bundles all the individual proof receipts as "assumptions"
     // This enables proof composition: the final proof assumes the correctness of sub-proofs
     // without re-executing them, making the final proof smaller and faster to verify
-    let env = ExecutorEnv::builder()
+    let mut env = ExecutorEnv::builder();
+    // Assumption 0: The blocks of a time-weighted TWAP were aggregated into its hourly fees
+    if let Some((_, aggregation_receipt, _)) = twap_blocks {
+        env.add_assumption(aggregation_receipt);
+    }
+    let env = env
         // Assumption 1: Data hashing was performed correctly
         .add_assumption(hashing_receipt)
         // Assumption 2: TWAP calculation is within tolerance
         .add_assumption(calculate_twap_receipt)
+        // Assumption 2a: Alternative reference prices are within tolerance
+        .add_assumption(geometric_twap_receipt)
+        .add_assumption(rolling_median_receipt)
+        .add_assumption(ema_receipt)
         // Assumption 3: Maximum return was calculated correctly
         .add_assumption(max_return_receipt)
+        // Assumption 3a: Tail-risk metrics were calculated correctly
+        .add_assumption(tail_risk_receipt)
         // Assumption 4: Seasonality removal (time series decomposition) is correct
         .add_assumption(remove_seasonality_error_bound_receipt)
         // Assumption 5: 7-day TWAP calculation is within tolerance
@@ -369,4 +626,9 @@ fn main() {
     receipt
         .verify(PROOF_COMPOSITION_TWAP_MAXRETURN_RESERVEPRICE_FLOATING_HASHING_GUEST_ID)
         .unwrap();
+
+    // ========== STEP 14: PERSIST THE RECEIPT ==========
+    // Save the receipt so auditors and monitoring can re-verify it offline with
+    // `cargo run -p verify-proof-composition -- proof_composition_receipt.bin`
+    save_receipt(&receipt, "proof_composition_receipt.bin").unwrap();
 }
@@ -1,4 +1,4 @@
-use core::RemoveSeasonalityErrorBoundFloatingInput;
+use core::{RemoveSeasonalityErrorBoundFloatingInput, RemoveSeasonalityErrorBoundFloatingOutput};
 use remove_seasonality_error_bound_floating_methods::REMOVE_SEASONALITY_ERROR_BOUND_FLOATING_GUEST_ELF;
 use risc0_zkvm::{default_prover, ExecutorEnv, Receipt};
 use std::thread;
@@ -6,7 +6,7 @@ use std::time::Duration;
 
 pub fn remove_seasonality_error_bound(
     input: RemoveSeasonalityErrorBoundFloatingInput,
-) -> (Receipt, RemoveSeasonalityErrorBoundFloatingInput) {
+) -> (Receipt, RemoveSeasonalityErrorBoundFloatingOutput) {
     let prover = default_prover();
 
     const MAX_RETRIES: u32 = 10;
@@ -28,7 +28,7 @@ pub fn remove_seasonality_error_bound(
         match prover.prove(env, REMOVE_SEASONALITY_ERROR_BOUND_FLOATING_GUEST_ELF) {
             Ok(prove_info) => {
                 let receipt = prove_info.receipt;
-                let res: RemoveSeasonalityErrorBoundFloatingInput =
+                let res: RemoveSeasonalityErrorBoundFloatingOutput =
                     receipt.journal.decode().unwrap();
                 eprintln!(
                     "remove_seasonality_error_bound: Proof generation succeeded on attempt {}",
@@ -1,6 +1,7 @@
 use common::{
     original::{self, convert_array1_to_dvec},
     tests::mock::get_first_period_data,
+    window::{round_horizon_from_hours, SettlementSpec},
 };
 use core::RemoveSeasonalityErrorBoundFloatingInput;
 use remove_seasonality_error_bound_floating::remove_seasonality_error_bound;
@@ -10,7 +11,8 @@ fn main() {
     let data = get_first_period_data();
     // run rust code in host
     // ensure convergence in host
-    let res = original::calculate_reserve_price(&data, 15000, 720);
+    let horizon = round_horizon_from_hours(720, &SettlementSpec::default()).unwrap();
+    let res = original::calculate_reserve_price(&data, 15000, &horizon);
 
     let input = RemoveSeasonalityErrorBoundFloatingInput {
         data: data.iter().map(|x| x.1).collect(),
@@ -0,0 +1,31 @@
+use common::{floating_point::calculate_rolling_median, tests::mock::get_first_period_data};
+use core::RollingMedianErrorBoundInput;
+use rolling_median_error_bound_floating::calculate_rolling_median as prove_rolling_median;
+use rolling_median_error_bound_floating_methods::ROLLING_MEDIAN_ERROR_BOUND_FLOATING_GUEST_ID;
+
+fn main() {
+    let avg_hourly_gas_fee = get_first_period_data()
+        .iter()
+        .map(|x| x.1)
+        .collect::<Vec<f64>>();
+
+    // median of the last 7 days
+    let window = 24 * 7;
+    let rolling_median = calculate_rolling_median(&avg_hourly_gas_fee, window).unwrap();
+    let rolling_median_result = rolling_median[rolling_median.len() - 1];
+
+    println!("rolling median: {:?}", rolling_median_result);
+
+    let input = RollingMedianErrorBoundInput {
+        avg_hourly_gas_fee,
+        window,
+        tolerance: 1.0,
+        rolling_median_result,
+    };
+
+    let (receipt, _res) = prove_rolling_median(input);
+
+    receipt
+        .verify(ROLLING_MEDIAN_ERROR_BOUND_FLOATING_GUEST_ID)
+        .unwrap();
+}
@@ -1,4 +1,4 @@
-use core::SimulatePriceVerifyPositionInput;
+use core::{SimulatePriceVerifyPositionInput, SimulatePriceVerifyPositionOutput};
 use risc0_zkvm::{default_prover, ExecutorEnv, Receipt};
 use simulate_price_verify_position_floating_methods::SIMULATE_PRICE_VERIFY_POSITION_FLOATING_GUEST_ELF;
 use std::thread;
@@ -6,7 +6,7 @@ use std::time::Duration;
 
 pub fn simulate_price_verify_position(
     input: SimulatePriceVerifyPositionInput,
-) -> (Receipt, SimulatePriceVerifyPositionInput) {
+) -> (Receipt, SimulatePriceVerifyPositionOutput) {
     let prover = default_prover();
 
     const MAX_RETRIES: u32 = 10;
@@ -28,7 +28,7 @@ pub fn simulate_price_verify_position(
         match prover.prove(env, SIMULATE_PRICE_VERIFY_POSITION_FLOATING_GUEST_ELF) {
             Ok(prove_info) => {
                 let receipt = prove_info.receipt;
-                let res: SimulatePriceVerifyPositionInput = receipt.journal.decode().unwrap();
+                let res: SimulatePriceVerifyPositionOutput = receipt.journal.decode().unwrap();
                 eprintln!(
                     "simulate_price_verify_position: Proof generation succeeded on attempt {}",
                     attempt
@@ -1,6 +1,10 @@
 use common::{
+    convert_felt_to_f64,
+    floating_point::{FeeModelKind, PayoffKind},
     original::{calculate_reserve_price, convert_array1_to_dvec},
     tests::mock::{convert_data_to_vec_of_tuples, get_5760_avg_base_fees_felt},
+    window::{round_horizon, SettlementSpec},
+    HashConfig,
 };
 use core::SimulatePriceVerifyPositionInput;
 use simulate_price_verify_position_floating::simulate_price_verify_position;
@@ -9,17 +13,37 @@ use simulate_price_verify_position_floating_methods::SIMULATE_PRICE_VERIFY_POSIT
 use core::HashingFeltInput;
 use hashing_felts::hash_felts;
 
+#[cfg(feature = "columnar")]
+use common::{
+    common::columnar::{write_paths, ColumnarFormat},
+    floating_point::{
+        calculate_simulated_log_prices, calculated_reserve_price_for_round, error_bound_f64,
+        simulate_price_verify_position as simulate_price_verify_position_on_host,
+    },
+    original::convert_array2_to_dmatrix,
+};
+#[cfg(feature = "columnar")]
+use nalgebra::DMatrix;
+
+// Directory the simulations are exported to when the host and guest reserve prices diverge
+#[cfg(feature = "columnar")]
+const DIVERGENCE_DIR: &str = "divergence";
+
 fn main() {
     // get only first period of (timestamp avg_gas_fee)
     // let data = get_first_period_data();
     let inputs_felt = get_5760_avg_base_fees_felt();
-    let (_hashing_receipt, hashing_res) = hash_felts(HashingFeltInput {
-        inputs: inputs_felt,
+    let (_hashing_receipt, _hashing_res) = hash_felts(HashingFeltInput {
+        inputs: inputs_felt.clone(),
+        hash_config: HashConfig::default(),
     });
 
     // max return
     // let data_8_months = get_max_return_input_data();
-    let data_8_months = hashing_res.f64_inputs;
+    let data_8_months = inputs_felt
+        .iter()
+        .map(|x| convert_felt_to_f64(*x))
+        .collect::<Vec<f64>>();
     let data = data_8_months[data_8_months.len().saturating_sub(2160)..].to_vec();
 
     let start_timestamp = 1708833600;
@@ -31,10 +55,16 @@ fn main() {
     // let start_timestamp = data[0].0;
     // let end_timestamp = data[data.len() - 1].0;
 
+    // a 30-day round starting when the data ends, settled on its last 7 days
+    let round_start_timestamp = end_timestamp;
+    let round_end_timestamp = end_timestamp + 3600 * 24 * 30;
+    let settlement = SettlementSpec::default();
+    let horizon = round_horizon(round_start_timestamp, round_end_timestamp, &settlement).unwrap();
+
     // run rust code in host
     // ensure convergence in host
     let data_with_timestamps = convert_data_to_vec_of_tuples(data.clone(), start_timestamp);
-    let res = calculate_reserve_price(&data_with_timestamps, 15000, 720);
+    let res = calculate_reserve_price(&data_with_timestamps, 15000, &horizon);
     // println!("res: {:?}", res);
     // create input for guest
     println!("original reserve price: {:?}", res.reserve_price);
@@ -42,6 +72,10 @@ fn main() {
     let input = SimulatePriceVerifyPositionInput {
         start_timestamp,
         end_timestamp,
+        round_start_timestamp,
+        round_end_timestamp,
+        settlement,
+        fee_model: FeeModelKind::MeanRevertingJump,
         positions: res.positions,
         pt: convert_array1_to_dvec(res.pt),
         pt_1: convert_array1_to_dvec(res.pt_1),
@@ -49,20 +83,94 @@ fn main() {
         de_seasonalised_detrended_log_base_fee: convert_array1_to_dvec(
             res.de_seasonalised_detrended_log_base_fee,
         ),
-        n_periods: 720,
+        n_periods: horizon.n_periods,
         num_paths: 4000,
         season_param: convert_array1_to_dvec(res.season_param),
         twap_7d: res.twap_7d,
         slope: res.slope,
         intercept: res.intercept,
+        payoff: PayoffKind::default(),
         reserve_price: res.reserve_price,
+        reserve_price_grid: None,
         tolerance: 5.0, // 5%
         data_length: data.len(),
     };
 
-    let (receipt, _simulate_price_res) = simulate_price_verify_position(input);
+    #[cfg(feature = "columnar")]
+    export_divergence(&input, &convert_array2_to_dmatrix(res.simulated_log_prices));
+
+    let (receipt, simulate_price_res) = simulate_price_verify_position(input);
 
     receipt
         .verify(SIMULATE_PRICE_VERIFY_POSITION_FLOATING_GUEST_ID)
         .unwrap();
+    println!("mrj_params: {:?}", simulate_price_res.mrj_params);
+}
+
+/// Repeats the guest's pricing of `input` on the host. When its reserve price is not within
+/// tolerance of the original one, which would fail the proof, writes both simulated log
+/// prices to `DIVERGENCE_DIR` as Parquet for a notebook to compare.
+#[cfg(feature = "columnar")]
+fn export_divergence(
+    input: &SimulatePriceVerifyPositionInput,
+    original_simulated_log_prices: &DMatrix<f64>,
+) {
+    let horizon = round_horizon(
+        input.round_start_timestamp,
+        input.round_end_timestamp,
+        &input.settlement,
+    )
+    .unwrap();
+    let (_, de_seasonalized_detrended_simulated_prices) = simulate_price_verify_position_on_host(
+        input.fee_model,
+        &input.positions,
+        &input.pt,
+        &input.pt_1,
+        input.gradient_tolerance,
+        &input.de_seasonalised_detrended_log_base_fee,
+        input.n_periods,
+        input.num_paths,
+    );
+    let simulated_log_prices = calculate_simulated_log_prices(
+        input.start_timestamp,
+        input.end_timestamp,
+        &input.season_param,
+        &de_seasonalized_detrended_simulated_prices,
+        &input.twap_7d,
+        input.slope,
+        input.intercept,
+        input.data_length,
+        input.num_paths,
+        input.n_periods,
+    )
+    .unwrap();
+    let reserve_price = calculated_reserve_price_for_round(
+        &simulated_log_prices,
+        &input.twap_7d,
+        &horizon,
+        &input.payoff,
+    )
+    .unwrap();
+
+    if error_bound_f64(input.reserve_price, reserve_price, input.tolerance) {
+        return;
+    }
+
+    println!(
+        "floating reserve price {:?} diverges from the original {:?}",
+        reserve_price, input.reserve_price
+    );
+    std::fs::create_dir_all(DIVERGENCE_DIR).unwrap();
+    let format = ColumnarFormat::Parquet;
+    for (name, paths) in [
+        (
+            "original_simulated_log_prices",
+            original_simulated_log_prices,
+        ),
+        ("floating_simulated_log_prices", &simulated_log_prices),
+    ] {
+        let file_name = format!("{}/{}.{}", DIVERGENCE_DIR, name, format.extension());
+        write_paths(&file_name, paths, format).unwrap();
+        println!("wrote {}", file_name);
+    }
 }
@@ -0,0 +1,23 @@
+use common::tests::mock::get_max_return_input_data;
+use core::TailRiskInput;
+use tail_risk_floating::tail_risk;
+use tail_risk_floating_methods::TAIL_RISK_FLOATING_GUEST_ID;
+
+fn main() {
+    let data = get_max_return_input_data();
+    let input = TailRiskInput {
+        data: data.iter().map(|x| x.1).collect::<Vec<f64>>(),
+        quantiles: vec![0.95, 0.99],
+    };
+    let (receipt, res) = tail_risk(input);
+
+    receipt.verify(TAIL_RISK_FLOATING_GUEST_ID).unwrap();
+    println!("quantiles: {:?}", res.quantiles);
+    println!("value_at_risk: {:?}", res.value_at_risk);
+    println!(
+        "conditional_value_at_risk: {:?}",
+        res.conditional_value_at_risk
+    );
+    println!("min_return: {:?}", res.min_return);
+    println!("max_drawdown: {:?}", res.max_drawdown);
+}
@@ -0,0 +1,41 @@
+use common::{
+    floating_point::{block_base_fee_samples, calculate_time_weighted_twap},
+    tests::mock::get_mock_blocks,
+    window::SECONDS_IN_HOUR,
+};
+use core::{BlockBaseFee, TimeWeightedTwapErrorBoundInput};
+use time_weighted_twap_error_bound_floating::calculate_time_weighted_twap as prove_time_weighted_twap;
+use time_weighted_twap_error_bound_floating_methods::TIME_WEIGHTED_TWAP_ERROR_BOUND_FLOATING_GUEST_ID;
+
+fn main() {
+    // one day of 12s blocks, plus a bounding block on either side
+    let start_timestamp = 1708833600;
+    let end_timestamp = start_timestamp + 24 * SECONDS_IN_HOUR;
+    let blocks = get_mock_blocks(start_timestamp as u64, 24);
+
+    let samples = block_base_fee_samples(&blocks, start_timestamp, end_timestamp).unwrap();
+    let twap_result = calculate_time_weighted_twap(&samples, end_timestamp).unwrap();
+
+    println!("time-weighted twap: {:?}", twap_result);
+
+    let input = TimeWeightedTwapErrorBoundInput {
+        blocks: blocks
+            .into_iter()
+            .map(|(number, timestamp, base_fee)| BlockBaseFee {
+                number,
+                timestamp,
+                base_fee,
+            })
+            .collect(),
+        start_timestamp,
+        end_timestamp,
+        twap_tolerance: 1.0,
+        twap_result,
+    };
+
+    let (receipt, _res) = prove_time_weighted_twap(input);
+
+    receipt
+        .verify(TIME_WEIGHTED_TWAP_ERROR_BOUND_FLOATING_GUEST_ID)
+        .unwrap();
+}
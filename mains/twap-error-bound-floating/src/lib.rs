@@ -1,10 +1,10 @@
-use core::TwapErrorBoundInput;
+use core::{TwapErrorBoundInput, TwapErrorBoundOutput};
 use risc0_zkvm::{default_prover, ExecutorEnv, Receipt};
 use std::thread;
 use std::time::Duration;
 use twap_error_bound_floating_methods::TWAP_ERROR_BOUND_FLOATING_GUEST_ELF;
 
-pub fn calculate_twap(input: TwapErrorBoundInput) -> (Receipt, TwapErrorBoundInput) {
+pub fn calculate_twap(input: TwapErrorBoundInput) -> (Receipt, TwapErrorBoundOutput) {
     let prover = default_prover();
 
     const MAX_RETRIES: u32 = 10;
@@ -26,7 +26,7 @@ pub fn calculate_twap(input: TwapErrorBoundInput) -> (Receipt, TwapErrorBoundInp
         match prover.prove(env, TWAP_ERROR_BOUND_FLOATING_GUEST_ELF) {
             Ok(prove_info) => {
                 let receipt = prove_info.receipt;
-                let res: TwapErrorBoundInput = receipt.journal.decode().unwrap();
+                let res: TwapErrorBoundOutput = receipt.journal.decode().unwrap();
                 eprintln!(
                     "calculate_twap: Proof generation succeeded on attempt {}",
                     attempt
@@ -0,0 +1,506 @@
+use common::convert_felt_to_f64_checked;
+use core::{PayoffOutput, ProofCompositionOutput, SignedFixedPointOutput};
+use eyre::{anyhow as err, Result};
+use proof_composition_twap_maxreturn_reserveprice_floating_hashing_methods::PROOF_COMPOSITION_TWAP_MAXRETURN_RESERVEPRICE_FLOATING_HASHING_GUEST_ID;
+use risc0_zkvm::Receipt;
+use starknet_core::types::Felt;
+use std::fs;
+
+/// Values the decoded journal is checked against. Every field is optional so that
+/// callers only assert what they actually know about the round.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExpectedJournal {
+    pub data_8_months_hash: Option<[u32; 8]>,
+    pub start_timestamp: Option<i64>,
+    pub end_timestamp: Option<i64>,
+    pub min_reserve_price: Option<f64>,
+    pub max_reserve_price: Option<f64>,
+}
+
+/// Receipts are stored with bincode, which is how the proof composition host persists them.
+pub fn load_receipt(path: &str) -> Result<Receipt> {
+    let bytes = fs::read(path).map_err(|e| err!("Cannot read receipt file '{}': {}", path, e))?;
+    bincode::deserialize(&bytes).map_err(|e| err!("Cannot decode receipt '{}': {}", path, e))
+}
+
+pub fn save_receipt(receipt: &Receipt, path: &str) -> Result<()> {
+    let bytes = bincode::serialize(receipt).map_err(|e| err!("Cannot encode receipt: {}", e))?;
+    fs::write(path, bytes).map_err(|e| err!("Cannot write receipt file '{}': {}", path, e))
+}
+
+/// Verifies the seal against the proof composition image ID and decodes the journal.
+pub fn verify_receipt(receipt: &Receipt) -> Result<ProofCompositionOutput> {
+    receipt
+        .verify(PROOF_COMPOSITION_TWAP_MAXRETURN_RESERVEPRICE_FLOATING_HASHING_GUEST_ID)
+        .map_err(|e| {
+            err!(
+                "Receipt does not verify against the proof composition image ID: {}",
+                e
+            )
+        })?;
+
+    receipt
+        .journal
+        .decode::<ProofCompositionOutput>()
+        .map_err(|e| err!("Journal is not a ProofCompositionOutput: {}", e))
+}
+
+/// Checks the decoded journal against the expected values, failing on the first mismatch.
+pub fn check_journal(output: &ProofCompositionOutput, expected: &ExpectedJournal) -> Result<()> {
+    if let Some(hash) = expected.data_8_months_hash {
+        if output.data_8_months_hash != hash {
+            return Err(err!(
+                "data_8_months_hash mismatch: journal has {}, expected {}",
+                format_hash(&output.data_8_months_hash),
+                format_hash(&hash)
+            ));
+        }
+    }
+
+    if let Some(start_timestamp) = expected.start_timestamp {
+        if output.start_timestamp != start_timestamp {
+            return Err(err!(
+                "start_timestamp mismatch: journal has {}, expected {}",
+                output.start_timestamp,
+                start_timestamp
+            ));
+        }
+    }
+
+    if let Some(end_timestamp) = expected.end_timestamp {
+        if output.end_timestamp != end_timestamp {
+            return Err(err!(
+                "end_timestamp mismatch: journal has {}, expected {}",
+                output.end_timestamp,
+                end_timestamp
+            ));
+        }
+    }
+
+    if expected.min_reserve_price.is_some() || expected.max_reserve_price.is_some() {
+        let reserve_price = decode_fixed_packed_hex(&output.reserve_price)?;
+
+        if let Some(min_reserve_price) = expected.min_reserve_price {
+            if reserve_price < min_reserve_price {
+                return Err(err!(
+                    "reserve_price {} is below the expected minimum {}",
+                    reserve_price,
+                    min_reserve_price
+                ));
+            }
+        }
+
+        if let Some(max_reserve_price) = expected.max_reserve_price {
+            if reserve_price > max_reserve_price {
+                return Err(err!(
+                    "reserve_price {} is above the expected maximum {}",
+                    reserve_price,
+                    max_reserve_price
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Verifies the receipt and checks its journal against the expected values.
+pub fn verify(receipt: &Receipt, expected: &ExpectedJournal) -> Result<ProofCompositionOutput> {
+    let output = verify_receipt(receipt)?;
+    check_journal(&output, expected)?;
+    Ok(output)
+}
+
+/// Decodes a packed UFixedPoint123x128 hex string (integer part in the high 128 bits,
+/// fractional part in the low 128 bits) as committed by the composition guest.
+pub fn decode_fixed_packed_hex(value: &str) -> Result<f64> {
+    // Felt::from_hex panics on more than 64 digits instead of failing
+    let digits = value.trim_start_matches("0x");
+    if digits.is_empty() || digits.len() > 64 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(err!("Invalid fixed point hex string '{}'", value));
+    }
+
+    let felt = Felt::from_hex(value)
+        .map_err(|e| err!("Invalid fixed point hex string '{}': {}", value, e))?;
+    Ok(convert_felt_to_f64_checked(felt)?.value)
+}
+
+/// Decodes a value committed as its sign and packed magnitude.
+pub fn decode_signed_fixed_packed_hex(value: &SignedFixedPointOutput) -> Result<f64> {
+    let magnitude = decode_fixed_packed_hex(&value.magnitude)?;
+    Ok(if value.negative {
+        -magnitude
+    } else {
+        magnitude
+    })
+}
+
+/// Parses a 32 byte hex digest into the big-endian `[u32; 8]` words used by the journal.
+pub fn parse_hash(value: &str) -> Result<[u32; 8]> {
+    let stripped = value.trim_start_matches("0x");
+    if stripped.len() != 64 {
+        return Err(err!(
+            "Expected 64 hex characters for a hash, got {} in '{}'",
+            stripped.len(),
+            value
+        ));
+    }
+
+    let mut hash = [0u32; 8];
+    for (word, chunk) in hash.iter_mut().zip(stripped.as_bytes().chunks(8)) {
+        let chunk =
+            std::str::from_utf8(chunk).map_err(|e| err!("Invalid hash '{}': {}", value, e))?;
+        *word =
+            u32::from_str_radix(chunk, 16).map_err(|e| err!("Invalid hash '{}': {}", value, e))?;
+    }
+
+    Ok(hash)
+}
+
+pub fn format_hash(hash: &[u32; 8]) -> String {
+    let words: String = hash.iter().map(|word| format!("{:08x}", word)).collect();
+    format!("0x{}", words)
+}
+
+pub fn format_payoff(payoff: &PayoffOutput) -> Result<String> {
+    Ok(match payoff {
+        PayoffOutput::CappedCall { cap_level } => {
+            format!(
+                "capped call, cap level {}",
+                decode_fixed_packed_hex(cap_level)?
+            )
+        }
+        PayoffOutput::CappedPut { cap_level } => {
+            format!(
+                "capped put, cap level {}",
+                decode_fixed_packed_hex(cap_level)?
+            )
+        }
+        PayoffOutput::Digital { payout } => {
+            format!("digital, payout {}", decode_fixed_packed_hex(payout)?)
+        }
+        PayoffOutput::VarianceSwap => "variance swap".to_string(),
+    })
+}
+
+/// Human-readable rendering of the journal with the fixed point fields decoded.
+pub fn format_journal(output: &ProofCompositionOutput) -> Result<String> {
+    let mut lines = vec![
+        format!(
+            "data_8_months_hash:            {}",
+            format_hash(&output.data_8_months_hash)
+        ),
+        format!(
+            "data_8_months_hash_config:     {:?} over batches of {}",
+            output.data_8_months_hash_config.scheme, output.data_8_months_hash_config.batch_size
+        ),
+        format!(
+            "data_8_months_merkle_root:     {}",
+            match &output.data_8_months_merkle_root {
+                Some(merkle_root) => format_hash(merkle_root),
+                None => "not known, hashed by the rolling guest".to_string(),
+            }
+        ),
+        format!(
+            "data_8_months_block_range:     {}",
+            match &output.data_8_months_block_range {
+                Some(block_range) => format!(
+                    "blocks {} to {}, digest {}",
+                    block_range.first_block,
+                    block_range.last_block,
+                    format_hash(&block_range.blocks_digest)
+                ),
+                None => "not aggregated from blocks".to_string(),
+            }
+        ),
+        format!("start_timestamp:               {}", output.start_timestamp),
+        format!("end_timestamp:                 {}", output.end_timestamp),
+        format!(
+            "reserve_price_start_timestamp: {}",
+            output.reserve_price_start_timestamp
+        ),
+        format!(
+            "reserve_price_end_timestamp:   {}",
+            output.reserve_price_end_timestamp
+        ),
+        format!(
+            "round_start_timestamp:         {}",
+            output.round_start_timestamp
+        ),
+        format!(
+            "round_end_timestamp:           {}",
+            output.round_end_timestamp
+        ),
+        format!(
+            "settlement_twap_window:        {}h",
+            output.settlement.twap_window_hours
+        ),
+        format!(
+            "payoff:                        {}",
+            format_payoff(&output.payoff)?
+        ),
+        format!(
+            "twap_start_timestamp:          {}",
+            output.twap_start_timestamp
+        ),
+        format!(
+            "twap_end_timestamp:            {}",
+            output.twap_end_timestamp
+        ),
+        format!("twap_kind:                     {:?}", output.twap_kind),
+        format!(
+            "max_return_start_timestamp:    {}",
+            output.max_return_start_timestamp
+        ),
+        format!(
+            "max_return_end_timestamp:      {}",
+            output.max_return_end_timestamp
+        ),
+        format!("fee_model:                     {:?}", output.fee_model),
+    ];
+
+    for (name, value) in [
+        ("reserve_price", &output.reserve_price),
+        ("twap_result", &output.twap_result),
+        ("max_return", &output.max_return),
+        ("realized_volatility", &output.realized_volatility),
+        ("floating_point_tolerance", &output.floating_point_tolerance),
+        ("reserve_price_tolerance", &output.reserve_price_tolerance),
+        ("twap_tolerance", &output.twap_tolerance),
+        ("gradient_tolerance", &output.gradient_tolerance),
+    ] {
+        lines.push(format!(
+            "{:<31}{} ({})",
+            format!("{}:", name),
+            decode_fixed_packed_hex(value)?,
+            value
+        ));
+    }
+
+    // alternative reference prices are only committed when the composition verified them
+    let mut references = vec![];
+    if let Some(value) = &output.geometric_twap_result {
+        references.push(("geometric_twap_result".to_string(), value));
+    }
+    if let Some(rolling_median) = &output.rolling_median {
+        references.push((
+            format!("rolling_median_{}h", rolling_median.window),
+            &rolling_median.rolling_median_result,
+        ));
+    }
+    if let Some(ema) = &output.ema {
+        references.push((
+            format!("ema_{}", decode_fixed_packed_hex(&ema.alpha)?),
+            &ema.ema_result,
+        ));
+    }
+    // so are the tail-risk metrics, whose returns are committed as gross returns 1 + r
+    if let Some(tail_risk) = &output.tail_risk {
+        for ((quantile, value_at_risk), conditional_value_at_risk) in tail_risk
+            .quantiles
+            .iter()
+            .zip(&tail_risk.value_at_risk)
+            .zip(&tail_risk.conditional_value_at_risk)
+        {
+            let quantile = decode_fixed_packed_hex(quantile)?;
+            references.push((format!("value_at_risk_{}", quantile), value_at_risk));
+            references.push((
+                format!("conditional_value_at_risk_{}", quantile),
+                conditional_value_at_risk,
+            ));
+        }
+        references.push(("min_return".to_string(), &tail_risk.min_return));
+        references.push(("max_drawdown".to_string(), &tail_risk.max_drawdown));
+    }
+    // and the reserve price grid, one line per horizon and strike level
+    if let Some(grid) = &output.reserve_price_grid {
+        for (horizon, reserve_prices) in grid.horizons.iter().zip(&grid.reserve_prices) {
+            for (strike_level, reserve_price) in grid.strike_levels.iter().zip(reserve_prices) {
+                references.push((
+                    format!(
+                        "reserve_price_{}h_{}",
+                        horizon,
+                        decode_fixed_packed_hex(strike_level)?
+                    ),
+                    reserve_price,
+                ));
+            }
+        }
+    }
+    for (name, value) in references {
+        lines.push(format!(
+            "{:<31}{} ({})",
+            format!("{}:", name),
+            decode_fixed_packed_hex(value)?,
+            value
+        ));
+    }
+
+    let mut mrj_params = Vec::new();
+    if let Some(params) = &output.mrj_params {
+        mrj_params.push(("mrj_alpha", &params.alpha));
+        mrj_params.push(("mrj_kappa", &params.kappa));
+        mrj_params.push(("mrj_mu_j", &params.mu_j));
+        mrj_params.push(("mrj_sigma", &params.sigma));
+        mrj_params.push(("mrj_sigma_j", &params.sigma_j));
+        mrj_params.push(("mrj_lambda", &params.lambda));
+    }
+    for (name, value) in mrj_params {
+        lines.push(format!(
+            "{:<31}{} ({}{})",
+            format!("{}:", name),
+            decode_signed_fixed_packed_hex(value)?,
+            if value.negative { "-" } else { "" },
+            value.magnitude
+        ));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{floating_point::FeeModelKind, window::SettlementSpec, HashConfig};
+    use core::TwapKind;
+
+    // 2.5 and 1.25 as packed UFixedPoint123x128
+    const RESERVE_PRICE: &str =
+        "0x0000000000000000000000000000000280000000000000000000000000000000";
+    const TWAP_RESULT: &str = "0x0000000000000000000000000000000140000000000000000000000000000000";
+    const HASH: &str = "0x123456782345678934567891456789ab56789abc6789abcd789abcde89abcdef";
+
+    fn journal() -> ProofCompositionOutput {
+        ProofCompositionOutput {
+            data_8_months_hash: parse_hash(HASH).unwrap(),
+            start_timestamp: 1672531200,
+            end_timestamp: 1704067200,
+            reserve_price_start_timestamp: 1672531200,
+            reserve_price_end_timestamp: 1704067200,
+            reserve_price: RESERVE_PRICE.to_string(),
+            twap_start_timestamp: 1672531200,
+            twap_end_timestamp: 1704067200,
+            twap_result: TWAP_RESULT.to_string(),
+            max_return_start_timestamp: 1672531200,
+            max_return_end_timestamp: 1704067200,
+            max_return: TWAP_RESULT.to_string(),
+            floating_point_tolerance: TWAP_RESULT.to_string(),
+            reserve_price_tolerance: TWAP_RESULT.to_string(),
+            twap_tolerance: TWAP_RESULT.to_string(),
+            gradient_tolerance: TWAP_RESULT.to_string(),
+            round_start_timestamp: 1704067200,
+            round_end_timestamp: 1706659200,
+            settlement: SettlementSpec::default(),
+            payoff: PayoffOutput::VarianceSwap,
+            reserve_price_grid: None,
+            data_8_months_hash_config: HashConfig::default(),
+            data_8_months_merkle_root: Some(parse_hash(HASH).unwrap()),
+            data_8_months_block_range: None,
+            twap_kind: TwapKind::Arithmetic,
+            geometric_twap_result: None,
+            rolling_median: None,
+            ema: None,
+            tail_risk: None,
+            fee_model: FeeModelKind::MeanRevertingJump,
+            mrj_params: None,
+            realized_volatility: TWAP_RESULT.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_hash() {
+        let words = parse_hash(HASH).unwrap();
+        assert_eq!(words[0], 0x12345678);
+        assert_eq!(words[7], 0x89abcdef);
+        assert_eq!(format_hash(&words), HASH);
+        // the prefix is optional
+        assert_eq!(parse_hash(&HASH[2..]).unwrap(), words);
+    }
+
+    #[test]
+    fn test_parse_hash_rejects_malformed_hashes() {
+        // a word short, a word too long, and empty
+        assert!(parse_hash(&HASH[..58]).is_err());
+        assert!(parse_hash(&format!("{}00000000", HASH)).is_err());
+        assert!(parse_hash("0x").is_err());
+        // not hex
+        assert!(parse_hash(&HASH.replace('a', "g")).is_err());
+        // 64 bytes, but not 64 hex characters
+        assert!(parse_hash(&format!("0x{}é", &HASH[2..64])).is_err());
+    }
+
+    #[test]
+    fn test_decode_fixed_packed_hex() {
+        assert_eq!(decode_fixed_packed_hex(RESERVE_PRICE).unwrap(), 2.5);
+        assert_eq!(
+            decode_signed_fixed_packed_hex(&SignedFixedPointOutput {
+                negative: true,
+                magnitude: TWAP_RESULT.to_string(),
+            })
+            .unwrap(),
+            -1.25
+        );
+        assert!(decode_fixed_packed_hex("0xnot hex").is_err());
+        assert!(decode_fixed_packed_hex("").is_err());
+        // wider than a felt
+        assert!(decode_fixed_packed_hex(&format!("{}00", RESERVE_PRICE)).is_err());
+    }
+
+    #[test]
+    fn test_check_journal() {
+        let journal = journal();
+        assert!(check_journal(&journal, &ExpectedJournal::default()).is_ok());
+        assert!(check_journal(
+            &journal,
+            &ExpectedJournal {
+                data_8_months_hash: Some(journal.data_8_months_hash),
+                start_timestamp: Some(1672531200),
+                end_timestamp: Some(1704067200),
+                min_reserve_price: Some(2.0),
+                max_reserve_price: Some(3.0),
+            }
+        )
+        .is_ok());
+
+        let mut other_hash = journal.data_8_months_hash;
+        other_hash[0] ^= 1;
+        for expected in [
+            ExpectedJournal {
+                data_8_months_hash: Some(other_hash),
+                ..ExpectedJournal::default()
+            },
+            ExpectedJournal {
+                start_timestamp: Some(1672531201),
+                ..ExpectedJournal::default()
+            },
+            ExpectedJournal {
+                end_timestamp: Some(1704067201),
+                ..ExpectedJournal::default()
+            },
+            ExpectedJournal {
+                min_reserve_price: Some(2.6),
+                ..ExpectedJournal::default()
+            },
+            ExpectedJournal {
+                max_reserve_price: Some(2.4),
+                ..ExpectedJournal::default()
+            },
+        ] {
+            assert!(check_journal(&journal, &expected).is_err());
+        }
+
+        // a reserve price that is not packed fixed point cannot be checked
+        let malformed = ProofCompositionOutput {
+            reserve_price: "2.5".to_string(),
+            ..journal
+        };
+        assert!(check_journal(
+            &malformed,
+            &ExpectedJournal {
+                min_reserve_price: Some(2.0),
+                ..ExpectedJournal::default()
+            }
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use eyre::{anyhow as err, Result};
+use std::{env, process};
+use verify_proof_composition::{
+    check_journal, format_journal, load_receipt, parse_hash, verify_receipt, ExpectedJournal,
+};
+
+const USAGE: &str = "usage: verify-proof-composition <receipt.bin> \
+[--data-hash <hex>] [--start-timestamp <i64>] [--end-timestamp <i64>] \
+[--min-reserve-price <f64>] [--max-reserve-price <f64>]";
+
+fn main() {
+    if let Err(e) = run(env::args().skip(1).collect()) {
+        eprintln!("verification failed: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run(args: Vec<String>) -> Result<()> {
+    let (receipt_path, expected) = parse_args(&args)?;
+
+    let receipt = load_receipt(&receipt_path)?;
+    let output = verify_receipt(&receipt)?;
+
+    println!("{}", format_journal(&output)?);
+
+    check_journal(&output, &expected)?;
+    println!("verification succeeded");
+
+    Ok(())
+}
+
+fn parse_args(args: &[String]) -> Result<(String, ExpectedJournal)> {
+    let mut receipt_path = None;
+    let mut expected = ExpectedJournal::default();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if !arg.starts_with("--") {
+            if receipt_path.replace(arg.clone()).is_some() {
+                return Err(err!("unexpected argument '{}'\n{}", arg, USAGE));
+            }
+            continue;
+        }
+
+        let value = iter
+            .next()
+            .ok_or_else(|| err!("missing value for '{}'\n{}", arg, USAGE))?;
+        match arg.as_str() {
+            "--data-hash" => expected.data_8_months_hash = Some(parse_hash(value)?),
+            "--start-timestamp" => expected.start_timestamp = Some(parse_value(arg, value)?),
+            "--end-timestamp" => expected.end_timestamp = Some(parse_value(arg, value)?),
+            "--min-reserve-price" => expected.min_reserve_price = Some(parse_value(arg, value)?),
+            "--max-reserve-price" => expected.max_reserve_price = Some(parse_value(arg, value)?),
+            _ => return Err(err!("unknown option '{}'\n{}", arg, USAGE)),
+        }
+    }
+
+    let receipt_path = receipt_path.ok_or_else(|| err!("missing receipt path\n{}", USAGE))?;
+    Ok((receipt_path, expected))
+}
+
+fn parse_value<T: std::str::FromStr>(name: &str, value: &str) -> Result<T>
+where
+    T::Err: std::fmt::Display,
+{
+    value
+        .parse()
+        .map_err(|e| err!("invalid value '{}' for '{}': {}", value, name, e))
+}
@@ -1,5 +1,6 @@
-use core::AddTwap7dErrorBoundFloatingInput;
 use common::floating_point::{add_twap_7d, error_bound_vec};
+use common::hash_f64s;
+use core::{AddTwap7dErrorBoundFloatingInput, AddTwap7dErrorBoundFloatingOutput};
 use risc0_zkvm::guest::env;
 
 fn main() {
@@ -9,5 +10,9 @@ fn main() {
     let is_within_tolerance = error_bound_vec(&input.twap_7d, &res, input.tolerance);
     assert!(is_within_tolerance);
 
-    env::commit(&input);
+    env::commit(&AddTwap7dErrorBoundFloatingOutput {
+        data_digest: hash_f64s(&input.data),
+        twap_7d_digest: hash_f64s(&input.twap_7d),
+        tolerance: input.tolerance,
+    });
 }
@@ -0,0 +1,50 @@
+use common::{
+    convert_felt_to_f64_checked, convert_u32_array_to_hash, hash_f64s, validate_hourly_avg_fees,
+    verify_avg_fees_range,
+};
+use core::{AvgFeesRangeInput, AvgFeesRangeOutput};
+use risc0_zkvm::guest::env;
+
+fn main() {
+    let input: AvgFeesRangeInput = env::read();
+
+    let records = input
+        .inputs
+        .iter()
+        .map(|x| (x.timestamp, x.data_points, x.avg_fee))
+        .collect::<Vec<_>>();
+
+    // the window must be contiguous hours on its own, the Merkle proof then places it
+    // at start_index among the leaves committed to by the root
+    validate_hourly_avg_fees(&records).unwrap();
+
+    let siblings = input
+        .siblings
+        .iter()
+        .map(convert_u32_array_to_hash)
+        .collect::<Vec<_>>();
+    verify_avg_fees_range(
+        &convert_u32_array_to_hash(&input.merkle_root),
+        input.leaf_count as usize,
+        input.start_index as usize,
+        &records,
+        &siblings,
+    )
+    .unwrap();
+
+    // convert felts to f64s, rejecting felts that are not UFixedPoint123x128 fees
+    let f64_inputs = input
+        .inputs
+        .iter()
+        .map(|x| convert_felt_to_f64_checked(x.avg_fee).unwrap().value)
+        .collect::<Vec<_>>();
+
+    env::commit(&AvgFeesRangeOutput {
+        merkle_root: input.merkle_root,
+        leaf_count: input.leaf_count,
+        start_index: input.start_index,
+        f64_inputs_digest: hash_f64s(&f64_inputs),
+        first_timestamp: records[0].0,
+        last_timestamp: records[records.len() - 1].0,
+    });
+}
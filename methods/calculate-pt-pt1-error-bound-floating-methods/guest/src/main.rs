@@ -1,5 +1,6 @@
 use common::floating_point::{error_bound_dvec, pre_minimize};
-use core::CalculatePtPt1ErrorBoundFloatingInput;
+use common::hash_f64s;
+use core::{CalculatePtPt1ErrorBoundFloatingInput, CalculatePtPt1ErrorBoundFloatingOutput};
 use risc0_zkvm::guest::env;
 
 fn main() {
@@ -12,5 +13,12 @@ fn main() {
     let is_within_tolerance_pt_1 = error_bound_dvec(&pt_1, &input.pt_1, input.tolerance);
     assert!(is_within_tolerance_pt_1);
 
-    env::commit(&input);
-}
\ No newline at end of file
+    env::commit(&CalculatePtPt1ErrorBoundFloatingOutput {
+        de_seasonalised_detrended_log_base_fee_digest: hash_f64s(
+            input.de_seasonalised_detrended_log_base_fee.as_slice(),
+        ),
+        pt_digest: hash_f64s(input.pt.as_slice()),
+        pt_1_digest: hash_f64s(input.pt_1.as_slice()),
+        tolerance: input.tolerance,
+    });
+}
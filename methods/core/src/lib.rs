@@ -23,7 +23,8 @@
 // When changing data volumes, update ALL of the following:
 // 1. This file: data_length field documentation (line 126)
 // 2. Hashing guest: methods/hashing-felts-methods/guest/src/main.rs (assertion on line 21)
-// 3. Proof composition guest: methods/proof-composition-.../guest/src/main.rs (line 109)
+// 3. Host timestamps: the proof composition guest derives every window length from the
+//    *_start_timestamp / *_end_timestamp fields of ProofCompositionInput
 // 4. Message handler: proving-service/.../proof_composition/mod.rs (REQUIRED_HOURS constant)
 // 5. Cairo contract: starknet-contracts/fossil-hash-store/src/lib.cairo (num_in_a_batch)
 //
@@ -31,13 +32,24 @@
 // ===========================
 // The ProofCompositionInput structure contains all data needed to verify 7 sub-proofs:
 // 1. Data Hashing - Verifies integrity of historical fee data
-// 2. Max Return - Calculates volatility from full historical data
-// 3. TWAP - Time-weighted average price from subset
+// 2. Max Return - Calculates volatility from full historical data, optionally with its
+//    tail-risk metrics (VaR, CVaR, min return and max drawdown)
+// 3. TWAP - Arithmetic or time-weighted average price from subset (see TwapKind)
 // 4. Seasonality Removal - Time series decomposition
 // 5. 7-day TWAP - Mean reversion level calculation
 // 6. Markov Transition Probabilities - Stochastic model parameters
-// 7. Price Simulation - Monte Carlo simulation and reserve price validation
+// 7. Price Simulation - Monte Carlo simulation and reserve price validation, committing
+//    the annualized MRJ parameters it simulated with
+//
+// Sub-guests commit their `*Output` struct rather than their input: every data vector
+// is replaced by its `common::hash_f64s` digest, so the composition guest verifies
+// against a few words per vector instead of re-serializing thousands of f64 values.
 
+use common::{
+    floating_point::{FeeModelKind, MrjParams, PayoffKind, ReservePriceGrid},
+    window::SettlementSpec,
+    HashConfig,
+};
 use nalgebra::DVector;
 use serde::{Deserialize, Serialize};
 use starknet_core::types::Felt;
@@ -49,6 +61,39 @@ pub struct AddTwap7dErrorBoundFloatingInput {
     pub tolerance: f64,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AddTwap7dErrorBoundFloatingOutput {
+    pub data_digest: [u32; 8],
+    pub twap_7d_digest: [u32; 8],
+    pub tolerance: f64,
+}
+
+/// Hourly average base fee record, matching the Cairo `AvgFees` struct stored by the
+/// fossil hash store.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AvgFees {
+    pub timestamp: u64,
+    pub data_points: u64,
+    pub avg_fee: Felt,
+}
+
+/// Block level base fee, as read from the block header.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct BlockBaseFee {
+    pub number: u64,
+    pub timestamp: u64,
+    pub base_fee: u64,
+}
+
+/// Consecutive blocks `first_block..=last_block` that hourly records were aggregated from,
+/// with the `common::hash_blocks` digest of their `BlockBaseFee`s.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct BlockRange {
+    pub first_block: u64,
+    pub last_block: u64,
+    pub blocks_digest: [u32; 8],
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct CalculatePtPt1ErrorBoundFloatingInput {
     pub de_seasonalised_detrended_log_base_fee: DVector<f64>,
@@ -57,15 +102,234 @@ pub struct CalculatePtPt1ErrorBoundFloatingInput {
     pub tolerance: f64,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CalculatePtPt1ErrorBoundFloatingOutput {
+    pub de_seasonalised_detrended_log_base_fee_digest: [u32; 8],
+    pub pt_digest: [u32; 8],
+    pub pt_1_digest: [u32; 8],
+    pub tolerance: f64,
+}
+
+/// Which hashing guest proved `ProofCompositionInput::data_8_months_hash`, with the parts
+/// of its journal that the proof composition can't recompute from the f64 fees.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum DataHashingProof {
+    /// `hashing-avg-fees` over the whole window, and the Merkle root it committed.
+    Full { merkle_root: [u32; 8] },
+    /// Latest round of `hashing-avg-fees-rolling`, and the batch hashes it committed. It
+    /// must have been proven with `HASHING_AVG_FEES_ROLLING_GUEST_ID` as `rolling_image_id`.
+    Rolling { batch_hashes: Vec<[u32; 8]> },
+}
+
+/// Hourly fees whose exponential moving average with smoothing factor `alpha` ends at
+/// `ema_result`, as computed by `common::floating_point::calculate_ema`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EmaErrorBoundInput {
+    pub avg_hourly_gas_fee: Vec<f64>,
+    pub alpha: f64,
+    pub tolerance: f64,
+    pub ema_result: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EmaErrorBoundOutput {
+    pub avg_hourly_gas_fee_digest: [u32; 8],
+    pub alpha: f64,
+    pub tolerance: f64,
+    pub ema_result: f64,
+}
+
+/// EMA reference price proven by the proof composition over the TWAP window.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EmaReference {
+    pub alpha: f64,
+    pub ema_result: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EmaReferenceOutput {
+    pub alpha: String,
+    pub ema_result: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GeometricTwapErrorBoundInput {
+    pub avg_hourly_gas_fee: Vec<f64>,
+    pub tolerance: f64,
+    pub geometric_twap_result: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GeometricTwapErrorBoundOutput {
+    pub avg_hourly_gas_fee_digest: [u32; 8],
+    pub tolerance: f64,
+    pub geometric_twap_result: f64,
+}
+
+/// Inputs of `calculate_reserve_price`, with the fee model and fitted positions of
+/// `SimulatePriceVerifyPositionInput` in place of simulated fees, and the seed the guest
+/// simulates the fee model and the stochastic trend from.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GreeksInput {
+    pub start_timestamp: i64,
+    pub end_timestamp: i64,
+    pub season_param: DVector<f64>,
+    pub fee_model: FeeModelKind,
+    pub positions: Vec<f64>,
+    pub gradient_tolerance: f64,
+    pub de_seasonalised_detrended_log_base_fee: DVector<f64>,
+    // Option round simulated hour by hour, and how it settles
+    pub round_start_timestamp: i64,
+    pub round_end_timestamp: i64,
+    pub settlement: SettlementSpec,
+    pub num_paths: usize,
+    pub twap_7d: Vec<f64>,
+    pub slope: f64,
+    pub intercept: f64,
+    pub data_length: usize,
+    pub payoff: PayoffKind,
+    pub seed: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GreeksOutput {
+    pub start_timestamp: i64,
+    pub end_timestamp: i64,
+    pub season_param_digest: [u32; 8],
+    // Positions verified as a fit of the de-seasonalised, detrended log base fee
+    pub fee_model: FeeModelKind,
+    pub positions_digest: [u32; 8],
+    pub gradient_tolerance: f64,
+    pub de_seasonalised_detrended_log_base_fee_digest: [u32; 8],
+    // Option round simulated hour by hour, and how it settles
+    pub round_start_timestamp: i64,
+    pub round_end_timestamp: i64,
+    pub settlement: SettlementSpec,
+    pub n_periods: usize,
+    pub num_paths: usize,
+    pub twap_7d_digest: [u32; 8],
+    pub slope: f64,
+    pub intercept: f64,
+    pub data_length: usize,
+    pub payoff: PayoffKind,
+    pub seed: u64,
+    pub reserve_price: f64,
+    pub dual_delta: f64,
+    pub vega: f64,
+    pub theta: f64,
+    // Sensitivities to the MRJ diffusion and jump volatilities, for the MRJ model
+    pub sigma_vega: Option<f64>,
+    pub sigma_j_vega: Option<f64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct HashingAvgFeesInput {
+    pub inputs: Vec<AvgFees>,
+    pub hash_config: HashConfig,
+    // Journal of the hourly aggregation receipt that produced `inputs`, if any
+    pub aggregation: Option<HourlyAggregationOutput>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct HashingAvgFeesOutput {
+    pub hash: [u32; 8],
+    // Root of the `common::AvgFeesMerkleTree` over the same records, which
+    // `AvgFeesRangeInput` windows are proven against
+    pub merkle_root: [u32; 8],
+    pub f64_inputs_digest: [u32; 8],
+    pub first_timestamp: u64,
+    pub last_timestamp: u64,
+    pub hash_config: HashConfig,
+    pub block_range: Option<BlockRange>,
+}
+
+/// One round of the rolling `AvgFees` hashing. Without `previous`, `inputs` is hashed as the
+/// whole window. Otherwise `inputs` holds only the batches following the previous window,
+/// which replace as many of its oldest batches.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct HashingAvgFeesRollingInput {
+    pub inputs: Vec<AvgFees>,
+    pub hash_config: HashConfig,
+    pub previous: Option<HashingAvgFeesRollingOutput>,
+    // A guest can't embed its own image ID, so it is passed in and committed
+    pub rolling_image_id: [u32; 8],
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct HashingAvgFeesRollingOutput {
+    pub hash: [u32; 8],
+    pub batch_hashes: Vec<[u32; 8]>,
+    // `common::hash_f64s_in_batches` of the fees of the window, carried forward like
+    // batch_hashes, and their `common::hash_f64_batch_digests`
+    pub f64_batch_digests: Vec<[u32; 8]>,
+    pub f64_inputs_digest: [u32; 8],
+    pub first_timestamp: u64,
+    pub last_timestamp: u64,
+    pub hash_config: HashConfig,
+    pub rolling_image_id: [u32; 8],
+}
+
+/// A contiguous window of hourly `AvgFees` records, with the sibling hashes binding it to
+/// the Merkle root over the whole dataset (`common::AvgFeesMerkleTree`).
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AvgFeesRangeInput {
+    pub merkle_root: [u32; 8],
+    pub leaf_count: u64,
+    pub start_index: u64,
+    pub inputs: Vec<AvgFees>,
+    pub siblings: Vec<[u32; 8]>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AvgFeesRangeOutput {
+    pub merkle_root: [u32; 8],
+    pub leaf_count: u64,
+    pub start_index: u64,
+    pub f64_inputs_digest: [u32; 8],
+    pub first_timestamp: u64,
+    pub last_timestamp: u64,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct HashingFeltInput {
     pub inputs: Vec<Felt>,
+    pub hash_config: HashConfig,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct HashingFeltOutput {
     pub hash: [u32; 8],
-    pub f64_inputs: Vec<f64>,
+    pub f64_inputs_digest: [u32; 8],
+    pub hash_config: HashConfig,
+}
+
+/// RLP-encoded Ethereum block headers, oldest first, the last of which must hash to
+/// `anchor_hash`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct HeaderChainInput {
+    pub headers: Vec<Vec<u8>>,
+    pub anchor_hash: [u32; 8],
+}
+
+/// Base fees of a verified header chain. `parent_hash` is the parent of the first block,
+/// so that a chain ending there can be linked to this one.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct HeaderChainOutput {
+    pub parent_hash: [u32; 8],
+    pub first_block_hash: [u32; 8],
+    pub last_block_hash: [u32; 8],
+    pub blocks: Vec<BlockBaseFee>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct HourlyAggregationInput {
+    pub blocks: Vec<BlockBaseFee>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct HourlyAggregationOutput {
+    pub block_range: BlockRange,
+    pub avg_fees: Vec<AvgFees>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -73,38 +337,92 @@ pub struct MaxReturnInput {
     pub data: Vec<f64>,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MaxReturnOutput {
+    pub data_digest: [u32; 8],
+    pub max_return: f64,
+}
+
+/// Annualized MRJ parameters as committed by the proof composition.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MrjParamsOutput {
+    pub alpha: SignedFixedPointOutput,
+    pub kappa: SignedFixedPointOutput,
+    pub mu_j: SignedFixedPointOutput,
+    pub sigma: SignedFixedPointOutput,
+    pub sigma_j: SignedFixedPointOutput,
+    pub lambda: SignedFixedPointOutput,
+}
+
+/// Payoff priced by the proof composition, with its parameters as fixed point hex strings.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum PayoffOutput {
+    CappedCall { cap_level: String },
+    CappedPut { cap_level: String },
+    Digital { payout: String },
+    VarianceSwap,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct ProofCompositionInput {
     pub data_8_months: Vec<f64>,
     pub data_8_months_hash: [u32; 8],
+    pub data_8_months_hash_config: HashConfig,
+    // Hashing receipt data_8_months_hash is verified against
+    pub data_8_months_hashing: DataHashingProof,
+    // Blocks the hourly data was aggregated from, when it was proven from blocks
+    pub data_8_months_block_range: Option<BlockRange>,
     pub data_8_months_start_timestamp: i64,
     pub data_8_months_end_timestamp: i64,
     pub start_timestamp: i64,
     pub end_timestamp: i64,
-    // Specific timestamp ranges for each calculation type
+    // Specific timestamp ranges for each calculation type, as hour aligned [start, end)
+    // windows within [data_8_months_start_timestamp, data_8_months_end_timestamp)
     pub twap_start_timestamp: i64,
     pub twap_end_timestamp: i64,
     pub reserve_price_start_timestamp: i64,
     pub reserve_price_end_timestamp: i64,
     pub max_return_start_timestamp: i64,
     pub max_return_end_timestamp: i64,
+    // Option round the reserve price is for, as an hour aligned [start, end) range. The
+    // simulation horizon is one period per hour of the round, settled under settlement
+    pub round_start_timestamp: i64,
+    pub round_end_timestamp: i64,
+    pub settlement: SettlementSpec,
+    // Fee model positions were fitted to, and that the reserve price is simulated with
+    pub fee_model: FeeModelKind,
     pub positions: Vec<f64>,
     pub pt: DVector<f64>,
     pub pt_1: DVector<f64>,
     pub gradient_tolerance: f64,
     pub de_seasonalised_detrended_log_base_fee: DVector<f64>,
-    pub n_periods: usize,
     pub num_paths: usize,
     pub season_param: DVector<f64>,
     pub twap_7d: Vec<f64>,
     pub slope: f64,
     pub intercept: f64,
+    // Option the reserve price prices
+    pub payoff: PayoffKind,
     pub reserve_price: f64,
+    // Reserve prices across strikes and horizons of the same simulation, proven within
+    // reserve_price_tolerance when set
+    pub reserve_price_grid: Option<ReservePriceGrid>,
     pub floating_point_tolerance: f64,
     pub reserve_price_tolerance: f64,
     pub twap_tolerance: f64,
     pub twap_result: f64,
+    pub twap_kind: TwapKind,
+    // Hourly aggregation of the blocks of the TWAP window, which TwapKind::TimeWeighted is
+    // proven from
+    pub twap_aggregation: Option<HourlyAggregationOutput>,
+    // Alternative reference prices over the TWAP window, each proven within twap_tolerance
+    // when set
+    pub geometric_twap_result: Option<f64>,
+    pub rolling_median: Option<RollingMedianReference>,
+    pub ema: Option<EmaReference>,
     pub max_return: f64,
+    // Tail of the returns over the max return window, proven exactly when set
+    pub tail_risk: Option<TailRiskReport>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -125,6 +443,37 @@ pub struct ProofCompositionOutput {
     pub reserve_price_tolerance: String,
     pub twap_tolerance: String,
     pub gradient_tolerance: String,
+    // Fields below are appended after those of the original journal, whose byte layout
+    // `decode_journal` in pitchlake_verifier reads at fixed offsets.
+    // Option round reserve_price was simulated over, and how it settles
+    pub round_start_timestamp: i64,
+    pub round_end_timestamp: i64,
+    pub settlement: SettlementSpec,
+    // Merkle root over the records behind data_8_months_hash, binding `AvgFeesRangeOutput`
+    // windows to the same data. Not known when the hash was proven by the rolling guest
+    pub data_8_months_merkle_root: Option<[u32; 8]>,
+    // Option reserve_price was priced for
+    pub payoff: PayoffOutput,
+    // Reserve prices across strikes and horizons, if proven
+    pub reserve_price_grid: Option<ReservePriceGridOutput>,
+    // Batch size and hash function behind data_8_months_hash
+    pub data_8_months_hash_config: HashConfig,
+    // Blocks behind data_8_months_hash, if the hourly fees were proven from them
+    pub data_8_months_block_range: Option<BlockRange>,
+    // TWAP definition twap_result was proven under
+    pub twap_kind: TwapKind,
+    // Alternative reference prices over the TWAP window, if proven
+    pub geometric_twap_result: Option<String>,
+    pub rolling_median: Option<RollingMedianReferenceOutput>,
+    pub ema: Option<EmaReferenceOutput>,
+    // Tail-risk metrics over the max return window, if proven
+    pub tail_risk: Option<TailRiskReportOutput>,
+    // Model state behind reserve_price: the fee model of the simulation, its annualized
+    // parameters when it is the MRJ model, and the annualized realized volatility of the
+    // log fees of the reserve price window
+    pub fee_model: FeeModelKind,
+    pub mrj_params: Option<MrjParamsOutput>,
+    pub realized_volatility: String,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -137,10 +486,65 @@ pub struct RemoveSeasonalityErrorBoundFloatingInput {
     pub tolerance: f64,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RemoveSeasonalityErrorBoundFloatingOutput {
+    pub data_digest: [u32; 8],
+    pub slope: f64,
+    pub intercept: f64,
+    pub de_seasonalised_detrended_log_base_fee_digest: [u32; 8],
+    pub season_param_digest: [u32; 8],
+    pub tolerance: f64,
+}
+
+/// Reserve price grid as committed by the proof composition, in the order of
+/// `ReservePriceGrid`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ReservePriceGridOutput {
+    pub strike_levels: Vec<String>,
+    pub horizons: Vec<usize>,
+    pub reserve_prices: Vec<Vec<String>>,
+}
+
+/// Hourly fees whose median over the trailing `window` hours, i.e. the last value of
+/// `common::floating_point::calculate_rolling_median`, is `rolling_median_result`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RollingMedianErrorBoundInput {
+    pub avg_hourly_gas_fee: Vec<f64>,
+    pub window: usize,
+    pub tolerance: f64,
+    pub rolling_median_result: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RollingMedianErrorBoundOutput {
+    pub avg_hourly_gas_fee_digest: [u32; 8],
+    pub window: usize,
+    pub tolerance: f64,
+    pub rolling_median_result: f64,
+}
+
+/// Rolling median reference price proven by the proof composition over the TWAP window.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RollingMedianReference {
+    pub window: usize,
+    pub rolling_median_result: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RollingMedianReferenceOutput {
+    pub window: usize,
+    pub rolling_median_result: String,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct SimulatePriceVerifyPositionInput {
     pub start_timestamp: i64,
     pub end_timestamp: i64,
+    // Option round n_periods must cover hour by hour, and how it settles
+    pub round_start_timestamp: i64,
+    pub round_end_timestamp: i64,
+    pub settlement: SettlementSpec,
+    pub fee_model: FeeModelKind,
     pub positions: Vec<f64>,
     pub pt: DVector<f64>,
     pub pt_1: DVector<f64>,
@@ -152,7 +556,10 @@ pub struct SimulatePriceVerifyPositionInput {
     pub twap_7d: Vec<f64>,
     pub slope: f64,
     pub intercept: f64,
+    pub payoff: PayoffKind,
     pub reserve_price: f64,
+    // Host prices across strikes and horizons, each checked within tolerance when set
+    pub reserve_price_grid: Option<ReservePriceGrid>,
     pub tolerance: f64,
     // DEVELOPER NOTE: Data Length Configuration
     // =========================================
@@ -166,9 +573,127 @@ pub struct SimulatePriceVerifyPositionInput {
     pub data_length: usize,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SimulatePriceVerifyPositionOutput {
+    pub start_timestamp: i64,
+    pub end_timestamp: i64,
+    // Option round n_periods must cover hour by hour, and how it settles
+    pub round_start_timestamp: i64,
+    pub round_end_timestamp: i64,
+    pub settlement: SettlementSpec,
+    pub fee_model: FeeModelKind,
+    pub positions_digest: [u32; 8],
+    pub pt_digest: [u32; 8],
+    pub pt_1_digest: [u32; 8],
+    pub gradient_tolerance: f64,
+    pub de_seasonalised_detrended_log_base_fee_digest: [u32; 8],
+    pub n_periods: usize,
+    pub num_paths: usize,
+    pub season_param_digest: [u32; 8],
+    pub twap_7d_digest: [u32; 8],
+    pub slope: f64,
+    pub intercept: f64,
+    pub payoff: PayoffKind,
+    pub reserve_price: f64,
+    // Host prices across strikes and horizons, each checked within tolerance when set
+    pub reserve_price_grid: Option<ReservePriceGrid>,
+    pub tolerance: f64,
+    pub data_length: usize,
+    // Annualized from positions, as used by the simulation, for the MRJ model
+    pub mrj_params: Option<MrjParams>,
+}
+
+/// A value that may be negative, as its magnitude in the unsigned fixed point encoding.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SignedFixedPointOutput {
+    pub negative: bool,
+    pub magnitude: String,
+}
+
+/// Hourly fees of the max return window and the quantiles to report the historical VaR
+/// and CVaR of their TWAP-30d returns at, as computed by
+/// `common::floating_point::calculate_tail_risk`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TailRiskInput {
+    pub data: Vec<f64>,
+    pub quantiles: Vec<f64>,
+}
+
+/// `value_at_risk` and `conditional_value_at_risk` hold one value per quantile.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TailRiskOutput {
+    pub data_digest: [u32; 8],
+    pub quantiles: Vec<f64>,
+    pub value_at_risk: Vec<f64>,
+    pub conditional_value_at_risk: Vec<f64>,
+    pub min_return: f64,
+    pub max_drawdown: f64,
+}
+
+/// Tail-risk metrics proven by the proof composition over the max return window.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TailRiskReport {
+    pub quantiles: Vec<f64>,
+    pub value_at_risk: Vec<f64>,
+    pub conditional_value_at_risk: Vec<f64>,
+    pub min_return: f64,
+    pub max_drawdown: f64,
+}
+
+/// Returns are committed as gross returns `1 + r`, as the fixed point type is unsigned.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TailRiskReportOutput {
+    pub quantiles: Vec<String>,
+    pub value_at_risk: Vec<String>,
+    pub conditional_value_at_risk: Vec<String>,
+    pub min_return: String,
+    pub max_drawdown: String,
+}
+
+/// Consecutive blocks whose base fees, weighted by how long each was in effect over
+/// `[start_timestamp, end_timestamp)`, average to `twap_result`, as computed by
+/// `common::floating_point::calculate_time_weighted_twap` on `block_base_fee_samples`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TimeWeightedTwapErrorBoundInput {
+    pub blocks: Vec<BlockBaseFee>,
+    pub start_timestamp: i64,
+    pub end_timestamp: i64,
+    pub twap_tolerance: f64,
+    pub twap_result: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TimeWeightedTwapErrorBoundOutput {
+    // common::hash_blocks digest of the blocks, as committed by the hourly aggregation
+    pub blocks_digest: [u32; 8],
+    pub start_timestamp: i64,
+    pub end_timestamp: i64,
+    pub twap_tolerance: f64,
+    pub twap_result: f64,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct TwapErrorBoundInput {
     pub avg_hourly_gas_fee: Vec<f64>,
     pub twap_tolerance: f64,
     pub twap_result: f64,
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TwapErrorBoundOutput {
+    pub avg_hourly_gas_fee_digest: [u32; 8],
+    pub twap_tolerance: f64,
+    pub twap_result: f64,
+}
+
+/// TWAP definition proven by the proof composition.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TwapKind {
+    /// Arithmetic mean of the hourly fees, proven by the twap-error-bound-floating guest.
+    #[default]
+    Arithmetic,
+    /// Mean of the block base fees weighted by the time each was in effect, proven by the
+    /// time-weighted-twap-error-bound-floating guest from the blocks the hourly fees were
+    /// aggregated from.
+    TimeWeighted,
+}
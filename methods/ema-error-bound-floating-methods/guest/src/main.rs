@@ -0,0 +1,20 @@
+use common::floating_point::{calculate_ema, error_bound_f64};
+use common::hash_f64s;
+use core::{EmaErrorBoundInput, EmaErrorBoundOutput};
+use risc0_zkvm::guest::env;
+
+fn main() {
+    let data: EmaErrorBoundInput = env::read();
+    let ema = calculate_ema(&data.avg_hourly_gas_fee, data.alpha).unwrap();
+
+    let is_within_error_bound =
+        error_bound_f64(ema[ema.len() - 1], data.ema_result, data.tolerance);
+    assert!(is_within_error_bound);
+
+    env::commit(&EmaErrorBoundOutput {
+        avg_hourly_gas_fee_digest: hash_f64s(&data.avg_hourly_gas_fee),
+        alpha: data.alpha,
+        tolerance: data.tolerance,
+        ema_result: data.ema_result,
+    });
+}
@@ -0,0 +1,22 @@
+use common::floating_point::{calculate_geometric_twap, error_bound_f64};
+use common::hash_f64s;
+use core::{GeometricTwapErrorBoundInput, GeometricTwapErrorBoundOutput};
+use risc0_zkvm::guest::env;
+
+fn main() {
+    let data: GeometricTwapErrorBoundInput = env::read();
+    let geometric_twap_result = calculate_geometric_twap(&data.avg_hourly_gas_fee).unwrap();
+
+    let is_within_error_bound = error_bound_f64(
+        geometric_twap_result,
+        data.geometric_twap_result,
+        data.tolerance,
+    );
+    assert!(is_within_error_bound);
+
+    env::commit(&GeometricTwapErrorBoundOutput {
+        avg_hourly_gas_fee_digest: hash_f64s(&data.avg_hourly_gas_fee),
+        tolerance: data.tolerance,
+        geometric_twap_result: data.geometric_twap_result,
+    });
+}
@@ -0,0 +1,76 @@
+use common::floating_point::{calculate_greeks, pre_minimize};
+use common::hash_f64s;
+use common::window::round_horizon;
+use core::{GreeksInput, GreeksOutput};
+use risc0_zkvm::guest::env;
+
+fn main() {
+    let input: GreeksInput = env::read();
+
+    // The horizon is one period per hour of the round, settled as the reserve price is
+    let horizon = round_horizon(
+        input.round_start_timestamp,
+        input.round_end_timestamp,
+        &input.settlement,
+    )
+    .unwrap();
+
+    // The Greeks are only as good as the positions they simulate the fee model from
+    let (pt, pt_1, _) = pre_minimize(&input.de_seasonalised_detrended_log_base_fee);
+    assert!(
+        input.fee_model.model().verify_params(
+            &input.positions,
+            &pt,
+            &pt_1,
+            input.gradient_tolerance
+        ),
+        "The positions must be a fit of the de-seasonalised, detrended log base fee"
+    );
+
+    let greeks = calculate_greeks(
+        input.start_timestamp,
+        input.end_timestamp,
+        &input.season_param,
+        input.fee_model,
+        &input.positions,
+        &input.de_seasonalised_detrended_log_base_fee,
+        &horizon,
+        input.num_paths,
+        &input.twap_7d,
+        input.slope,
+        input.intercept,
+        input.data_length,
+        &input.payoff,
+        input.seed,
+    )
+    .unwrap();
+
+    env::commit(&GreeksOutput {
+        start_timestamp: input.start_timestamp,
+        end_timestamp: input.end_timestamp,
+        season_param_digest: hash_f64s(input.season_param.as_slice()),
+        fee_model: input.fee_model,
+        positions_digest: hash_f64s(&input.positions),
+        gradient_tolerance: input.gradient_tolerance,
+        de_seasonalised_detrended_log_base_fee_digest: hash_f64s(
+            input.de_seasonalised_detrended_log_base_fee.as_slice(),
+        ),
+        round_start_timestamp: input.round_start_timestamp,
+        round_end_timestamp: input.round_end_timestamp,
+        settlement: input.settlement,
+        n_periods: horizon.n_periods,
+        num_paths: input.num_paths,
+        twap_7d_digest: hash_f64s(&input.twap_7d),
+        slope: input.slope,
+        intercept: input.intercept,
+        data_length: input.data_length,
+        payoff: input.payoff,
+        seed: input.seed,
+        reserve_price: greeks.reserve_price,
+        dual_delta: greeks.dual_delta,
+        vega: greeks.vega,
+        theta: greeks.theta,
+        sigma_vega: greeks.sigma_vega,
+        sigma_j_vega: greeks.sigma_j_vega,
+    });
+}
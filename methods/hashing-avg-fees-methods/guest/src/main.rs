@@ -0,0 +1,54 @@
+use common::{
+    convert_felt_to_f64_checked, convert_hash_to_u32_array,
+    generate_batched_hash_for_all_avg_fees_with_config, hash_f64s, validate_hourly_avg_fees,
+    AvgFeesMerkleTree,
+};
+use core::{HashingAvgFeesInput, HashingAvgFeesOutput};
+use hourly_aggregation_methods::HOURLY_AGGREGATION_GUEST_ID;
+use risc0_zkvm::{guest::env, serde};
+
+fn main() {
+    let input: HashingAvgFeesInput = env::read();
+
+    // records aggregated from blocks are only trusted once the aggregation receipt is verified
+    if let Some(aggregation) = &input.aggregation {
+        env::verify(
+            HOURLY_AGGREGATION_GUEST_ID,
+            &serde::to_vec(aggregation).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(aggregation.avg_fees, input.inputs);
+    }
+
+    let records = input
+        .inputs
+        .iter()
+        .map(|x| (x.timestamp, x.data_points, x.avg_fee))
+        .collect::<Vec<_>>();
+
+    // every record must be the next hour after the previous one and average at least one block,
+    // so the committed first/last timestamps pin down which hours the fees belong to
+    validate_hourly_avg_fees(&records).unwrap();
+
+    let hash_res = generate_batched_hash_for_all_avg_fees_with_config(&records, &input.hash_config);
+    // committed next to the hash, so that windows proven against the root by the range
+    // guest are known to come from the same records
+    let merkle_root = AvgFeesMerkleTree::new(&records).unwrap().root();
+
+    // convert felts to f64s, rejecting felts that are not UFixedPoint123x128 fees
+    let f64_inputs = input
+        .inputs
+        .iter()
+        .map(|x| convert_felt_to_f64_checked(x.avg_fee).unwrap().value)
+        .collect::<Vec<_>>();
+
+    env::commit(&HashingAvgFeesOutput {
+        hash: convert_hash_to_u32_array(&hash_res),
+        merkle_root: convert_hash_to_u32_array(&merkle_root),
+        f64_inputs_digest: hash_f64s(&f64_inputs),
+        first_timestamp: records[0].0,
+        last_timestamp: records[records.len() - 1].0,
+        hash_config: input.hash_config,
+        block_range: input.aggregation.map(|aggregation| aggregation.block_range),
+    });
+}
@@ -0,0 +1,82 @@
+use common::{
+    convert_felt_to_f64_checked, convert_hash_to_u32_array, convert_u32_array_to_hash,
+    hash_avg_fees_batches_with_config, hash_f64_batch_digests, hash_f64s_in_batches,
+    hash_of_hashes, roll_batch_hashes, validate_hourly_avg_fees, validate_rolling_avg_fees,
+    window::SECONDS_IN_HOUR,
+};
+use core::{HashingAvgFeesRollingInput, HashingAvgFeesRollingOutput};
+use risc0_zkvm::{guest::env, serde};
+
+fn main() {
+    let input: HashingAvgFeesRollingInput = env::read();
+
+    let records = input
+        .inputs
+        .iter()
+        .map(|x| (x.timestamp, x.data_points, x.avg_fee))
+        .collect::<Vec<_>>();
+    let new_batch_hashes = hash_avg_fees_batches_with_config(&records, &input.hash_config);
+
+    // convert felts to f64s, rejecting felts that are not UFixedPoint123x128 fees
+    let f64_inputs = input
+        .inputs
+        .iter()
+        .map(|x| convert_felt_to_f64_checked(x.avg_fee).unwrap().value)
+        .collect::<Vec<_>>();
+    let new_f64_batch_digests = hash_f64s_in_batches(&f64_inputs, input.hash_config.batch_size);
+
+    let (batch_hashes, f64_batch_digests, first_timestamp) = match &input.previous {
+        Some(previous) => {
+            // the previous round must come from this same guest, with the same batching,
+            // and its batch hashes are only trusted once its receipt is verified
+            assert_eq!(previous.rolling_image_id, input.rolling_image_id);
+            assert_eq!(previous.hash_config, input.hash_config);
+            env::verify(input.rolling_image_id, &serde::to_vec(previous).unwrap()).unwrap();
+
+            validate_rolling_avg_fees(
+                previous.last_timestamp,
+                &records,
+                input.hash_config.batch_size,
+            )
+            .unwrap();
+
+            let previous_batch_hashes = previous
+                .batch_hashes
+                .iter()
+                .map(|hash| convert_u32_array_to_hash(hash).to_vec())
+                .collect::<Vec<_>>();
+            let batch_hashes =
+                roll_batch_hashes(&previous_batch_hashes, &new_batch_hashes).unwrap();
+            let f64_batch_digests =
+                roll_batch_hashes(&previous.f64_batch_digests, &new_f64_batch_digests).unwrap();
+
+            let first_timestamp =
+                previous.first_timestamp + records.len() as u64 * SECONDS_IN_HOUR as u64;
+            (batch_hashes, f64_batch_digests, first_timestamp)
+        }
+        None => {
+            // the first window must also be whole batches, so that later rounds stay aligned
+            validate_hourly_avg_fees(&records).unwrap();
+            assert!(input.hash_config.batch_size > 0);
+            assert_eq!(records.len() % input.hash_config.batch_size, 0);
+
+            (new_batch_hashes, new_f64_batch_digests, records[0].0)
+        }
+    };
+
+    let hash_res = hash_of_hashes(&batch_hashes, input.hash_config.scheme);
+
+    env::commit(&HashingAvgFeesRollingOutput {
+        hash: convert_hash_to_u32_array(&hash_res),
+        batch_hashes: batch_hashes
+            .iter()
+            .map(|hash| convert_hash_to_u32_array(hash))
+            .collect(),
+        f64_inputs_digest: hash_f64_batch_digests(&f64_batch_digests),
+        f64_batch_digests,
+        first_timestamp,
+        last_timestamp: records[records.len() - 1].0,
+        hash_config: input.hash_config,
+        rolling_image_id: input.rolling_image_id,
+    });
+}
@@ -1,4 +1,7 @@
-use common::{convert_felt_to_f64, generate_batched_hash_for_all_avg_base_fees};
+use common::{
+    convert_felt_to_f64_checked, convert_hash_to_u32_array,
+    generate_batched_hash_for_all_avg_base_fees_with_config, hash_f64s,
+};
 use core::{HashingFeltInput, HashingFeltOutput};
 use risc0_zkvm::guest::env;
 
@@ -21,22 +24,20 @@ fn main() {
     assert_eq!(input.inputs.len(), 1440,
         "Expected 1440 hourly fee values for POC (2 months). Production requires 5760 (8 months).");
 
-    let hash_res = generate_batched_hash_for_all_avg_base_fees(&input.inputs);
+    // the batch size must match the Cairo num_in_a_batch for the hash to be recomputable on-chain
+    let hash_res =
+        generate_batched_hash_for_all_avg_base_fees_with_config(&input.inputs, &input.hash_config);
 
-    let mut u32_result = [0u32; 8];
-    for i in 0..8 {
-        u32_result[i] = u32::from_be_bytes(hash_res[i * 4..(i + 1) * 4].try_into().unwrap());
-    }
-
-    // convert felts to f64s
+    // convert felts to f64s, rejecting felts that are not UFixedPoint123x128 fees
     let f64_inputs = input
         .inputs
         .iter()
-        .map(|x| convert_felt_to_f64(*x))
+        .map(|x| convert_felt_to_f64_checked(*x).unwrap().value)
         .collect::<Vec<_>>();
 
     env::commit(&HashingFeltOutput {
-        hash: u32_result,
-        f64_inputs,
+        hash: convert_hash_to_u32_array(&hash_res),
+        f64_inputs_digest: hash_f64s(&f64_inputs),
+        hash_config: input.hash_config,
     });
 }
@@ -0,0 +1,31 @@
+use common::{
+    convert_hash_to_u32_array, convert_u32_array_to_hash, extract_base_fees, verify_header_chain,
+};
+use core::{BlockBaseFee, HeaderChainInput, HeaderChainOutput};
+use risc0_zkvm::guest::env;
+
+fn main() {
+    let input: HeaderChainInput = env::read();
+
+    // every header is linked by hash to the trusted anchor, so its fields can be trusted
+    let headers = verify_header_chain(
+        &input.headers,
+        &convert_u32_array_to_hash(&input.anchor_hash),
+    )
+    .unwrap();
+    let blocks = extract_base_fees(&headers).unwrap();
+
+    env::commit(&HeaderChainOutput {
+        parent_hash: convert_hash_to_u32_array(&headers[0].parent_hash),
+        first_block_hash: convert_hash_to_u32_array(&headers[0].hash),
+        last_block_hash: input.anchor_hash,
+        blocks: blocks
+            .into_iter()
+            .map(|(number, timestamp, base_fee)| BlockBaseFee {
+                number,
+                timestamp,
+                base_fee,
+            })
+            .collect(),
+    });
+}
@@ -0,0 +1,32 @@
+use common::{aggregate_hourly_base_fees, hash_blocks};
+use core::{AvgFees, BlockRange, HourlyAggregationInput, HourlyAggregationOutput};
+use risc0_zkvm::guest::env;
+
+fn main() {
+    let input: HourlyAggregationInput = env::read();
+
+    let blocks = input
+        .blocks
+        .iter()
+        .map(|x| (x.number, x.timestamp, x.base_fee))
+        .collect::<Vec<_>>();
+
+    // block numbers must be consecutive, so no block of an aggregated hour can be left out
+    let records = aggregate_hourly_base_fees(&blocks).unwrap();
+
+    env::commit(&HourlyAggregationOutput {
+        block_range: BlockRange {
+            first_block: blocks[0].0,
+            last_block: blocks[blocks.len() - 1].0,
+            blocks_digest: hash_blocks(&blocks),
+        },
+        avg_fees: records
+            .into_iter()
+            .map(|(timestamp, data_points, avg_fee)| AvgFees {
+                timestamp,
+                data_points,
+                avg_fee,
+            })
+            .collect(),
+    });
+}
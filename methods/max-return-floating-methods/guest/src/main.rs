@@ -1,10 +1,14 @@
 use common::floating_point::calculate_max_returns;
-use core::MaxReturnInput;
+use common::hash_f64s;
+use core::{MaxReturnInput, MaxReturnOutput};
 use risc0_zkvm::guest::env;
 
 fn main() {
     let input: MaxReturnInput = env::read();
 
     let max_return = calculate_max_returns(&input.data);
-    env::commit(&(input, max_return));
+    env::commit(&MaxReturnOutput {
+        data_digest: hash_f64s(&input.data),
+        max_return,
+    });
 }
@@ -1,5 +1,11 @@
+use common::floating_point::{calculate_realized_volatility, FeeModelKind, MrjParams, PayoffKind};
+use common::window::hourly_window_range;
 use core::ProofCompositionInput;
 use core::ProofCompositionOutput;
+use core::{
+    DataHashingProof, EmaReferenceOutput, MrjParamsOutput, PayoffOutput, ReservePriceGridOutput,
+    RollingMedianReferenceOutput, SignedFixedPointOutput, TailRiskReportOutput,
+};
 use risc0_zkvm::guest::env;
 
 use guest_fixed_utils::{StorePacking, UFixedPoint123x128};
@@ -10,9 +16,49 @@ fn to_fixed_packed_hex(value: f64) -> String {
     UFixedPoint123x128::pack(UFixedPoint123x128::from(value)).to_hex_string()
 }
 
+fn to_signed_fixed_packed_hex(value: f64) -> SignedFixedPointOutput {
+    SignedFixedPointOutput {
+        negative: value < 0.0,
+        magnitude: to_fixed_packed_hex(value.abs()),
+    }
+}
+
+/// Identifies the payoff the reserve price was priced for, with its parameters encoded
+/// like every other value in the journal.
+fn to_payoff_output(payoff: PayoffKind) -> PayoffOutput {
+    match payoff {
+        PayoffKind::CappedCall(call) => PayoffOutput::CappedCall {
+            cap_level: to_fixed_packed_hex(call.cap_level),
+        },
+        PayoffKind::CappedPut(put) => PayoffOutput::CappedPut {
+            cap_level: to_fixed_packed_hex(put.cap_level),
+        },
+        PayoffKind::Digital(digital) => PayoffOutput::Digital {
+            payout: to_fixed_packed_hex(digital.payout),
+        },
+        PayoffKind::VarianceSwap(_) => PayoffOutput::VarianceSwap,
+    }
+}
+
 fn main() {
     // Create mock data for ProofCompositionInput
     let data: ProofCompositionInput = env::read();
+    let mrj_params = match data.fee_model {
+        FeeModelKind::MeanRevertingJump => {
+            Some(MrjParams::from_positions(&data.positions).unwrap())
+        }
+        _ => None,
+    };
+    // Realized over the reserve price window, as in the composition guest
+    let reserve_price_window = hourly_window_range(
+        data.data_8_months_start_timestamp,
+        data.data_8_months_end_timestamp,
+        data.reserve_price_start_timestamp,
+        data.reserve_price_end_timestamp,
+    )
+    .unwrap();
+    let realized_volatility =
+        calculate_realized_volatility(&data.data_8_months[reserve_price_window]).unwrap();
 
     let output = ProofCompositionOutput {
         data_8_months_hash: data.data_8_months_hash,
@@ -21,7 +67,28 @@ fn main() {
         // Use specific timestamp ranges from input
         reserve_price_start_timestamp: data.reserve_price_start_timestamp,
         reserve_price_end_timestamp: data.reserve_price_end_timestamp,
+        round_start_timestamp: data.round_start_timestamp,
+        round_end_timestamp: data.round_end_timestamp,
+        settlement: data.settlement,
+        data_8_months_merkle_root: match data.data_8_months_hashing {
+            DataHashingProof::Full { merkle_root } => Some(merkle_root),
+            DataHashingProof::Rolling { .. } => None,
+        },
+        payoff: to_payoff_output(data.payoff),
         reserve_price: to_fixed_packed_hex(data.reserve_price),
+        reserve_price_grid: data.reserve_price_grid.map(|grid| ReservePriceGridOutput {
+            strike_levels: grid
+                .strike_levels
+                .into_iter()
+                .map(to_fixed_packed_hex)
+                .collect(),
+            horizons: grid.horizons,
+            reserve_prices: grid
+                .reserve_prices
+                .into_iter()
+                .map(|row| row.into_iter().map(to_fixed_packed_hex).collect())
+                .collect(),
+        }),
         twap_start_timestamp: data.twap_start_timestamp,
         twap_end_timestamp: data.twap_end_timestamp,
         twap_result: to_fixed_packed_hex(data.twap_result),
@@ -32,6 +99,49 @@ fn main() {
         reserve_price_tolerance: to_fixed_packed_hex(data.reserve_price_tolerance),
         gradient_tolerance: to_fixed_packed_hex(data.gradient_tolerance),
         twap_tolerance: to_fixed_packed_hex(data.twap_tolerance),
+        data_8_months_hash_config: data.data_8_months_hash_config,
+        data_8_months_block_range: data.data_8_months_block_range,
+        twap_kind: data.twap_kind,
+        geometric_twap_result: data.geometric_twap_result.map(to_fixed_packed_hex),
+        rolling_median: data
+            .rolling_median
+            .map(|rolling_median| RollingMedianReferenceOutput {
+                window: rolling_median.window,
+                rolling_median_result: to_fixed_packed_hex(rolling_median.rolling_median_result),
+            }),
+        ema: data.ema.map(|ema| EmaReferenceOutput {
+            alpha: to_fixed_packed_hex(ema.alpha),
+            ema_result: to_fixed_packed_hex(ema.ema_result),
+        }),
+        tail_risk: data.tail_risk.map(|tail_risk| TailRiskReportOutput {
+            quantiles: tail_risk
+                .quantiles
+                .into_iter()
+                .map(to_fixed_packed_hex)
+                .collect(),
+            value_at_risk: tail_risk
+                .value_at_risk
+                .into_iter()
+                .map(|value| to_fixed_packed_hex(1.0 + value))
+                .collect(),
+            conditional_value_at_risk: tail_risk
+                .conditional_value_at_risk
+                .into_iter()
+                .map(|value| to_fixed_packed_hex(1.0 + value))
+                .collect(),
+            min_return: to_fixed_packed_hex(1.0 + tail_risk.min_return),
+            max_drawdown: to_fixed_packed_hex(tail_risk.max_drawdown),
+        }),
+        fee_model: data.fee_model,
+        mrj_params: mrj_params.map(|mrj_params| MrjParamsOutput {
+            alpha: to_signed_fixed_packed_hex(mrj_params.alpha),
+            kappa: to_signed_fixed_packed_hex(mrj_params.kappa),
+            mu_j: to_signed_fixed_packed_hex(mrj_params.mu_j),
+            sigma: to_signed_fixed_packed_hex(mrj_params.sigma),
+            sigma_j: to_signed_fixed_packed_hex(mrj_params.sigma_j),
+            lambda: to_signed_fixed_packed_hex(mrj_params.lambda),
+        }),
+        realized_volatility: to_fixed_packed_hex(realized_volatility),
     };
 
     env::commit(&output);
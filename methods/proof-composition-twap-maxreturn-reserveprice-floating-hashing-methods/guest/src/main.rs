@@ -10,16 +10,44 @@
 
 // Import input/output structures for all computation stages
 use core::{
-    AddTwap7dErrorBoundFloatingInput,           // Input for 7-day TWAP verification
-    CalculatePtPt1ErrorBoundFloatingInput,      // Input for Markov transition matrix verification
-    HashingFeltOutput,                           // Output from data hashing
+    AddTwap7dErrorBoundFloatingOutput,          // Journal of the 7-day TWAP verification
+    CalculatePtPt1ErrorBoundFloatingOutput,     // Journal of the Markov transition matrix verification
+    EmaErrorBoundOutput,                         // Journal of the EMA reference price verification
+    EmaReferenceOutput,                          // EMA reference price, as committed
+    GeometricTwapErrorBoundOutput,               // Journal of the geometric TWAP verification
+    DataHashingProof,                            // Hashing guest data_8_months_hash was proven by
+    HashingAvgFeesOutput,                        // Output from hashing the AvgFees records
+    HashingAvgFeesRollingOutput,                 // Output from the latest rolling hashing round
+    MaxReturnOutput,                             // Journal of the max return calculation
+    MrjParamsOutput,                             // Annualized MRJ parameters, as committed
+    PayoffOutput,                                // Priced payoff, as committed
     ProofCompositionInput,                       // Combined input from all computations
     ProofCompositionOutput,                      // Final output committed to the proof
-    RemoveSeasonalityErrorBoundFloatingInput,   // Input for time series decomposition verification
-    SimulatePriceVerifyPositionInput,           // Input for Monte Carlo simulation verification
-    TwapErrorBoundInput,                         // Input for TWAP verification
+    RemoveSeasonalityErrorBoundFloatingOutput,  // Journal of the time series decomposition verification
+    ReservePriceGridOutput,                      // Reserve prices across strikes and horizons, as committed
+    RollingMedianErrorBoundOutput,               // Journal of the rolling median verification
+    RollingMedianReferenceOutput,                // Rolling median reference price, as committed
+    SignedFixedPointOutput,                      // Possibly negative value, as committed
+    SimulatePriceVerifyPositionOutput,          // Journal of the Monte Carlo simulation verification
+    TailRiskOutput,                              // Journal of the tail-risk calculation
+    TailRiskReportOutput,                        // Tail-risk metrics, as committed
+    TimeWeightedTwapErrorBoundOutput,            // Journal of the time-weighted TWAP verification
+    TwapErrorBoundOutput,                        // Journal of the TWAP verification
+    TwapKind,                                    // TWAP definition to verify
 };
 
+// Sub-guests commit SHA-256 digests of their input vectors instead of the vectors themselves
+use common::{hash_f64_batch_digests, hash_f64s, hash_f64s_in_batches};
+
+// Hourly fees aggregated from blocks are decoded as in the hashing guest
+use common::convert_felt_to_f64_checked;
+
+// Model state committed alongside the reserve price
+use common::floating_point::{calculate_realized_volatility, FeeModelKind, MrjParams, PayoffKind};
+
+// Hourly calculation windows are derived from their timestamps
+use common::window::{hourly_window_length, hourly_window_range, round_horizon, SECONDS_IN_HOUR};
+
 // Import RISC Zero ZK-VM runtime environment and serialization utilities
 use risc0_zkvm::{guest::env, serde};
 
@@ -30,8 +58,15 @@ use remove_seasonality_error_bound_floating_methods::REMOVE_SEASONALITY_ERROR_BO
 use calculate_pt_pt1_error_bound_floating_methods::CALCULATE_PT_PT1_ERROR_BOUND_FLOATING_GUEST_ID;
 use simulate_price_verify_position_floating_methods::SIMULATE_PRICE_VERIFY_POSITION_FLOATING_GUEST_ID;
 use twap_error_bound_floating_methods::TWAP_ERROR_BOUND_FLOATING_GUEST_ID;
+use time_weighted_twap_error_bound_floating_methods::TIME_WEIGHTED_TWAP_ERROR_BOUND_FLOATING_GUEST_ID;
+use geometric_twap_error_bound_floating_methods::GEOMETRIC_TWAP_ERROR_BOUND_FLOATING_GUEST_ID;
+use rolling_median_error_bound_floating_methods::ROLLING_MEDIAN_ERROR_BOUND_FLOATING_GUEST_ID;
+use ema_error_bound_floating_methods::EMA_ERROR_BOUND_FLOATING_GUEST_ID;
 use max_return_floating_methods::MAX_RETURN_FLOATING_GUEST_ID;
-use hashing_felts_methods::HASHING_FELTS_GUEST_ID;
+use tail_risk_floating_methods::TAIL_RISK_FLOATING_GUEST_ID;
+use hashing_avg_fees_methods::HASHING_AVG_FEES_GUEST_ID;
+use hashing_avg_fees_rolling_methods::HASHING_AVG_FEES_ROLLING_GUEST_ID;
+use hourly_aggregation_methods::HOURLY_AGGREGATION_GUEST_ID;
 
 // Import fixed-point arithmetic utilities for Starknet compatibility
 // UFixedPoint123x128 represents numbers with 123 bits for the integer part and 128 bits for the fractional part
@@ -51,80 +86,334 @@ fn to_fixed_packed_hex(value: f64) -> String {
     UFixedPoint123x128::pack(UFixedPoint123x128::from(value)).to_hex_string()
 }
 
+/// Same as `to_fixed_packed_hex`, for values that may be negative: the unsigned fixed
+/// point encoding of the magnitude, with the sign alongside.
+fn to_signed_fixed_packed_hex(value: f64) -> SignedFixedPointOutput {
+    SignedFixedPointOutput {
+        negative: value < 0.0,
+        magnitude: to_fixed_packed_hex(value.abs()),
+    }
+}
+
+/// Identifies the payoff the reserve price was priced for, with its parameters encoded
+/// like every other value in the journal.
+fn to_payoff_output(payoff: PayoffKind) -> PayoffOutput {
+    match payoff {
+        PayoffKind::CappedCall(call) => PayoffOutput::CappedCall {
+            cap_level: to_fixed_packed_hex(call.cap_level),
+        },
+        PayoffKind::CappedPut(put) => PayoffOutput::CappedPut {
+            cap_level: to_fixed_packed_hex(put.cap_level),
+        },
+        PayoffKind::Digital(digital) => PayoffOutput::Digital {
+            payout: to_fixed_packed_hex(digital.payout),
+        },
+        PayoffKind::VarianceSwap(_) => PayoffOutput::VarianceSwap,
+    }
+}
+
 fn main() {
     // ========== STEP 1: READ INPUT DATA ==========
     // Read the ProofCompositionInput from the host
     // This contains all the data and parameters needed to verify the sub-proofs
     let data: ProofCompositionInput = env::read();
 
+    // ========== STEP 1A: DERIVE CALCULATION WINDOWS FROM TIMESTAMPS ==========
+    // Every window is a half-open [start, end) range of hours. All timestamps must fall on
+    // an hour boundary, and every window must lie within the 8-month data range, whose
+    // length must match the number of hourly data points provided.
+    let data_8_months_length = hourly_window_length(
+        data.data_8_months_start_timestamp,
+        data.data_8_months_end_timestamp,
+    )
+    .unwrap();
+    assert_eq!(
+        data.data_8_months.len(),
+        data_8_months_length,
+        "data_8_months must contain one value per hour of its timestamp range"
+    );
+
+    let window = |start_timestamp, end_timestamp| {
+        hourly_window_range(
+            data.data_8_months_start_timestamp,
+            data.data_8_months_end_timestamp,
+            start_timestamp,
+            end_timestamp,
+        )
+        .unwrap()
+    };
+    window(data.start_timestamp, data.end_timestamp);
+    let reserve_price_window = window(
+        data.reserve_price_start_timestamp,
+        data.reserve_price_end_timestamp,
+    );
+    let twap_window = window(data.twap_start_timestamp, data.twap_end_timestamp);
+    let max_return_window = window(
+        data.max_return_start_timestamp,
+        data.max_return_end_timestamp,
+    );
+
+    // The simulation horizon is one period per hour of the option round, which starts when
+    // the reserve price data ends, as the simulation starts from its last hour, and the
+    // settlement window is the last hours of the round
+    assert_eq!(
+        data.round_start_timestamp, data.reserve_price_end_timestamp,
+        "The option round must start when the reserve price window ends"
+    );
+    let round = round_horizon(
+        data.round_start_timestamp,
+        data.round_end_timestamp,
+        &data.settlement,
+    )
+    .unwrap();
+
+    // Digests of the vectors shared between several sub-proofs are computed once here.
+    // Each sub-guest committed the same digests of its own inputs, so matching journals
+    // prove that every sub-proof ran on exactly this data.
+    let data_8_months_digest = hash_f64s(&data.data_8_months);
+    let max_return_data_digest = hash_f64s(&data.data_8_months[max_return_window]);
+    let de_seasonalised_detrended_log_base_fee_digest =
+        hash_f64s(data.de_seasonalised_detrended_log_base_fee.as_slice());
+    let pt_digest = hash_f64s(data.pt.as_slice());
+    let pt_1_digest = hash_f64s(data.pt_1.as_slice());
+    let season_param_digest = hash_f64s(data.season_param.as_slice());
+    let twap_7d_digest = hash_f64s(&data.twap_7d);
+
     // ========== STEP 2: VERIFY SUB-PROOF #1 - DATA HASHING ==========
-    // Verify that the hash of the 8-month gas fee data is correct
+    // Verify that the hash of the 8-month AvgFees records is correct
     // This ensures data integrity and creates a commitment that can be verified on-chain
     //
     // The env::verify() function checks that:
-    // 1. A receipt for HASHING_FELTS_GUEST_ID exists in the assumptions
-    // 2. The receipt's output matches the provided HashingFeltOutput
+    // 1. A receipt for the hashing guest exists in the assumptions
+    // 2. The receipt's output matches the expected journal, whose digest of the f64 fees
+    //    binds the hash commitment to the data used by every other sub-proof
+    // 3. The hashed records are contiguous hours from data_8_months_start_timestamp up to
+    //    data_8_months_end_timestamp, so the data range is proven rather than asserted
     //
     // If verification fails, the entire proof generation fails
-    env::verify(
-        HASHING_FELTS_GUEST_ID,  // Guest program ID for the hashing sub-proof
-        &serde::to_vec(&HashingFeltOutput {
-            hash: data.data_8_months_hash,           // The hash commitment
-            f64_inputs: data.data_8_months.clone(),  // The original 8-month data
-        })
-        .unwrap(),
-    )
-    .unwrap();
+    let data_8_months_first_timestamp = u64::try_from(data.data_8_months_start_timestamp).unwrap();
+    let data_8_months_last_timestamp =
+        u64::try_from(data.data_8_months_end_timestamp - SECONDS_IN_HOUR).unwrap();
+    let data_8_months_merkle_root = match &data.data_8_months_hashing {
+        // The whole window hashed at once
+        DataHashingProof::Full { merkle_root } => {
+            env::verify(
+                HASHING_AVG_FEES_GUEST_ID,  // Guest program ID for the hashing sub-proof
+                &serde::to_vec(&HashingAvgFeesOutput {
+                    hash: data.data_8_months_hash,           // The hash commitment
+                    merkle_root: *merkle_root,               // Merkle root over the same records
+                    f64_inputs_digest: data_8_months_digest, // Digest of the original 8-month data
+                    // Timestamp of the first and last hourly record
+                    first_timestamp: data_8_months_first_timestamp,
+                    last_timestamp: data_8_months_last_timestamp,
+                    // Batch size and hash function the commitment was computed with
+                    hash_config: data.data_8_months_hash_config,
+                    // Blocks the hourly records were aggregated from, if proven from blocks
+                    block_range: data.data_8_months_block_range.clone(),
+                })
+                .unwrap(),
+            )
+            .unwrap();
+            Some(*merkle_root)
+        }
+        // The latest round of the rolling hashing, which only hashed the batches that are
+        // new since the previous round. Its f64 digests are kept per batch so that they can
+        // roll forward as well
+        DataHashingProof::Rolling { batch_hashes } => {
+            assert!(
+                data.data_8_months_block_range.is_none(),
+                "The rolling hashing does not prove which blocks the records come from"
+            );
+            let f64_batch_digests = hash_f64s_in_batches(
+                &data.data_8_months,
+                data.data_8_months_hash_config.batch_size,
+            );
+            env::verify(
+                HASHING_AVG_FEES_ROLLING_GUEST_ID,  // Guest program ID for the rolling hashing
+                &serde::to_vec(&HashingAvgFeesRollingOutput {
+                    hash: data.data_8_months_hash,  // The hash commitment
+                    batch_hashes: batch_hashes.clone(),
+                    f64_inputs_digest: hash_f64_batch_digests(&f64_batch_digests),
+                    f64_batch_digests,
+                    first_timestamp: data_8_months_first_timestamp,
+                    last_timestamp: data_8_months_last_timestamp,
+                    hash_config: data.data_8_months_hash_config,
+                    // Every round verifies the previous one under the image ID it commits,
+                    // so requiring our own copy of the rolling guest's ID here ensures the
+                    // whole chain of rounds was proven by that guest
+                    rolling_image_id: HASHING_AVG_FEES_ROLLING_GUEST_ID,
+                })
+                .unwrap(),
+            )
+            .unwrap();
+            None
+        }
+    };
 
     // ========== STEP 3: VERIFY SUB-PROOF #2 - MAXIMUM RETURN ==========
     // Verify that the maximum return (volatility measure) was calculated correctly
-    // from the 8-month historical data
+    // from the hours of the max return window (the full 8 months in production)
     //
-    // Output format: (input_data, max_return_result)
     // This ensures the volatility calculation used for option pricing is correct
     env::verify(
         MAX_RETURN_FLOATING_GUEST_ID,  // Guest program ID for max return calculation
-        &serde::to_vec(&(data.data_8_months.clone(), data.max_return)).unwrap(),
+        &serde::to_vec(&MaxReturnOutput {
+            data_digest: max_return_data_digest,
+            max_return: data.max_return,
+        })
+        .unwrap(),
     )
     .unwrap();
 
+    // ========== STEP 3A: VERIFY OPTIONAL TAIL-RISK METRICS ==========
+    // The max return alone says nothing about how often large returns happen. When set,
+    // the historical VaR and CVaR of the same returns at the given quantiles, their minimum
+    // and the max drawdown of the TWAP-30d are verified over the max return window
+    if let Some(tail_risk) = &data.tail_risk {
+        env::verify(
+            TAIL_RISK_FLOATING_GUEST_ID,  // Guest program ID for the tail-risk calculation
+            &serde::to_vec(&TailRiskOutput {
+                data_digest: max_return_data_digest,
+                quantiles: tail_risk.quantiles.clone(),
+                value_at_risk: tail_risk.value_at_risk.clone(),
+                conditional_value_at_risk: tail_risk.conditional_value_at_risk.clone(),
+                min_return: tail_risk.min_return,
+                max_drawdown: tail_risk.max_drawdown,
+            })
+            .unwrap(),
+        )
+        .unwrap();
+    }
+
     // ========== STEP 4: EXTRACT 3-MONTH DATA SUBSET ==========
-    // Extract the subset needed for reserve price, TWAP, and time series decomposition
+    // Extract the reserve price window, used for time series decomposition, the 7-day
+    // TWAP and the price simulation
     //
     // DEVELOPER NOTE: Data Subset Configuration
     // =========================================
-    // Production configuration: Extract last 2160 hours (90 days / 3 months) from 5760 hours (8 months)
-    //   let data_3_months = data.data_8_months[data.data_8_months.len().saturating_sub(2160)..].to_vec();
-    //
-    // Current configuration: Extract last 720 hours (30 days / 1 month) from 1440 hours (2 months)
-    // This aligns with the POC's reduced data availability.
-    //
-    // The subset is used for:
-    //   - Reserve price calculation (requires sufficient data for time series decomposition)
-    //   - TWAP calculation (time-weighted average price)
-    //   - Seasonality and trend analysis
-    //
-    // IMPORTANT: When scaling to production (8 months total), update to extract 2160 hours:
-    //   let data_3_months = data.data_8_months[data.data_8_months.len().saturating_sub(2160)..].to_vec();
-    let data_3_months =
-        data.data_8_months[data.data_8_months.len().saturating_sub(720)..].to_vec();
+    // The window length follows from reserve_price_start/end_timestamp:
+    // 2160 hours (90 days / 3 months) in production, 720 hours (30 days / 1 month) in the POC
+    let data_3_months = &data.data_8_months[reserve_price_window];
+    let data_3_months_digest = hash_f64s(data_3_months);
 
     // ========== STEP 5: VERIFY SUB-PROOF #3 - TWAP (TIME-WEIGHTED AVERAGE PRICE) ==========
-    // Verify that the time-weighted average price was calculated correctly
+    // Verify that the TWAP was calculated correctly under the definition selected by
+    // twap_kind, which is committed so that a verifier knows which one was proven
     // TWAP is used as a reference price for gas fee options
     //
     // This checks that the TWAP calculation is within the specified tolerance (typically 1%)
-    let twap_error_bound_input = TwapErrorBoundInput {
-        avg_hourly_gas_fee: data_3_months.clone(),  // 90 days of hourly gas fees
-        twap_tolerance: data.twap_tolerance,         // Acceptable deviation (1%)
-        twap_result: data.twap_result,               // Expected TWAP result
-    };
+    let twap_data = &data.data_8_months[twap_window];
+    match data.twap_kind {
+        // Arithmetic mean of the hourly gas fees
+        TwapKind::Arithmetic => {
+            let twap_error_bound_output = TwapErrorBoundOutput {
+                // Hourly gas fees of the TWAP window
+                avg_hourly_gas_fee_digest: hash_f64s(twap_data),
+                twap_tolerance: data.twap_tolerance,             // Acceptable deviation (1%)
+                twap_result: data.twap_result,                   // Expected TWAP result
+            };
 
-    env::verify(
-        TWAP_ERROR_BOUND_FLOATING_GUEST_ID,  // Guest program ID for TWAP verification
-        &serde::to_vec(&twap_error_bound_input).unwrap(),
-    )
-    .unwrap();
+            env::verify(
+                TWAP_ERROR_BOUND_FLOATING_GUEST_ID,  // Guest program ID for TWAP verification
+                &serde::to_vec(&twap_error_bound_output).unwrap(),
+            )
+            .unwrap();
+        }
+        // Block base fees weighted by the time each was in effect. The blocks are those
+        // the hourly fees of the TWAP window were aggregated from, so the time-weighted
+        // TWAP is bound to the same data as the hash commitment
+        TwapKind::TimeWeighted => {
+            let aggregation = data
+                .twap_aggregation
+                .as_ref()
+                .expect("A time-weighted TWAP needs the hourly aggregation of its blocks");
+            env::verify(
+                HOURLY_AGGREGATION_GUEST_ID,  // Guest program ID for the hourly aggregation
+                &serde::to_vec(aggregation).unwrap(),
+            )
+            .unwrap();
+
+            // The aggregated hours must be exactly the hours of the TWAP window, averaging
+            // to its hourly gas fees
+            assert_eq!(
+                aggregation.avg_fees.len(),
+                twap_data.len(),
+                "The aggregation must cover the TWAP window"
+            );
+            for (i, (avg_fees, fee)) in aggregation.avg_fees.iter().zip(twap_data).enumerate() {
+                assert_eq!(
+                    avg_fees.timestamp as i64,
+                    data.twap_start_timestamp + i as i64 * SECONDS_IN_HOUR,
+                    "The aggregation must cover the TWAP window"
+                );
+                assert_eq!(
+                    convert_felt_to_f64_checked(avg_fees.avg_fee).unwrap().value,
+                    *fee,
+                    "The aggregated fees must be the hourly gas fees of the TWAP window"
+                );
+            }
+
+            let time_weighted_twap_error_bound_output = TimeWeightedTwapErrorBoundOutput {
+                // Blocks of the aggregation
+                blocks_digest: aggregation.block_range.blocks_digest,
+                start_timestamp: data.twap_start_timestamp,      // Start of the TWAP window
+                end_timestamp: data.twap_end_timestamp,          // End of the TWAP window
+                twap_tolerance: data.twap_tolerance,             // Acceptable deviation (1%)
+                twap_result: data.twap_result,                   // Expected TWAP result
+            };
+
+            env::verify(
+                TIME_WEIGHTED_TWAP_ERROR_BOUND_FLOATING_GUEST_ID,  // Guest program ID
+                &serde::to_vec(&time_weighted_twap_error_bound_output).unwrap(),
+            )
+            .unwrap();
+        }
+    }
+
+    // ========== STEP 5A: VERIFY OPTIONAL ALTERNATIVE REFERENCE PRICES ==========
+    // Vaults may settle against a manipulation-resistant reference instead of the TWAP:
+    // a single spike hour moves the arithmetic mean a lot, but the geometric mean little
+    // and the median not at all. Each reference that is set is verified over the same
+    // hourly fees as the TWAP, within the TWAP tolerance, and committed to the output
+    let twap_data_digest = hash_f64s(twap_data);
+    if let Some(geometric_twap_result) = data.geometric_twap_result {
+        env::verify(
+            GEOMETRIC_TWAP_ERROR_BOUND_FLOATING_GUEST_ID,  // Geometric mean of the hourly fees
+            &serde::to_vec(&GeometricTwapErrorBoundOutput {
+                avg_hourly_gas_fee_digest: twap_data_digest,
+                tolerance: data.twap_tolerance,
+                geometric_twap_result,
+            })
+            .unwrap(),
+        )
+        .unwrap();
+    }
+    if let Some(rolling_median) = &data.rolling_median {
+        env::verify(
+            ROLLING_MEDIAN_ERROR_BOUND_FLOATING_GUEST_ID,  // Median of the trailing window hours
+            &serde::to_vec(&RollingMedianErrorBoundOutput {
+                avg_hourly_gas_fee_digest: twap_data_digest,
+                window: rolling_median.window,
+                tolerance: data.twap_tolerance,
+                rolling_median_result: rolling_median.rolling_median_result,
+            })
+            .unwrap(),
+        )
+        .unwrap();
+    }
+    if let Some(ema) = &data.ema {
+        env::verify(
+            EMA_ERROR_BOUND_FLOATING_GUEST_ID,  // Exponential moving average at the window end
+            &serde::to_vec(&EmaErrorBoundOutput {
+                avg_hourly_gas_fee_digest: twap_data_digest,
+                alpha: ema.alpha,
+                tolerance: data.twap_tolerance,
+                ema_result: ema.ema_result,
+            })
+            .unwrap(),
+        )
+        .unwrap();
+    }
 
     // ========== STEP 6: VERIFY SUB-PROOF #4 - SEASONALITY REMOVAL ==========
     // Verify that the time series decomposition was performed correctly
@@ -135,19 +424,19 @@ fn main() {
     // 3. Residuals: Random fluctuations (de_seasonalised_detrended_log_base_fee)
     //
     // This decomposition is essential for accurate price modeling and forecasting
-    let remove_seasonality_error_bound_input = RemoveSeasonalityErrorBoundFloatingInput {
-        data: data_3_months.clone(),            // Original 90-day gas fee data
+    let remove_seasonality_error_bound_output = RemoveSeasonalityErrorBoundFloatingOutput {
+        data_digest: data_3_months_digest,       // Original 90-day gas fee data
         slope: data.slope,                       // Linear trend slope
         intercept: data.intercept,               // Linear trend intercept
         // Residuals after removing trend and seasonality (in log space)
-        de_seasonalised_detrended_log_base_fee: data.de_seasonalised_detrended_log_base_fee.clone(),
-        season_param: data.season_param.clone(), // 24 hourly seasonal parameters
+        de_seasonalised_detrended_log_base_fee_digest,
+        season_param_digest,                     // 24 hourly seasonal parameters
         tolerance: data.floating_point_tolerance, // Precision tolerance (0.00001%)
     };
 
     env::verify(
         REMOVE_SEASONALITY_ERROR_BOUND_FLOATING_GUEST_ID,  // Guest program ID
-        &serde::to_vec(&remove_seasonality_error_bound_input).unwrap(),
+        &serde::to_vec(&remove_seasonality_error_bound_output).unwrap(),
     )
     .unwrap();
 
@@ -157,15 +446,15 @@ fn main() {
     // The 7-day TWAP serves as a mean reversion level in the price model
     // Gas prices tend to revert to this 7-day average, which is a key assumption
     // in the Markov chain model used for price simulation
-    let add_twap_7d_error_bound_input = AddTwap7dErrorBoundFloatingInput {
-        data: data_3_months.clone(),              // 90 days of hourly gas fees
-        twap_7d: data.twap_7d.clone().clone(),   // 7-day rolling TWAP values
+    let add_twap_7d_error_bound_output = AddTwap7dErrorBoundFloatingOutput {
+        data_digest: data_3_months_digest,        // 90 days of hourly gas fees
+        twap_7d_digest,                           // 7-day rolling TWAP values
         tolerance: data.floating_point_tolerance, // Precision tolerance (0.00001%)
     };
 
     env::verify(
         ADD_TWAP_7D_ERROR_BOUND_FLOATING_GUEST_ID,  // Guest program ID
-        &serde::to_vec(&add_twap_7d_error_bound_input).unwrap(),
+        &serde::to_vec(&add_twap_7d_error_bound_output).unwrap(),
     )
     .unwrap();
 
@@ -178,17 +467,17 @@ fn main() {
     //
     // These matrices are derived from the residuals (de_seasonalised_detrended_log_base_fee)
     // and capture the stochastic behavior of gas prices after removing trend and seasonality
-    let calculate_pt_pt1_error_bound_input = CalculatePtPt1ErrorBoundFloatingInput {
+    let calculate_pt_pt1_error_bound_output = CalculatePtPt1ErrorBoundFloatingOutput {
         // Residuals used to estimate transition probabilities
-        de_seasonalised_detrended_log_base_fee: data.de_seasonalised_detrended_log_base_fee.clone(),
-        pt: data.pt.clone(),                      // Transition matrix for period t
-        pt_1: data.pt_1.clone(),                  // Transition matrix for period t-1
+        de_seasonalised_detrended_log_base_fee_digest,
+        pt_digest,                                // Transition matrix for period t
+        pt_1_digest,                              // Transition matrix for period t-1
         tolerance: data.floating_point_tolerance, // Precision tolerance (0.00001%)
     };
 
     env::verify(
         CALCULATE_PT_PT1_ERROR_BOUND_FLOATING_GUEST_ID,  // Guest program ID
-        &serde::to_vec(&calculate_pt_pt1_error_bound_input).unwrap(),
+        &serde::to_vec(&calculate_pt_pt1_error_bound_output).unwrap(),
     )
     .unwrap();
 
@@ -203,32 +492,52 @@ fn main() {
     //
     // This is the core of the reserve price calculation, ensuring that the price
     // is fair and that option sellers are adequately compensated for the risk
-    let simulate_price_verify_position_input = SimulatePriceVerifyPositionInput {
-        start_timestamp: data.start_timestamp,    // Start of analysis period
-        end_timestamp: data.end_timestamp,        // End of analysis period
+    //
+    // The positions are verified and simulated under the selected fee model. For the MRJ
+    // model the guest also commits the annualized parameters it simulated with, which are
+    // a deterministic function of the positions
+    let mrj_params = match data.fee_model {
+        FeeModelKind::MeanRevertingJump => Some(MrjParams::from_positions(&data.positions).unwrap()),
+        _ => None,
+    };
+    let simulate_price_verify_position_output = SimulatePriceVerifyPositionOutput {
+        start_timestamp: data.reserve_price_start_timestamp, // Start of reserve price window
+        end_timestamp: data.reserve_price_end_timestamp,     // End of reserve price window
+        round_start_timestamp: data.round_start_timestamp, // Start of the option round
+        round_end_timestamp: data.round_end_timestamp,     // End of the option round
+        settlement: data.settlement,              // Settlement window of the round
         data_length: data_3_months.len(),        // Number of data points (POC: 720, Production: 2160)
-        positions: data.positions.clone(),        // Optimized option positions
-        pt: data.pt.clone(),                      // Markov transition matrix t
-        pt_1: data.pt_1.clone(),                  // Markov transition matrix t-1
+        fee_model: data.fee_model,                // Fee model the positions were fitted to
+        positions_digest: hash_f64s(&data.positions), // Optimized option positions
+        pt_digest,                                // Markov transition matrix t
+        pt_1_digest,                              // Markov transition matrix t-1
         gradient_tolerance: data.gradient_tolerance, // Convergence tolerance (5%)
         // Residuals for stochastic price simulation
-        de_seasonalised_detrended_log_base_fee: data.de_seasonalised_detrended_log_base_fee.clone(),
-        n_periods: data.n_periods,                // Number of simulation periods (configurable)
+        de_seasonalised_detrended_log_base_fee_digest,
+        n_periods: round.n_periods,               // One simulation period per hour of the round
         num_paths: data.num_paths,                // 4000 Monte Carlo simulation paths
-        season_param: data.season_param.clone(),  // 24 hourly seasonal parameters
-        twap_7d: data.twap_7d.clone(),           // 7-day TWAP (mean reversion level)
+        season_param_digest,                      // 24 hourly seasonal parameters
+        twap_7d_digest,                           // 7-day TWAP (mean reversion level)
         slope: data.slope,                        // Linear trend slope
         intercept: data.intercept,                // Linear trend intercept
+        payoff: data.payoff,                      // Payoff the reserve price is priced for
         reserve_price: data.reserve_price,        // Calculated reserve price
+        reserve_price_grid: data.reserve_price_grid.clone(), // Other strikes and horizons, if set
         tolerance: data.reserve_price_tolerance,  // Reserve price tolerance (5%)
+        mrj_params,                               // Annualized MRJ parameters
     };
 
     env::verify(
         SIMULATE_PRICE_VERIFY_POSITION_FLOATING_GUEST_ID,  // Guest program ID
-        &serde::to_vec(&simulate_price_verify_position_input).unwrap(),
+        &serde::to_vec(&simulate_price_verify_position_output).unwrap(),
     )
     .unwrap();
 
+    // ========== STEP 9A: REALIZED VOLATILITY ==========
+    // Annualized standard deviation of the hourly log fee changes over the reserve price
+    // window, computed here from the hashed data, to set against the MRJ sigma
+    let realized_volatility = calculate_realized_volatility(data_3_months).unwrap();
+
     // ========== STEP 10: CONSTRUCT OUTPUT FOR ON-CHAIN VERIFICATION ==========
     // After all 7 sub-proofs have been verified, construct the final output
     // that will be committed to the ZK proof and made available for on-chain verification
@@ -243,18 +552,32 @@ fn main() {
         end_timestamp: data.end_timestamp,
 
         // Reserve price calculation results (uses 3-month period)
-        reserve_price_start_timestamp: data.start_timestamp,  // 90-day period start
-        reserve_price_end_timestamp: data.end_timestamp,      // 90-day period end
+        reserve_price_start_timestamp: data.reserve_price_start_timestamp, // 90-day period start
+        reserve_price_end_timestamp: data.reserve_price_end_timestamp,     // 90-day period end
+        round_start_timestamp: data.round_start_timestamp, // Option round the price is for
+        round_end_timestamp: data.round_end_timestamp,
+        settlement: data.settlement,                       // How the round settles
+        payoff: to_payoff_output(data.payoff),                 // Option the reserve price is for
         reserve_price: to_fixed_packed_hex(data.reserve_price), // Minimum option selling price
+        // Reserve prices across strikes and horizons of the same simulation, if proven
+        reserve_price_grid: data.reserve_price_grid.map(|grid| ReservePriceGridOutput {
+            strike_levels: grid.strike_levels.into_iter().map(to_fixed_packed_hex).collect(),
+            horizons: grid.horizons,
+            reserve_prices: grid
+                .reserve_prices
+                .into_iter()
+                .map(|row| row.into_iter().map(to_fixed_packed_hex).collect())
+                .collect(),
+        }),
 
         // TWAP calculation results (uses 3-month period)
-        twap_start_timestamp: data.start_timestamp,           // 90-day period start
-        twap_end_timestamp: data.end_timestamp,               // 90-day period end
+        twap_start_timestamp: data.twap_start_timestamp,      // TWAP window start
+        twap_end_timestamp: data.twap_end_timestamp,          // TWAP window end
         twap_result: to_fixed_packed_hex(data.twap_result),  // Time-weighted average gas price
 
         // Maximum return (volatility) calculation (uses full 8-month period)
-        max_return_start_timestamp: data.data_8_months_start_timestamp, // 240-day period start
-        max_return_end_timestamp: data.data_8_months_end_timestamp,     // 240-day period end
+        max_return_start_timestamp: data.max_return_start_timestamp, // 240-day period start
+        max_return_end_timestamp: data.max_return_end_timestamp,     // 240-day period end
         max_return: to_fixed_packed_hex(data.max_return),    // Historical volatility measure
 
         // Tolerances used for verification (converted to fixed-point for on-chain checks)
@@ -262,6 +585,56 @@ fn main() {
         reserve_price_tolerance: to_fixed_packed_hex(data.reserve_price_tolerance),   // 5%
         gradient_tolerance: to_fixed_packed_hex(data.gradient_tolerance),             // 5%
         twap_tolerance: to_fixed_packed_hex(data.twap_tolerance),                     // 1%
+
+        // How data_8_months_hash was computed, so it can be recomputed on-chain
+        data_8_months_hash_config: data.data_8_months_hash_config,
+        // Merkle root over the same records, verified with the hash by the hashing sub-proof
+        data_8_months_merkle_root,
+        // Which blocks the hourly data was aggregated from, if any
+        data_8_months_block_range: data.data_8_months_block_range,
+        // Which TWAP definition twap_result was verified under
+        twap_kind: data.twap_kind,
+
+        // Alternative reference prices over the TWAP window, if verified
+        geometric_twap_result: data.geometric_twap_result.map(to_fixed_packed_hex),
+        rolling_median: data.rolling_median.map(|rolling_median| RollingMedianReferenceOutput {
+            window: rolling_median.window,
+            rolling_median_result: to_fixed_packed_hex(rolling_median.rolling_median_result),
+        }),
+        ema: data.ema.map(|ema| EmaReferenceOutput {
+            alpha: to_fixed_packed_hex(ema.alpha),
+            ema_result: to_fixed_packed_hex(ema.ema_result),
+        }),
+
+        // Tail-risk metrics over the max return window, if verified. Returns may be
+        // negative, so they are committed as gross returns 1 + r
+        tail_risk: data.tail_risk.map(|tail_risk| TailRiskReportOutput {
+            quantiles: tail_risk.quantiles.into_iter().map(to_fixed_packed_hex).collect(),
+            value_at_risk: tail_risk
+                .value_at_risk
+                .into_iter()
+                .map(|value| to_fixed_packed_hex(1.0 + value))
+                .collect(),
+            conditional_value_at_risk: tail_risk
+                .conditional_value_at_risk
+                .into_iter()
+                .map(|value| to_fixed_packed_hex(1.0 + value))
+                .collect(),
+            min_return: to_fixed_packed_hex(1.0 + tail_risk.min_return),
+            max_drawdown: to_fixed_packed_hex(tail_risk.max_drawdown),
+        }),
+
+        // Model state behind the reserve price, for display next to it
+        fee_model: data.fee_model,
+        mrj_params: mrj_params.map(|mrj_params| MrjParamsOutput {
+            alpha: to_signed_fixed_packed_hex(mrj_params.alpha),     // Mean reversion level times kappa
+            kappa: to_signed_fixed_packed_hex(mrj_params.kappa),     // Mean reversion speed
+            mu_j: to_signed_fixed_packed_hex(mrj_params.mu_j),       // Mean jump size
+            sigma: to_signed_fixed_packed_hex(mrj_params.sigma),     // Diffusion volatility
+            sigma_j: to_signed_fixed_packed_hex(mrj_params.sigma_j), // Jump size volatility
+            lambda: to_signed_fixed_packed_hex(mrj_params.lambda),   // Jump intensity
+        }),
+        realized_volatility: to_fixed_packed_hex(realized_volatility),
     };
 
     // ========== STEP 11: COMMIT OUTPUT TO THE PROOF ==========
@@ -271,8 +644,9 @@ fn main() {
     // The public journal contains:
     // - Data hash (to verify data integrity)
     // - Reserve price (the key result)
-    // - TWAP (for reference pricing)
-    // - Max return (for volatility assessment)
+    // - TWAP (for reference pricing) and the TWAP definition it was proven under
+    // - Max return (for volatility assessment) and, if verified, its tail-risk metrics
+    // - MRJ parameters and realized volatility (the model state behind the reserve price)
     // - All relevant timestamps and tolerances
     //
     // Anyone can verify this proof on-chain and trust these results without
@@ -1,5 +1,6 @@
 use common::floating_point::{calculate_remove_seasonality, error_bound_dvec, error_bound_f64};
-use core::RemoveSeasonalityErrorBoundFloatingInput;
+use common::hash_f64s;
+use core::{RemoveSeasonalityErrorBoundFloatingInput, RemoveSeasonalityErrorBoundFloatingOutput};
 use risc0_zkvm::guest::env;
 
 fn main() {
@@ -24,5 +25,14 @@ fn main() {
     let is_within_tolerance_intercept = error_bound_f64(data.intercept, intercept, data.tolerance);
     assert!(is_within_tolerance_intercept);
 
-    env::commit(&data);
+    env::commit(&RemoveSeasonalityErrorBoundFloatingOutput {
+        data_digest: hash_f64s(&data.data),
+        slope: data.slope,
+        intercept: data.intercept,
+        de_seasonalised_detrended_log_base_fee_digest: hash_f64s(
+            data.de_seasonalised_detrended_log_base_fee.as_slice(),
+        ),
+        season_param_digest: hash_f64s(data.season_param.as_slice()),
+        tolerance: data.tolerance,
+    });
 }
@@ -0,0 +1,27 @@
+use common::floating_point::{calculate_median, error_bound_f64};
+use common::hash_f64s;
+use core::{RollingMedianErrorBoundInput, RollingMedianErrorBoundOutput};
+use risc0_zkvm::guest::env;
+
+fn main() {
+    let data: RollingMedianErrorBoundInput = env::read();
+    let fees = &data.avg_hourly_gas_fee;
+    assert!(data.window > 0 && data.window <= fees.len());
+
+    // the last value of the rolling median, without computing the earlier ones
+    let rolling_median_result = calculate_median(&fees[fees.len() - data.window..]).unwrap();
+
+    let is_within_error_bound = error_bound_f64(
+        rolling_median_result,
+        data.rolling_median_result,
+        data.tolerance,
+    );
+    assert!(is_within_error_bound);
+
+    env::commit(&RollingMedianErrorBoundOutput {
+        avg_hourly_gas_fee_digest: hash_f64s(fees),
+        window: data.window,
+        tolerance: data.tolerance,
+        rolling_median_result: data.rolling_median_result,
+    });
+}
@@ -1,15 +1,32 @@
 use common::floating_point::{
-    calculate_reserve_price, error_bound_f64, simulate_price_verify_position,
+    calculate_simulated_log_prices, calculated_reserve_price_for_round,
+    calculated_reserve_price_grid_from_simulated_log_prices, error_bound_f64,
+    error_bound_reserve_price_grid, simulate_price_verify_position, FeeModelKind, MrjParams,
 };
-use core::SimulatePriceVerifyPositionInput;
+use common::hash_f64s;
+use common::window::round_horizon;
+use core::{SimulatePriceVerifyPositionInput, SimulatePriceVerifyPositionOutput};
 use risc0_zkvm::guest::env;
 
 // TODO: error bound check for reserve_price
 fn main() {
     let data: SimulatePriceVerifyPositionInput = env::read();
 
+    // The horizon is one period per hour of the round, not a free parameter
+    let horizon = round_horizon(
+        data.round_start_timestamp,
+        data.round_end_timestamp,
+        &data.settlement,
+    )
+    .unwrap();
+    assert_eq!(
+        data.n_periods, horizon.n_periods,
+        "n_periods must be the number of hours in the round"
+    );
+
     let (is_saddle_point, de_seasonalized_detrended_simulated_prices) =
         simulate_price_verify_position(
+            data.fee_model,
             &data.positions,
             &data.pt,
             &data.pt_1,
@@ -21,7 +38,7 @@ fn main() {
 
     assert!(is_saddle_point);
 
-    let reserve_price = calculate_reserve_price(
+    let simulated_log_prices = calculate_simulated_log_prices(
         data.start_timestamp,
         data.end_timestamp,
         &data.season_param,
@@ -35,9 +52,66 @@ fn main() {
     )
     .unwrap();
 
+    let reserve_price = calculated_reserve_price_for_round(
+        &simulated_log_prices,
+        &data.twap_7d,
+        &horizon,
+        &data.payoff,
+    )
+    .unwrap();
+
     let is_within_tolerance_reserve_price =
         error_bound_f64(reserve_price, data.reserve_price, data.tolerance);
     assert!(is_within_tolerance_reserve_price);
 
-    env::commit(&data);
+    // Other strikes and horizons, priced on the same simulated log prices
+    if let Some(reserve_price_grid) = &data.reserve_price_grid {
+        let calculated_grid = calculated_reserve_price_grid_from_simulated_log_prices(
+            &simulated_log_prices,
+            &data.twap_7d,
+            &reserve_price_grid.strike_levels,
+            &reserve_price_grid.horizons,
+            &data.payoff,
+            &data.settlement,
+        )
+        .unwrap();
+        assert!(error_bound_reserve_price_grid(
+            reserve_price_grid,
+            &calculated_grid,
+            data.tolerance
+        ));
+    }
+
+    env::commit(&SimulatePriceVerifyPositionOutput {
+        start_timestamp: data.start_timestamp,
+        end_timestamp: data.end_timestamp,
+        round_start_timestamp: data.round_start_timestamp,
+        round_end_timestamp: data.round_end_timestamp,
+        settlement: data.settlement,
+        fee_model: data.fee_model,
+        positions_digest: hash_f64s(&data.positions),
+        pt_digest: hash_f64s(data.pt.as_slice()),
+        pt_1_digest: hash_f64s(data.pt_1.as_slice()),
+        gradient_tolerance: data.gradient_tolerance,
+        de_seasonalised_detrended_log_base_fee_digest: hash_f64s(
+            data.de_seasonalised_detrended_log_base_fee.as_slice(),
+        ),
+        n_periods: data.n_periods,
+        num_paths: data.num_paths,
+        season_param_digest: hash_f64s(data.season_param.as_slice()),
+        twap_7d_digest: hash_f64s(&data.twap_7d),
+        slope: data.slope,
+        intercept: data.intercept,
+        payoff: data.payoff,
+        reserve_price: data.reserve_price,
+        reserve_price_grid: data.reserve_price_grid,
+        tolerance: data.tolerance,
+        data_length: data.data_length,
+        mrj_params: match data.fee_model {
+            FeeModelKind::MeanRevertingJump => {
+                Some(MrjParams::from_positions(&data.positions).unwrap())
+            }
+            _ => None,
+        },
+    });
 }
@@ -0,0 +1,26 @@
+use common::floating_point::calculate_tail_risk;
+use common::hash_f64s;
+use core::{TailRiskInput, TailRiskOutput};
+use risc0_zkvm::guest::env;
+
+fn main() {
+    let input: TailRiskInput = env::read();
+
+    let tail_risk = calculate_tail_risk(&input.data, &input.quantiles).unwrap();
+    env::commit(&TailRiskOutput {
+        data_digest: hash_f64s(&input.data),
+        quantiles: input.quantiles,
+        value_at_risk: tail_risk
+            .quantiles
+            .iter()
+            .map(|quantile| quantile.value_at_risk)
+            .collect(),
+        conditional_value_at_risk: tail_risk
+            .quantiles
+            .iter()
+            .map(|quantile| quantile.conditional_value_at_risk)
+            .collect(),
+        min_return: tail_risk.min_return,
+        max_drawdown: tail_risk.max_drawdown,
+    });
+}
@@ -0,0 +1,32 @@
+use common::floating_point::{
+    block_base_fee_samples, calculate_time_weighted_twap, error_bound_f64,
+};
+use common::hash_blocks;
+use core::{TimeWeightedTwapErrorBoundInput, TimeWeightedTwapErrorBoundOutput};
+use risc0_zkvm::guest::env;
+
+fn main() {
+    let data: TimeWeightedTwapErrorBoundInput = env::read();
+
+    let blocks = data
+        .blocks
+        .iter()
+        .map(|x| (x.number, x.timestamp, x.base_fee))
+        .collect::<Vec<_>>();
+
+    // the blocks must be consecutive and cover the window, so none of its fees is left out
+    let samples =
+        block_base_fee_samples(&blocks, data.start_timestamp, data.end_timestamp).unwrap();
+    let twap_result = calculate_time_weighted_twap(&samples, data.end_timestamp).unwrap();
+
+    let is_within_error_bound = error_bound_f64(twap_result, data.twap_result, data.twap_tolerance);
+    assert!(is_within_error_bound);
+
+    env::commit(&TimeWeightedTwapErrorBoundOutput {
+        blocks_digest: hash_blocks(&blocks),
+        start_timestamp: data.start_timestamp,
+        end_timestamp: data.end_timestamp,
+        twap_tolerance: data.twap_tolerance,
+        twap_result: data.twap_result,
+    });
+}
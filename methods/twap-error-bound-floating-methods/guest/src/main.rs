@@ -1,5 +1,6 @@
 use common::floating_point::{calculate_twap, error_bound_f64};
-use core::TwapErrorBoundInput;
+use common::hash_f64s;
+use core::{TwapErrorBoundInput, TwapErrorBoundOutput};
 use risc0_zkvm::guest::env;
 
 fn main() {
@@ -9,5 +10,9 @@ fn main() {
     let is_within_error_bound = error_bound_f64(twap_result, data.twap_result, data.twap_tolerance);
     assert!(is_within_error_bound);
 
-    env::commit(&data);
+    env::commit(&TwapErrorBoundOutput {
+        avg_hourly_gas_fee_digest: hash_f64s(&data.avg_hourly_gas_fee),
+        twap_tolerance: data.twap_tolerance,
+        twap_result: data.twap_result,
+    });
 }
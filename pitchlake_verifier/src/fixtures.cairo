@@ -6,13 +6,25 @@ pub const JOURNAL_DECODED: Journal = Journal {
     ],
     start_timestamp: 1672531200,
     end_timestamp: 1704067200,
+    reserve_price_start_timestamp: 1672531200,
+    reserve_price_end_timestamp: 1704067200,
     reserve_price: 0x0000000000000000000000000000000280000000000000000000000000000000,
+    twap_start_timestamp: 1672531200,
+    twap_end_timestamp: 1704067200,
+    twap_result: 0x0000000000000000000000000000000140000000000000000000000000000000,
+    max_return_start_timestamp: 1672531200,
+    max_return_end_timestamp: 1704067200,
+    max_return: 0x000000000000000000000000000000004ccccccccccccc000000000000000000,
     floating_point_tolerance: 0x0000000000000000000000000000000000068db8bac710cb4000000000000000,
     reserve_price_tolerance: 0x00000000000000000000000000000000028f5c28f5c28f600000000000000000,
     twap_tolerance: 0x000000000000000000000000000000000ccccccccccccd000000000000000000,
     gradient_tolerance: 0x00000000000000000000000000000000004189374bc6a7f00000000000000000,
-    twap_result: 0x0000000000000000000000000000000140000000000000000000000000000000,
-    max_return: 0x000000000000000000000000000000004ccccccccccccc000000000000000000,
+    round_start_timestamp: 1704067200,
+    round_end_timestamp: 1706659200,
+    settlement_twap_window_hours: 168,
+    data_8_months_merkle_root: Option::Some(
+        [2596069104, 2882400001, 3168727058, 3454992675, 3740275252, 4009829189, 4027724886, 19088743],
+    ),
 };
 
 pub fn JOURNAL_BYTES() -> Array<u8> {
@@ -65,6 +77,22 @@ pub fn JOURNAL_BYTES() -> Array<u8> {
         0,
         0,
         0,
+        0,
+        205,
+        176,
+        99,
+        0,
+        0,
+        0,
+        0,
+        128,
+        0,
+        146,
+        101,
+        0,
+        0,
+        0,
+        0,
         66,
         0,
         0,
@@ -137,6 +165,22 @@ pub fn JOURNAL_BYTES() -> Array<u8> {
         48,
         0,
         0,
+        0,
+        205,
+        176,
+        99,
+        0,
+        0,
+        0,
+        0,
+        128,
+        0,
+        146,
+        101,
+        0,
+        0,
+        0,
+        0,
         66,
         0,
         0,
@@ -174,24 +218,18 @@ pub fn JOURNAL_BYTES() -> Array<u8> {
         48,
         48,
         48,
+        49,
+        52,
+        48,
+        48,
+        48,
+        48,
+        48,
         48,
         48,
         48,
         48,
-        54,
-        56,
-        100,
-        98,
-        56,
-        98,
-        97,
-        99,
-        55,
-        49,
         48,
-        99,
-        98,
-        52,
         48,
         48,
         48,
@@ -207,6 +245,28 @@ pub fn JOURNAL_BYTES() -> Array<u8> {
         48,
         48,
         48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        0,
+        0,
+        0,
+        205,
+        176,
+        99,
+        0,
+        0,
+        0,
+        0,
+        128,
+        0,
+        146,
+        101,
+        0,
+        0,
         0,
         0,
         66,
@@ -247,21 +307,21 @@ pub fn JOURNAL_BYTES() -> Array<u8> {
         48,
         48,
         48,
-        48,
-        50,
-        56,
-        102,
-        53,
+        52,
         99,
-        50,
-        56,
-        102,
-        53,
         99,
-        50,
-        56,
-        102,
-        54,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        48,
         48,
         48,
         48,
@@ -320,22 +380,22 @@ pub fn JOURNAL_BYTES() -> Array<u8> {
         48,
         48,
         48,
-        99,
-        99,
-        99,
-        99,
-        99,
-        99,
-        99,
-        99,
-        99,
-        99,
-        99,
-        99,
-        100,
         48,
         48,
+        54,
+        56,
+        100,
+        98,
+        56,
+        98,
+        97,
+        99,
+        55,
+        49,
         48,
+        99,
+        98,
+        52,
         48,
         48,
         48,
@@ -392,20 +452,20 @@ pub fn JOURNAL_BYTES() -> Array<u8> {
         48,
         48,
         48,
-        48,
-        52,
-        49,
+        50,
         56,
-        57,
-        51,
-        55,
-        52,
-        98,
+        102,
+        53,
         99,
-        54,
-        97,
-        55,
+        50,
+        56,
+        102,
+        53,
+        99,
+        50,
+        56,
         102,
+        54,
         48,
         48,
         48,
@@ -462,8 +522,24 @@ pub fn JOURNAL_BYTES() -> Array<u8> {
         48,
         48,
         48,
-        49,
-        52,
+        48,
+        48,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        100,
+        48,
+        48,
+        48,
         48,
         48,
         48,
@@ -479,6 +555,16 @@ pub fn JOURNAL_BYTES() -> Array<u8> {
         48,
         48,
         48,
+        0,
+        0,
+        66,
+        0,
+        0,
+        0,
+        48,
+        120,
+        48,
+        48,
         48,
         48,
         48,
@@ -495,14 +581,7 @@ pub fn JOURNAL_BYTES() -> Array<u8> {
         48,
         48,
         48,
-        0,
-        0,
-        66,
-        0,
-        0,
-        0,
         48,
-        120,
         48,
         48,
         48,
@@ -518,6 +597,19 @@ pub fn JOURNAL_BYTES() -> Array<u8> {
         48,
         48,
         48,
+        52,
+        49,
+        56,
+        57,
+        51,
+        55,
+        52,
+        98,
+        99,
+        54,
+        97,
+        55,
+        102,
         48,
         48,
         48,
@@ -535,20 +627,700 @@ pub fn JOURNAL_BYTES() -> Array<u8> {
         48,
         48,
         48,
+        0,
+        0,
+        128,
+        0,
+        146,
+        101,
+        0,
+        0,
+        0,
+        0,
+        128,
+        141,
+        185,
+        101,
+        0,
+        0,
+        0,
+        0,
+        168,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        1,
+        0,
+        0,
+        0,
+        240,
+        222,
+        188,
+        154,
+        1,
+        239,
+        205,
+        171,
+        18,
+        240,
+        222,
+        188,
+        35,
+        1,
+        239,
+        205,
         52,
-        99,
-        99,
-        99,
-        99,
-        99,
-        99,
-        99,
-        99,
-        99,
-        99,
-        99,
-        99,
-        99,
+        18,
+        240,
+        222,
+        69,
+        35,
+        1,
+        239,
+        86,
+        52,
+        18,
+        240,
+        103,
+        69,
+        35,
+        1,
+        0,
+        0,
+        0,
+        0,
+        66,
+        0,
+        0,
+        0,
+        48,
+        120,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        52,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        99,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        180,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        1,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        66,
+        0,
+        0,
+        0,
+        48,
+        120,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        56,
+        99,
+        50,
+        56,
+        102,
+        53,
+        99,
+        50,
+        56,
+        102,
+        53,
+        99,
+        50,
+        56,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        66,
+        0,
+        0,
+        0,
+        48,
+        120,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        49,
+        98,
+        54,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        55,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        66,
+        0,
+        0,
+        0,
+        48,
+        120,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        49,
+        57,
+        57,
+        57,
+        57,
+        57,
+        57,
+        57,
+        57,
+        57,
+        57,
+        57,
+        57,
+        97,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        66,
+        0,
+        0,
+        0,
+        48,
+        120,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        57,
+        53,
+        99,
+        48,
+        55,
+        53,
+        57,
+        100,
+        50,
+        101,
+        51,
+        54,
+        53,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        66,
+        0,
+        0,
+        0,
+        48,
+        120,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        51,
+        57,
+        51,
+        101,
+        52,
+        98,
+        56,
+        98,
+        55,
+        102,
+        100,
+        98,
+        98,
+        50,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        66,
+        0,
+        0,
+        0,
+        48,
+        120,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        97,
+        102,
+        51,
+        51,
+        51,
+        51,
+        51,
+        51,
+        51,
+        51,
+        51,
+        51,
+        51,
+        56,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        0,
+        0,
+        66,
+        0,
+        0,
+        0,
+        48,
+        120,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        48,
+        49,
+        51,
+        55,
+        56,
+        57,
+        55,
+        56,
+        52,
+        97,
+        53,
+        102,
+        50,
+        97,
+        53,
+        48,
+        48,
         48,
         48,
         48,
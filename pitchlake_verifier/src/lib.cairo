@@ -31,18 +31,30 @@ pub struct PitchLakeJobRequest {
     pub program_id: felt252 // 'PITCH_LAKE_V1'}
 }
 
+// Leading fields of the `ProofCompositionOutput` journal, in the same order. The fields
+// committed after `data_8_months_merkle_root` are not decoded.
 #[derive(Drop, Debug, Copy, PartialEq, Serde)]
 pub struct Journal {
     pub data_8_months_hash: [u32; 8],
     pub start_timestamp: u64,
     pub end_timestamp: u64,
+    pub reserve_price_start_timestamp: u64,
+    pub reserve_price_end_timestamp: u64,
     pub reserve_price: felt252,
+    pub twap_start_timestamp: u64,
+    pub twap_end_timestamp: u64,
+    pub twap_result: felt252,
+    pub max_return_start_timestamp: u64,
+    pub max_return_end_timestamp: u64,
+    pub max_return: felt252,
     pub floating_point_tolerance: felt252,
     pub reserve_price_tolerance: felt252,
     pub twap_tolerance: felt252,
     pub gradient_tolerance: felt252,
-    pub twap_result: felt252,
-    pub max_return: felt252,
+    pub round_start_timestamp: u64,
+    pub round_end_timestamp: u64,
+    pub settlement_twap_window_hours: u64,
+    pub data_8_months_merkle_root: Option<[u32; 8]>,
 }
 
 #[derive(Drop, Debug, Copy, PartialEq, Serde)]
@@ -53,123 +65,109 @@ pub struct AvgFees {
 }
 
 pub fn decode_journal(journal_bytes: Span<u8>) -> Journal {
-    // Parse data_8_months_hash (32 bytes total, as 8 u32 values)
-    // First u32 (bytes 0-3)
-    let val0: u32 = (*journal_bytes.at(0)).into()
-        + (BitShift::shl((*journal_bytes.at(1)).into(), 8))
-        + (BitShift::shl((*journal_bytes.at(2)).into(), 16))
-        + (BitShift::shl((*journal_bytes.at(3)).into(), 24));
-
-    // Second u32 (bytes 4-7)
-    let val1: u32 = (*journal_bytes.at(4)).into()
-        + (BitShift::shl((*journal_bytes.at(5)).into(), 8))
-        + (BitShift::shl((*journal_bytes.at(6)).into(), 16))
-        + (BitShift::shl((*journal_bytes.at(7)).into(), 24));
-
-    // Third u32 (bytes 8-11)
-    let val2: u32 = (*journal_bytes.at(8)).into()
-        + (BitShift::shl((*journal_bytes.at(9)).into(), 8))
-        + (BitShift::shl((*journal_bytes.at(10)).into(), 16))
-        + (BitShift::shl((*journal_bytes.at(11)).into(), 24));
-
-    // Fourth u32 (bytes 12-15)
-    let val3: u32 = (*journal_bytes.at(12)).into()
-        + (BitShift::shl((*journal_bytes.at(13)).into(), 8))
-        + (BitShift::shl((*journal_bytes.at(14)).into(), 16))
-        + (BitShift::shl((*journal_bytes.at(15)).into(), 24));
-
-    // Fifth u32 (bytes 16-19)
-    let val4: u32 = (*journal_bytes.at(16)).into()
-        + (BitShift::shl((*journal_bytes.at(17)).into(), 8))
-        + (BitShift::shl((*journal_bytes.at(18)).into(), 16))
-        + (BitShift::shl((*journal_bytes.at(19)).into(), 24));
-
-    // Sixth u32 (bytes 20-23)
-    let val5: u32 = (*journal_bytes.at(20)).into()
-        + (BitShift::shl((*journal_bytes.at(21)).into(), 8))
-        + (BitShift::shl((*journal_bytes.at(22)).into(), 16))
-        + (BitShift::shl((*journal_bytes.at(23)).into(), 24));
-
-    // Seventh u32 (bytes 24-27)
-    let val6: u32 = (*journal_bytes.at(24)).into()
-        + (BitShift::shl((*journal_bytes.at(25)).into(), 8))
-        + (BitShift::shl((*journal_bytes.at(26)).into(), 16))
-        + (BitShift::shl((*journal_bytes.at(27)).into(), 24));
-
-    // Eighth u32 (bytes 28-31)
-    let val7: u32 = (*journal_bytes.at(28)).into()
-        + (BitShift::shl((*journal_bytes.at(29)).into(), 8))
-        + (BitShift::shl((*journal_bytes.at(30)).into(), 16))
-        + (BitShift::shl((*journal_bytes.at(31)).into(), 24));
-
-    // Create array with the extracted values
-    let data_8_months_hash = [val0, val1, val2, val3, val4, val5, val6, val7];
-
-    // Parse start_timestamp (8 bytes)
-    let mut byte_offset = 32; // After data_8_months_hash
-    let mut start_timestamp: u64 = 0;
-    let mut byte_idx = 0;
-    while byte_idx < U64_SIZE {
-        let current_byte: u64 = (*journal_bytes.at(byte_offset + byte_idx)).into();
-        let shifted_byte: u64 = BitShift::shl(current_byte, (8 * byte_idx).into());
-        start_timestamp += shifted_byte;
-        byte_idx += 1;
-    }
+    // The journal is serialized with the risc0 serde: u32 words in little endian, u64 and
+    // i64 as two words, and strings as their byte length followed by their bytes padded
+    // to a word.
 
-    // Parse end_timestamp (8 bytes)
-    byte_offset += U64_SIZE;
-    let mut end_timestamp: u64 = 0;
-    let mut byte_idx = 0;
-    while byte_idx < U64_SIZE {
-        let current_byte: u64 = (*journal_bytes.at(byte_offset + byte_idx)).into();
-        let shifted_byte: u64 = BitShift::shl(current_byte, (8 * byte_idx).into());
-        end_timestamp += shifted_byte;
-        byte_idx += 1;
-    }
+    let (data_8_months_hash, byte_offset) = parse_hash(journal_bytes, 0);
 
-    // Parse all floating point values
-    byte_offset += U64_SIZE;
+    let (start_timestamp, byte_offset) = parse_u64(journal_bytes, byte_offset);
+    let (end_timestamp, byte_offset) = parse_u64(journal_bytes, byte_offset);
 
-    // Parse reserve_price (8 bytes)
+    let (reserve_price_start_timestamp, byte_offset) = parse_u64(journal_bytes, byte_offset);
+    let (reserve_price_end_timestamp, byte_offset) = parse_u64(journal_bytes, byte_offset);
     let (reserve_price, byte_offset) = parse_packed_fixed_point(journal_bytes, byte_offset);
 
-    // Parse floating_point_tolerance (8 bytes)
+    let (twap_start_timestamp, byte_offset) = parse_u64(journal_bytes, byte_offset);
+    let (twap_end_timestamp, byte_offset) = parse_u64(journal_bytes, byte_offset);
+    let (twap_result, byte_offset) = parse_packed_fixed_point(journal_bytes, byte_offset);
+
+    let (max_return_start_timestamp, byte_offset) = parse_u64(journal_bytes, byte_offset);
+    let (max_return_end_timestamp, byte_offset) = parse_u64(journal_bytes, byte_offset);
+    let (max_return, byte_offset) = parse_packed_fixed_point(journal_bytes, byte_offset);
+
     let (floating_point_tolerance, byte_offset) = parse_packed_fixed_point(
         journal_bytes, byte_offset,
     );
-
-    // Parse reserve_price_tolerance (8 bytes)
     let (reserve_price_tolerance, byte_offset) = parse_packed_fixed_point(
         journal_bytes, byte_offset,
     );
-
-    // Parse twap_tolerance (8 bytes)
     let (twap_tolerance, byte_offset) = parse_packed_fixed_point(journal_bytes, byte_offset);
-
-    // Parse gradient_tolerance (8 bytes)
     let (gradient_tolerance, byte_offset) = parse_packed_fixed_point(journal_bytes, byte_offset);
 
-    // Parse twap_result (8 bytes)
-    let (twap_result, byte_offset) = parse_packed_fixed_point(journal_bytes, byte_offset);
-
-    // Parse max_return (8 bytes)
-    let (max_return, _) = parse_packed_fixed_point(journal_bytes, byte_offset);
+    let (round_start_timestamp, byte_offset) = parse_u64(journal_bytes, byte_offset);
+    let (round_end_timestamp, byte_offset) = parse_u64(journal_bytes, byte_offset);
+    // `SettlementSpec` only holds the settlement window, a usize
+    let (settlement_twap_window_hours, byte_offset) = parse_u64(journal_bytes, byte_offset);
+    // Option tag: 0 for None, 1 for Some followed by the hash
+    let (merkle_root_tag, byte_offset) = parse_u32(journal_bytes, byte_offset);
+    let data_8_months_merkle_root = if merkle_root_tag == 0 {
+        Option::None
+    } else {
+        let (merkle_root, _) = parse_hash(journal_bytes, byte_offset);
+        Option::Some(merkle_root)
+    };
 
     Journal {
         data_8_months_hash,
         start_timestamp,
         end_timestamp,
+        reserve_price_start_timestamp,
+        reserve_price_end_timestamp,
         reserve_price,
+        twap_start_timestamp,
+        twap_end_timestamp,
+        twap_result,
+        max_return_start_timestamp,
+        max_return_end_timestamp,
+        max_return,
         floating_point_tolerance,
         reserve_price_tolerance,
         twap_tolerance,
         gradient_tolerance,
-        twap_result,
-        max_return,
+        round_start_timestamp,
+        round_end_timestamp,
+        settlement_twap_window_hours,
+        data_8_months_merkle_root,
+    }
+}
+
+// Helper function to parse a hash (32 bytes total, as 8 u32 values)
+fn parse_hash(journal_bytes: Span<u8>, byte_offset: usize) -> ([u32; 8], usize) {
+    let (val0, byte_offset) = parse_u32(journal_bytes, byte_offset);
+    let (val1, byte_offset) = parse_u32(journal_bytes, byte_offset);
+    let (val2, byte_offset) = parse_u32(journal_bytes, byte_offset);
+    let (val3, byte_offset) = parse_u32(journal_bytes, byte_offset);
+    let (val4, byte_offset) = parse_u32(journal_bytes, byte_offset);
+    let (val5, byte_offset) = parse_u32(journal_bytes, byte_offset);
+    let (val6, byte_offset) = parse_u32(journal_bytes, byte_offset);
+    let (val7, byte_offset) = parse_u32(journal_bytes, byte_offset);
+    ([val0, val1, val2, val3, val4, val5, val6, val7], byte_offset)
+}
+
+// Helper function to parse 4 little endian bytes into a u32 value
+fn parse_u32(journal_bytes: Span<u8>, byte_offset: usize) -> (u32, usize) {
+    let value: u32 = (*journal_bytes.at(byte_offset)).into()
+        + (BitShift::shl((*journal_bytes.at(byte_offset + 1)).into(), 8))
+        + (BitShift::shl((*journal_bytes.at(byte_offset + 2)).into(), 16))
+        + (BitShift::shl((*journal_bytes.at(byte_offset + 3)).into(), 24));
+    (value, byte_offset + U32_SIZE)
+}
+
+// Helper function to parse 8 little endian bytes into a u64 value
+fn parse_u64(journal_bytes: Span<u8>, byte_offset: usize) -> (u64, usize) {
+    let mut value: u64 = 0;
+    let mut byte_idx = 0;
+    while byte_idx < U64_SIZE {
+        let current_byte: u64 = (*journal_bytes.at(byte_offset + byte_idx)).into();
+        let shifted_byte: u64 = BitShift::shl(current_byte, (8 * byte_idx).into());
+        value += shifted_byte;
+        byte_idx += 1;
     }
+    (value, byte_offset + U64_SIZE)
 }
 
-// Helper function to parse 8 bytes into a UFixedPoint123x128 value
+// Helper function to parse a packed UFixedPoint123x128 hex string into its felt252 value
 fn parse_packed_fixed_point(journal_bytes: Span<u8>, mut byte_offset: usize) -> (felt252, usize) {
     byte_offset += U32_SIZE; // Skip length indicator (66, 0, 0, 0)
     byte_offset += HEX_PREFIX_SIZE; // Skip "0x" prefix
@@ -191,6 +189,7 @@ fn parse_packed_fixed_point(journal_bytes: Span<u8>, mut byte_offset: usize) ->
         value = shifted_hash + hex_byte - hex_base;
         hex_idx += 1;
     }
+    // The 64 hex chars and the 2 bytes padding the 66 byte string to a word
     byte_offset += HEX_HASH_WITH_PREFIX_SIZE;
 
     let felt_value: felt252 = value.try_into().unwrap();
@@ -261,664 +260,19 @@ fn pow<T, +Sub<T>, +Mul<T>, +Div<T>, +Rem<T>, +PartialEq<T>, +Into<u8, T>, +Drop
 #[cfg(test)]
 mod tests {
     use fp::{UFixedPoint123x128StorePacking as SP, UFixedPointTrait};
+    use super::fixtures::{JOURNAL_BYTES, JOURNAL_DECODED};
     use super::*;
 
-    #[derive(Drop, Debug, Copy, PartialEq, Serde)]
-    pub struct TestJournal {
-        pub data_8_months_hash: [u32; 8],
-        pub start_timestamp: u64,
-        pub end_timestamp: u64,
-        pub reserve_price: u256,
-        pub floating_point_tolerance: u256,
-        pub reserve_price_tolerance: u256,
-        pub twap_tolerance: u256,
-        pub gradient_tolerance: u256,
-        pub twap_result: u256,
-        pub max_return: u256,
-    }
-
     #[test]
     fn decode_journal_test() {
-        let journal_bytes = get_journal_bytes();
-        let journal = decode_journal(journal_bytes);
-        let expected_journal = get_expected_results();
-        // Verify data_8_months_hash (converting to the expected array of u32)
+        let journal = decode_journal(JOURNAL_BYTES().span());
+        assert_eq!(journal, JOURNAL_DECODED);
 
-        assert_eq!(journal.data_8_months_hash, expected_journal.data_8_months_hash);
-
-        // Verify timestamps
-        assert_eq!(journal.start_timestamp, expected_journal.start_timestamp);
-        assert_eq!(journal.end_timestamp, expected_journal.end_timestamp);
-
-        // For the floating point values, we would need to check the bit representation
-        // or approximate values, depending on how UFixedPoint123x128 stores values
-        assert_eq!(
-            SP::unpack(journal.reserve_price).get_integer(), expected_journal.reserve_price.high,
-        );
-        assert_eq!(
-            SP::unpack(journal.reserve_price).get_fractional(), expected_journal.reserve_price.low,
-        );
-        assert_eq!(
-            SP::unpack(journal.floating_point_tolerance).get_integer(),
-            expected_journal.floating_point_tolerance.high,
-        );
-        assert_eq!(
-            SP::unpack(journal.floating_point_tolerance).get_fractional(),
-            expected_journal.floating_point_tolerance.low,
-        );
-        assert_eq!(
-            SP::unpack(journal.reserve_price_tolerance).get_integer(),
-            expected_journal.reserve_price_tolerance.high,
-        );
-        assert_eq!(
-            SP::unpack(journal.reserve_price_tolerance).get_fractional(),
-            expected_journal.reserve_price_tolerance.low,
-        );
+        // 2.5 as UFixedPoint123x128
+        assert_eq!(SP::unpack(journal.reserve_price).get_integer(), 2);
         assert_eq!(
-            SP::unpack(journal.twap_tolerance).get_integer(), expected_journal.twap_tolerance.high,
+            SP::unpack(journal.reserve_price).get_fractional(), 0x80000000000000000000000000000000,
         );
-        assert_eq!(
-            SP::unpack(journal.twap_tolerance).get_fractional(),
-            expected_journal.twap_tolerance.low,
-        );
-        assert_eq!(
-            SP::unpack(journal.gradient_tolerance).get_integer(),
-            expected_journal.gradient_tolerance.high,
-        );
-        assert_eq!(
-            SP::unpack(journal.gradient_tolerance).get_fractional(),
-            expected_journal.gradient_tolerance.low,
-        );
-        assert_eq!(
-            SP::unpack(journal.twap_result).get_integer(), expected_journal.twap_result.high,
-        );
-        assert_eq!(
-            UFixedPoint123x128StorePacking::unpack(journal.twap_result).get_fractional(),
-            expected_journal.twap_result.low,
-        );
-        assert_eq!(
-            UFixedPoint123x128StorePacking::unpack(journal.max_return).get_integer(),
-            expected_journal.max_return.high,
-        );
-        assert_eq!(
-            UFixedPoint123x128StorePacking::unpack(journal.max_return).get_fractional(),
-            expected_journal.max_return.low,
-        );
-    }
-
-    fn get_expected_results() -> TestJournal {
-        TestJournal {
-            data_8_months_hash: [
-                176682157, 3315611904, 69122759, 3259044264, 1698705339, 1448440140, 3846648702,
-                370555961,
-            ],
-            start_timestamp: 1708833600,
-            end_timestamp: 1716609600,
-            reserve_price: u256 { high: 2436485959, low: 159863518606830028081101360966223790080 },
-            floating_point_tolerance: u256 { high: 0, low: 3402823669209384912995114146594816 },
-            reserve_price_tolerance: u256 { high: 5, low: 0 },
-            twap_tolerance: u256 { high: 1, low: 0 },
-            gradient_tolerance: u256 { high: 0, low: 17014118346046924117642026945517453312 },
-            twap_result: u256 { high: 14346521680, low: 192471954174812891655089835803777433600 },
-            max_return: u256 { high: 1, low: 183365823839893747160194852195351396352 },
-        }
-    }
-
-    fn get_journal_bytes() -> Span<u8> {
-        array![
-            173,
-            244,
-            135,
-            10,
-            0,
-            57,
-            160,
-            197,
-            199,
-            186,
-            30,
-            4,
-            168,
-            17,
-            65,
-            194,
-            187,
-            47,
-            64,
-            101,
-            76,
-            113,
-            85,
-            86,
-            126,
-            51,
-            71,
-            229,
-            57,
-            60,
-            22,
-            22,
-            64,
-            187,
-            218,
-            101,
-            0,
-            0,
-            0,
-            0,
-            64,
-            98,
-            81,
-            102,
-            0,
-            0,
-            0,
-            0,
-            66,
-            0,
-            0,
-            0,
-            48,
-            120,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            57,
-            49,
-            51,
-            57,
-            100,
-            51,
-            52,
-            55,
-            55,
-            56,
-            52,
-            52,
-            57,
-            56,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            0,
-            0,
-            66,
-            0,
-            0,
-            0,
-            48,
-            120,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            97,
-            55,
-            99,
-            53,
-            97,
-            99,
-            52,
-            55,
-            49,
-            98,
-            52,
-            55,
-            56,
-            56,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            0,
-            0,
-            66,
-            0,
-            0,
-            0,
-            48,
-            120,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            53,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            0,
-            0,
-            66,
-            0,
-            0,
-            0,
-            48,
-            120,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            49,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            0,
-            0,
-            66,
-            0,
-            0,
-            0,
-            48,
-            120,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            99,
-            99,
-            99,
-            99,
-            99,
-            99,
-            99,
-            99,
-            99,
-            99,
-            99,
-            99,
-            100,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            0,
-            0,
-            66,
-            0,
-            0,
-            0,
-            48,
-            120,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            51,
-            53,
-            55,
-            49,
-            101,
-            56,
-            99,
-            53,
-            48,
-            57,
-            48,
-            99,
-            99,
-            99,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            0,
-            0,
-            66,
-            0,
-            0,
-            0,
-            48,
-            120,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            49,
-            56,
-            57,
-            102,
-            50,
-            102,
-            57,
-            49,
-            99,
-            55,
-            101,
-            98,
-            53,
-            101,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            48,
-            0,
-            0,
-        ]
-            .span()
+        assert_eq!(journal.settlement_twap_window_hours, 168);
     }
 }
@@ -69,6 +69,8 @@ pub mod PitchLakeVerifier {
         gradient_tolerance: felt252,
         twap_result: felt252,
         max_return: felt252,
+        // Root that windows of the hashed fees are proven against, if known
+        data_8_months_merkle_root: Option<[u32; 8]>,
     }
 
     #[constructor]
@@ -138,6 +140,7 @@ pub mod PitchLakeVerifier {
                         gradient_tolerance: journal.gradient_tolerance,
                         twap_result: journal.twap_result,
                         max_return: journal.max_return,
+                        data_8_months_merkle_root: journal.data_8_months_merkle_root,
                     },
                 );
         }